@@ -0,0 +1,268 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::commands;
+use crate::commands::CommandError;
+use crate::economy;
+use crate::menus;
+use crate::time;
+
+type Arguments = commands::arguments::Arguments;
+type Expense = economy::expense::Expense;
+type Income = economy::income::Income;
+type Money = economy::money::Money;
+type AllActivities = economy::all_activities::AllActivities;
+type Tree = crate::concepts::tree::Tree;
+type Date = time::date::Date;
+type Month = time::date::Month;
+
+// Fields shared by expenses and incomes.
+struct CommonFields {
+	date: Date,
+	price: Money,
+	currency: String,
+	account: String,
+	concepts: Vec<String>,
+	description: String,
+}
+
+fn parse_common_fields(
+	args: &Arguments,
+	all_data: &AllActivities,
+	tree: &Tree,
+) -> Result<CommonFields, CommandError> {
+	let date_text = args.get_required("date")?;
+	let date = Date::parse_valid(date_text).map_err(|e| {
+		CommandError::Usage(format!(
+			"invalid value '{date_text}' of option '--date': {}",
+			e.reason
+		))
+	})?;
+
+	let price = args.parse_required::<Money>("price")?;
+	if price.is_negative() {
+		return Err(CommandError::InvalidData(format!(
+			"price '{price}' is negative"
+		)));
+	}
+
+	let concepts: Vec<String> = args
+		.get_required("concepts")?
+		.split(';')
+		.map(|c| c.trim().to_string())
+		.collect();
+	if !economy::validation::has_concepts(tree, &concepts) {
+		return Err(CommandError::InvalidData(format!(
+			"concepts '{}' do not exist",
+			concepts.join(";")
+		)));
+	}
+
+	let currency = match args.get_option("currency") {
+		Some(currency) => currency.to_uppercase(),
+		None => all_data.get_exchange_rates().get_home_currency().clone(),
+	};
+
+	let account = args.get_option("account").cloned().unwrap_or_default();
+	if !account.is_empty() && !all_data.get_accounts().has_account(&account) {
+		return Err(CommandError::InvalidData(format!(
+			"account '{account}' does not exist"
+		)));
+	}
+
+	let description = args.get_option("description").cloned().unwrap_or_default();
+
+	Ok(CommonFields {
+		date,
+		price: price.round_to_currency(&currency),
+		currency,
+		account,
+		concepts,
+		description,
+	})
+}
+
+pub fn add_expense(args: &Arguments, all_data: &mut AllActivities) -> Result<(), CommandError> {
+	args.check(
+		0,
+		&[
+			"date",
+			"price",
+			"concepts",
+			"shop",
+			"city",
+			"description",
+			"currency",
+			"account",
+			"force",
+		],
+	)?;
+	let common = parse_common_fields(args, all_data, all_data.get_expense_concepts().get_tree())?;
+	let shop = args.get_option("shop").cloned().unwrap_or_default();
+	let city = args.get_option("city").cloned().unwrap_or_default();
+
+	let expense = Expense {
+		day_of_year: common.date,
+		price: common.price,
+		currency: common.currency,
+		account: common.account,
+		concepts: common.concepts,
+		shop,
+		city,
+		description: common.description,
+		transaction_id: "".to_string(),
+		recurring: "".to_string(),
+	};
+
+	if !args.has_flag("force") {
+		if let Some((year, month, idx)) =
+			economy::duplicates::find_duplicate_expense(all_data, &expense)
+		{
+			return Err(CommandError::Duplicate(format!(
+				"the expense looks like the expense with id {idx} of {month} {year}"
+			)));
+		}
+	}
+
+	println!(
+		"Added expense of {} {} on {}.",
+		expense.price.display_in(&expense.currency),
+		expense.currency,
+		expense.day_of_year
+	);
+
+	let year_data = all_data.add_year(expense.day_of_year.year);
+	let month_data = year_data.get_expenses_mut().add(&expense.day_of_year.month);
+	month_data.push(expense);
+	Ok(())
+}
+
+pub fn add_income(args: &Arguments, all_data: &mut AllActivities) -> Result<(), CommandError> {
+	args.check(
+		0,
+		&[
+			"date",
+			"price",
+			"concepts",
+			"from",
+			"place",
+			"description",
+			"currency",
+			"account",
+			"force",
+		],
+	)?;
+	let common = parse_common_fields(args, all_data, all_data.get_income_concepts().get_tree())?;
+	let from = args.get_option("from").cloned().unwrap_or_default();
+	let place = args.get_option("place").cloned().unwrap_or_default();
+
+	let income = Income {
+		day_of_year: common.date,
+		price: common.price,
+		currency: common.currency,
+		account: common.account,
+		concepts: common.concepts,
+		from,
+		place,
+		description: common.description,
+		transaction_id: "".to_string(),
+		recurring: "".to_string(),
+	};
+
+	if !args.has_flag("force") {
+		if let Some((year, month, idx)) =
+			economy::duplicates::find_duplicate_income(all_data, &income)
+		{
+			return Err(CommandError::Duplicate(format!(
+				"the income looks like the income with id {idx} of {month} {year}"
+			)));
+		}
+	}
+
+	println!(
+		"Added income of {} {} on {}.",
+		income.price.display_in(&income.currency),
+		income.currency,
+		income.day_of_year
+	);
+
+	let year_data = all_data.add_year(income.day_of_year.year);
+	let month_data = year_data.get_incomes_mut().add(&income.day_of_year.month);
+	month_data.push(income);
+	Ok(())
+}
+
+#[duplicate::duplicate_item(
+	method          get_month            print_month                 print_year                 print_all;
+	[list_expenses] [get_month_expenses] [print_data_month_expenses] [print_data_year_expenses] [print_all_expenses];
+	[list_incomes]  [get_month_incomes]  [print_data_month_incomes]  [print_data_year_incomes]  [print_all_incomes];
+)]
+fn method(
+	all_data: &AllActivities,
+	year: Option<u32>,
+	month: Option<Month>,
+) -> Result<(), CommandError> {
+	let rates = all_data.get_exchange_rates();
+	match (year, month) {
+		(Some(year), Some(month)) => {
+			let Some(month_data) = all_data.get_month(&year, &month) else {
+				return Err(CommandError::NotFound(format!(
+					"there is no data for {month} {year}"
+				)));
+			};
+			menus::activities::print_month(month_data, rates);
+		},
+		(Some(year), None) => {
+			let Some(year_data) = all_data.get_year(&year) else {
+				return Err(CommandError::NotFound(format!(
+					"there is no data for {year}"
+				)));
+			};
+			menus::activities::print_year(year_data, rates);
+		},
+		_ => menus::activities::print_all(all_data),
+	}
+	Ok(())
+}
+
+pub fn list(args: &Arguments, all_data: &mut AllActivities) -> Result<(), CommandError> {
+	args.check(1, &["year", "month"])?;
+	let (year, month) = commands::parse_period(args)?;
+
+	match args.get_positional(0).map(|s| s.as_str()) {
+		None | Some("expenses") => list_expenses(all_data, year, month),
+		Some("incomes") => list_incomes(all_data, year, month),
+		Some(what) => Err(CommandError::Usage(format!(
+			"cannot list '{what}', only 'expenses' or 'incomes'"
+		))),
+	}
+}
@@ -0,0 +1,164 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+use crate::commands::CommandError;
+
+/**
+ * @brief Arguments of a subcommand.
+ *
+ * The first argument is the name of the subcommand. Options are given as
+ * '--name value' or '--name=value', except flags, which take no value; every
+ * other argument is positional.
+ */
+// Options that take no value.
+static FLAGS: [&str; 1] = ["force"];
+
+pub struct Arguments {
+	m_command: String,
+	m_positional: Vec<String>,
+	m_options: BTreeMap<String, String>,
+}
+
+impl Arguments {
+	pub fn parse(args: &[String]) -> Result<Arguments, CommandError> {
+		let Some((command, rest)) = args.split_first() else {
+			return Err(CommandError::Usage("no command given".to_string()));
+		};
+
+		let mut positional = Vec::new();
+		let mut options = BTreeMap::new();
+
+		let mut it = rest.iter();
+		while let Some(arg) = it.next() {
+			let Some(option) = arg.strip_prefix("--") else {
+				positional.push(arg.clone());
+				continue;
+			};
+
+			let (name, value) = match option.split_once('=') {
+				Some((name, value)) => (name.to_string(), value.to_string()),
+				None if FLAGS.contains(&option) => (option.to_string(), "".to_string()),
+				None => {
+					let Some(value) = it.next() else {
+						return Err(CommandError::Usage(format!(
+							"option '--{option}' needs a value"
+						)));
+					};
+					(option.to_string(), value.clone())
+				},
+			};
+			if options.insert(name.clone(), value).is_some() {
+				return Err(CommandError::Usage(format!(
+					"option '--{name}' is given more than once"
+				)));
+			}
+		}
+
+		Ok(Arguments {
+			m_command: command.clone(),
+			m_positional: positional,
+			m_options: options,
+		})
+	}
+
+	pub fn get_command(&self) -> &String {
+		&self.m_command
+	}
+
+	pub fn get_positional(&self, i: usize) -> Option<&String> {
+		self.m_positional.get(i)
+	}
+
+	// Fails if any positional argument or option is not among the expected ones.
+	pub fn check(&self, num_positional: usize, options: &[&str]) -> Result<(), CommandError> {
+		if let Some(arg) = self.m_positional.get(num_positional) {
+			return Err(CommandError::Usage(format!(
+				"unexpected argument '{arg}' for command '{}'",
+				self.m_command
+			)));
+		}
+		if let Some(name) = self
+			.m_options
+			.keys()
+			.find(|name| !options.contains(&name.as_str()))
+		{
+			return Err(CommandError::Usage(format!(
+				"unknown option '--{name}' for command '{}'",
+				self.m_command
+			)));
+		}
+		Ok(())
+	}
+
+	pub fn has_flag(&self, name: &str) -> bool {
+		self.m_options.contains_key(name)
+	}
+
+	pub fn get_option(&self, name: &str) -> Option<&String> {
+		self.m_options.get(name)
+	}
+
+	pub fn get_required(&self, name: &str) -> Result<&String, CommandError> {
+		self.get_option(name).ok_or(CommandError::Usage(format!(
+			"option '--{name}' is required for command '{}'",
+			self.m_command
+		)))
+	}
+
+	pub fn parse_option<T>(&self, name: &str) -> Result<Option<T>, CommandError>
+	where
+		T: FromStr,
+		<T as FromStr>::Err: std::fmt::Display,
+	{
+		let Some(value) = self.get_option(name) else {
+			return Ok(None);
+		};
+		match value.parse::<T>() {
+			Ok(v) => Ok(Some(v)),
+			Err(e) => Err(CommandError::Usage(format!(
+				"invalid value '{value}' of option '--{name}': {e}"
+			))),
+		}
+	}
+
+	pub fn parse_required<T>(&self, name: &str) -> Result<T, CommandError>
+	where
+		T: FromStr,
+		<T as FromStr>::Err: std::fmt::Display,
+	{
+		self.get_required(name)?;
+		Ok(self.parse_option(name)?.expect("The option should exist"))
+	}
+}
@@ -0,0 +1,151 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::fmt;
+
+use crate::economy::all_activities::AllActivities;
+use crate::time::date::Month;
+
+pub mod activities;
+pub mod arguments;
+pub mod reports;
+
+type Arguments = arguments::Arguments;
+
+pub const EXIT_SUCCESS: i32 = 0;
+pub const EXIT_IO_ERROR: i32 = 1;
+pub const EXIT_USAGE: i32 = 2;
+pub const EXIT_NOT_FOUND: i32 = 3;
+pub const EXIT_INVALID_DATA: i32 = 4;
+pub const EXIT_DUPLICATE: i32 = 5;
+
+#[derive(Debug)]
+pub enum CommandError {
+	// The command or its arguments are wrong.
+	Usage(String),
+	// The data asked for does not exist.
+	NotFound(String),
+	// The activity given is not consistent with the data, or the data has problems.
+	InvalidData(String),
+	// The activity given seems to be already in the data.
+	Duplicate(String),
+}
+
+impl CommandError {
+	pub fn exit_code(&self) -> i32 {
+		match self {
+			CommandError::Usage(_) => EXIT_USAGE,
+			CommandError::NotFound(_) => EXIT_NOT_FOUND,
+			CommandError::InvalidData(_) => EXIT_INVALID_DATA,
+			CommandError::Duplicate(_) => EXIT_DUPLICATE,
+		}
+	}
+}
+
+impl fmt::Display for CommandError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			CommandError::Usage(s) => write!(f, "Error: {s}. Run 'finances help' for usage."),
+			CommandError::NotFound(s) => write!(f, "Error: {s}."),
+			CommandError::InvalidData(s) => write!(f, "Error: {s}."),
+			CommandError::Duplicate(s) => {
+				write!(f, "Error: {s}. Add '--force' to add it anyway.")
+			},
+		}
+	}
+}
+
+impl std::error::Error for CommandError {}
+
+pub fn print_usage() {
+	println!("Usage: finances [COMMAND [ARGUMENTS]]");
+	println!();
+	println!("Without a command, the interactive menu is started.");
+	println!();
+	println!("Commands:");
+	println!("    add-expense --date DATE --price PRICE --concepts CONCEPTS");
+	println!("                [--shop SHOP] [--city CITY] [--description TEXT]");
+	println!("                [--currency CURRENCY] [--account ACCOUNT] [--force]");
+	println!("    add-income  --date DATE --price PRICE --concepts CONCEPTS");
+	println!("                [--from FROM] [--place PLACE] [--description TEXT]");
+	println!("                [--currency CURRENCY] [--account ACCOUNT] [--force]");
+	println!("    list [expenses|incomes] [--year YEAR [--month MONTH]]");
+	println!("    summary [--year YEAR [--month MONTH]]");
+	println!("    stats shops|expense-concepts|income-concepts|from|place");
+	println!("          [--sort name|times|value] [--depth DEPTH]");
+	println!("    validate");
+	println!("    help");
+	println!();
+	println!("Dates are written as 2024/March/5 or 2024-03-05, and concepts are");
+	println!("separated by ';', as in 'Food;Groceries'. An expense or an income that");
+	println!("looks like one already in the data is only added with '--force'.");
+	println!();
+	println!("Exit status:");
+	println!("    {EXIT_SUCCESS}  success");
+	println!("    {EXIT_IO_ERROR}  the data could not be read or written");
+	println!("    {EXIT_USAGE}  wrong command or arguments");
+	println!("    {EXIT_NOT_FOUND}  the year or month asked for has no data");
+	println!("    {EXIT_INVALID_DATA}  the activity given or the data is not consistent");
+	println!("    {EXIT_DUPLICATE}  the activity given seems to be already in the data");
+}
+
+// Reads the options '--year' and '--month'. A month needs a year.
+pub fn parse_period(args: &Arguments) -> Result<(Option<u32>, Option<Month>), CommandError> {
+	let year = args.parse_option::<u32>("year")?;
+	let month = args.parse_option::<Month>("month")?;
+	if year.is_none() && month.is_some() {
+		return Err(CommandError::Usage(
+			"option '--month' needs option '--year'".to_string(),
+		));
+	}
+	Ok((year, month))
+}
+
+// Checks that the command exists before any data is read.
+pub fn check_command(args: &Arguments) -> Result<(), CommandError> {
+	match args.get_command().as_str() {
+		"add-expense" | "add-income" | "list" | "summary" | "stats" | "validate" => Ok(()),
+		command => Err(CommandError::Usage(format!("unknown command '{command}'"))),
+	}
+}
+
+pub fn run(args: &Arguments, all_data: &mut AllActivities) -> Result<(), CommandError> {
+	match args.get_command().as_str() {
+		"add-expense" => activities::add_expense(args, all_data),
+		"add-income" => activities::add_income(args, all_data),
+		"list" => activities::list(args, all_data),
+		"summary" => reports::summary(args, all_data),
+		"stats" => reports::stats(args, all_data),
+		"validate" => reports::validate(args, all_data),
+		command => Err(CommandError::Usage(format!("unknown command '{command}'"))),
+	}
+}
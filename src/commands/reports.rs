@@ -0,0 +1,171 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::commands;
+use crate::commands::CommandError;
+use crate::economy;
+use crate::menus;
+use crate::time;
+
+type Arguments = commands::arguments::Arguments;
+type Money = economy::money::Money;
+type AllActivities = economy::all_activities::AllActivities;
+type Month = time::date::Month;
+type Cell = menus::utils::Cell;
+type SortFunc = fn(&(Vec<String>, Cell), &(Vec<String>, Cell)) -> std::cmp::Ordering;
+
+#[duplicate::duplicate_item(
+	method         iter_activity;
+	[sum_expenses] [iter_expenses];
+	[sum_incomes]  [iter_incomes];
+)]
+fn method(
+	all_data: &AllActivities,
+	year: Option<u32>,
+	month: &Option<Month>,
+	num_unconverted: &mut u32,
+) -> Money {
+	let rates = all_data.get_exchange_rates();
+	let mut total = Money::new();
+
+	for year_data in all_data.iter_activities() {
+		if year.is_some_and(|y| y != *year_data.get_year()) {
+			continue;
+		}
+		for month_data in year_data.iter_activity() {
+			if month.as_ref().is_some_and(|m| m != month_data.get_month()) {
+				continue;
+			}
+			for activity in month_data.iter() {
+				match rates.convert(activity.price, &activity.currency, &activity.day_of_year) {
+					Some(value) => total += value,
+					None => *num_unconverted += 1,
+				}
+			}
+		}
+	}
+	total
+}
+
+pub fn summary(args: &Arguments, all_data: &mut AllActivities) -> Result<(), CommandError> {
+	args.check(0, &["year", "month"])?;
+	let (year, month) = commands::parse_period(args)?;
+	if let Some(y) = year {
+		if !all_data.has_year(&y) {
+			return Err(CommandError::NotFound(format!("there is no data for {y}")));
+		}
+	}
+
+	let mut num_unconverted: u32 = 0;
+	let expenses = sum_expenses(all_data, year, &month, &mut num_unconverted);
+	let incomes = sum_incomes(all_data, year, &month, &mut num_unconverted);
+
+	let currency = all_data.get_exchange_rates().get_reporting_currency();
+	println!("Expenses: {} {currency}", expenses.display_in(currency));
+	println!("Incomes:  {} {currency}", incomes.display_in(currency));
+	println!(
+		"Balance:  {} {currency}",
+		(incomes - expenses).display_in(currency)
+	);
+	menus::utils::display_unconverted(num_unconverted, currency);
+	Ok(())
+}
+
+pub fn stats(args: &Arguments, all_data: &mut AllActivities) -> Result<(), CommandError> {
+	args.check(1, &["sort", "depth"])?;
+
+	let sort: SortFunc = match args.get_option("sort").map(|s| s.as_str()) {
+		Some("name") => menus::statistics::sort_by_concept,
+		Some("times") => menus::statistics::sort_by_times,
+		Some("value") | None => menus::statistics::sort_by_value,
+		Some(s) => {
+			return Err(CommandError::Usage(format!(
+				"cannot sort by '{s}', only by 'name', 'times' or 'value'"
+			)))
+		},
+	};
+	let depth = args.parse_option::<usize>("depth")?.unwrap_or(1);
+
+	match args.get_positional(0).map(|s| s.as_str()) {
+		Some("shops") => menus::statistics::history_expenses_shops(all_data, sort),
+		Some("expense-concepts") => {
+			menus::statistics::history_expenses_concepts(all_data, sort, |e| {
+				menus::statistics::concept(depth, e)
+			})
+		},
+		Some("income-concepts") => {
+			menus::statistics::history_incomes_concepts(all_data, sort, |i| {
+				menus::statistics::concept(depth, i)
+			})
+		},
+		Some("from") => menus::statistics::history_of_from_and_place_incomes(
+			all_data,
+			"From".to_string(),
+			sort,
+			menus::statistics::from,
+		),
+		Some("place") => menus::statistics::history_of_from_and_place_incomes(
+			all_data,
+			"Place".to_string(),
+			sort,
+			menus::statistics::place,
+		),
+		Some(what) => {
+			return Err(CommandError::Usage(format!(
+				"there are no statistics of '{what}'"
+			)))
+		},
+		None => {
+			return Err(CommandError::Usage(
+				"command 'stats' needs the statistics to compute".to_string(),
+			))
+		},
+	}
+	Ok(())
+}
+
+pub fn validate(args: &Arguments, all_data: &mut AllActivities) -> Result<(), CommandError> {
+	args.check(0, &[])?;
+
+	let issues = economy::validation::validate(all_data);
+	if issues.is_empty() {
+		println!("No problems found.");
+		return Ok(());
+	}
+	for issue in issues.iter() {
+		println!("{issue}");
+	}
+	Err(CommandError::InvalidData(format!(
+		"found {} problems in the data",
+		issues.len()
+	)))
+}
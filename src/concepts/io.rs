@@ -33,12 +33,14 @@
 use std::fmt;
 use std::mem;
 
-use std::io::{BufRead, Result, Write};
+use std::io::{BufRead, Result};
 
 use crate::economy::all_activities::AllActivities;
 
 use crate::concepts::types::ConceptTypes;
 
+use crate::storage::backups;
+
 use crate::concepts::tree::KeyTree;
 use crate::concepts::tree::Tree;
 
@@ -50,11 +52,20 @@ fn read_types(data_dir: &String, filename: String, concept_types: &mut ConceptTy
 	let mut entire_file_str = String::new();
 	for line in reader.lines() {
 		entire_file_str.push_str(line.unwrap().trim());
+		entire_file_str.push('\n');
 	}
 
-	let mut tree = build_tree(entire_file_str);
+	concept_types.set_tree(parse_tree(&entire_file_str));
+}
+
+// Builds a tree out of its textual representation, as written in the files
+// of concept types.
+pub fn parse_tree(s: &str) -> Tree {
+	let entire_tree_str: String = s.lines().map(str::trim).collect();
+
+	let mut tree = build_tree(entire_tree_str);
 	tree.normalize_tree();
-	concept_types.set_tree(tree);
+	tree
 }
 
 pub fn read_expense_types(data_dir: &String, all_data: &mut AllActivities) {
@@ -80,11 +91,10 @@ fn write_concept_types(
 	filename: String,
 	concept_types: &ConceptTypes,
 ) -> Result<()> {
-	let filename = data_dir.to_owned() + &filename;
-	println!("Writing into '{filename}'...");
+	eprintln!("Writing into '{data_dir}{filename}'...");
 
-	let mut file = std::fs::File::create(filename).expect("I wanted to create a file");
-	write!(file, "{}", concept_types.get_tree())
+	let contents = concept_types.get_tree().to_string();
+	backups::write_file(data_dir, &filename, contents.as_bytes())
 }
 
 pub fn write_all_data(data_dir: &String, all_data: &AllActivities) -> Result<()> {
@@ -0,0 +1,155 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::economy::fields;
+use crate::economy::money::Money;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Account {
+	pub name: String,
+	pub opening_balance: Money,
+	pub currency: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseAccountError {
+	pub reason: String,
+	// Position, starting at 1, of the first wrong character of the line.
+	pub column: usize,
+}
+
+impl std::fmt::Display for ParseAccountError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{} (column {})", self.reason, self.column)
+	}
+}
+
+impl std::error::Error for ParseAccountError {}
+
+impl std::str::FromStr for Account {
+	type Err = ParseAccountError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parts = fields::split_fields(s);
+		let [name, balance, currency] = parts.as_slice() else {
+			return Err(ParseAccountError {
+				reason: format!("expected 3 fields, found {}", parts.len()),
+				column: 1,
+			});
+		};
+
+		Ok(Account {
+			name: name.value.to_string(),
+			opening_balance: balance
+				.value
+				.parse::<Money>()
+				.map_err(|_| ParseAccountError {
+					reason: format!("'{}' is not a valid amount", balance.value),
+					column: balance.column,
+				})?
+				.round_to_currency(currency.value),
+			currency: currency.value.to_string(),
+		})
+	}
+}
+
+#[derive(Debug)]
+pub struct Accounts {
+	m_changes: bool,
+	m_accounts: Vec<Account>,
+}
+
+impl Accounts {
+	pub fn new() -> Accounts {
+		Accounts {
+			m_changes: false,
+			m_accounts: Vec::new(),
+		}
+	}
+
+	pub fn iter(&self) -> std::slice::Iter<'_, Account> {
+		self.m_accounts.iter()
+	}
+
+	pub fn get_names(&self) -> Vec<String> {
+		self.m_accounts.iter().map(|a| a.name.clone()).collect()
+	}
+
+	pub fn get_account(&self, name: &str) -> Option<&Account> {
+		match self
+			.m_accounts
+			.binary_search_by(|a| a.name.as_str().cmp(name))
+		{
+			Ok(idx) => Some(&self.m_accounts[idx]),
+			Err(_) => None,
+		}
+	}
+
+	pub fn has_account(&self, name: &str) -> bool {
+		self.get_account(name).is_some()
+	}
+
+	// Adds an account, replacing any other account with the same name.
+	pub fn add(&mut self, account: Account) {
+		match self
+			.m_accounts
+			.binary_search_by(|a| a.name.cmp(&account.name))
+		{
+			Ok(idx) => {
+				self.m_accounts[idx] = account;
+			},
+			Err(idx) => {
+				self.m_accounts.insert(idx, account);
+			},
+		}
+	}
+
+	pub fn remove(&mut self, name: &str) {
+		if let Ok(idx) = self
+			.m_accounts
+			.binary_search_by(|a| a.name.as_str().cmp(name))
+		{
+			self.m_accounts.remove(idx);
+		}
+	}
+
+	pub fn size(&self) -> usize {
+		self.m_accounts.len()
+	}
+
+	pub fn has_changes(&self) -> bool {
+		self.m_changes
+	}
+	pub fn set_changes(&mut self, c: bool) {
+		self.m_changes = c;
+	}
+}
@@ -32,9 +32,15 @@
 
 extern crate duplicate;
 
+use crate::economy::accounts::Accounts;
+use crate::economy::budgets::Budgets;
+use crate::economy::exchange_rates::ExchangeRates;
 use crate::economy::expense::Expense;
 use crate::economy::income::Income;
 use crate::economy::monthly_activities::MonthlyActivities;
+use crate::economy::recurring::RecurringTemplates;
+use crate::economy::savings_goals::SavingsGoals;
+use crate::economy::transfer::Transfer;
 use crate::economy::yearly_activities::YearlyActivities;
 
 use crate::time::date::Month;
@@ -48,6 +54,11 @@ pub struct AllActivities {
 
 	m_expense_types: ConceptTypes,
 	m_income_types: ConceptTypes,
+	m_exchange_rates: ExchangeRates,
+	m_accounts: Accounts,
+	m_budgets: Budgets,
+	m_savings_goals: SavingsGoals,
+	m_recurring_templates: RecurringTemplates,
 	m_activities: Vec<YearlyActivities>,
 }
 
@@ -58,6 +69,11 @@ impl AllActivities {
 			m_max_year: 0,
 			m_expense_types: ConceptTypes::new(),
 			m_income_types: ConceptTypes::new(),
+			m_exchange_rates: ExchangeRates::new(),
+			m_accounts: Accounts::new(),
+			m_budgets: Budgets::new(),
+			m_savings_goals: SavingsGoals::new(),
+			m_recurring_templates: RecurringTemplates::new(),
 			m_activities: Vec::new(),
 		}
 	}
@@ -95,6 +111,55 @@ impl AllActivities {
 		&mut self.m_income_types
 	}
 
+	// Currencies
+
+	pub fn get_exchange_rates(&self) -> &ExchangeRates {
+		&self.m_exchange_rates
+	}
+	pub fn get_exchange_rates_mut(&mut self) -> &mut ExchangeRates {
+		&mut self.m_exchange_rates
+	}
+
+	// Accounts
+
+	pub fn get_accounts(&self) -> &Accounts {
+		&self.m_accounts
+	}
+	pub fn get_accounts_mut(&mut self) -> &mut Accounts {
+		self.m_accounts.set_changes(true);
+		&mut self.m_accounts
+	}
+
+	// Budgets
+
+	pub fn get_budgets(&self) -> &Budgets {
+		&self.m_budgets
+	}
+	pub fn get_budgets_mut(&mut self) -> &mut Budgets {
+		self.m_budgets.set_changes(true);
+		&mut self.m_budgets
+	}
+
+	// Savings goals
+
+	pub fn get_savings_goals(&self) -> &SavingsGoals {
+		&self.m_savings_goals
+	}
+	pub fn get_savings_goals_mut(&mut self) -> &mut SavingsGoals {
+		self.m_savings_goals.set_changes(true);
+		&mut self.m_savings_goals
+	}
+
+	// Recurring templates
+
+	pub fn get_recurring_templates(&self) -> &RecurringTemplates {
+		&self.m_recurring_templates
+	}
+	pub fn get_recurring_templates_mut(&mut self) -> &mut RecurringTemplates {
+		self.m_recurring_templates.set_changes(true);
+		&mut self.m_recurring_templates
+	}
+
 	// -----
 
 	#[duplicate::duplicate_item(
@@ -116,9 +181,10 @@ impl AllActivities {
 	}
 
 	#[duplicate::duplicate_item(
-		method               retrieve       result;
-		[get_month_expenses] [get_expenses] [Expense];
-		[get_month_incomes]  [get_incomes]  [Income] ;
+		method                retrieve        result;
+		[get_month_expenses]  [get_expenses]  [Expense];
+		[get_month_incomes]   [get_incomes]   [Income];
+		[get_month_transfers] [get_transfers] [Transfer];
 	)]
 	pub fn method(&self, y: &u32, m: &Month) -> Option<&MonthlyActivities<result>> {
 		if let Some(year) = self.get_year(y) {
@@ -183,15 +249,36 @@ impl AllActivities {
 		}
 	}
 
+	pub fn set_changes_concepts(&mut self, c: bool) {
+		self.m_expense_types.set_changes(c);
+		self.m_income_types.set_changes(c);
+	}
+
 	pub fn set_changes_activities(&mut self, c: bool) {
 		for ye in self.m_activities.iter_mut() {
 			ye.set_changes(c);
 		}
 	}
 
+	pub fn has_changes(&self) -> bool {
+		self.m_expense_types.has_changes()
+			|| self.m_income_types.has_changes()
+			|| self.m_exchange_rates.has_changes()
+			|| self.m_accounts.has_changes()
+			|| self.m_budgets.has_changes()
+			|| self.m_savings_goals.has_changes()
+			|| self.m_recurring_templates.has_changes()
+			|| self.m_activities.iter().any(|ye| ye.has_changes())
+	}
+
 	pub fn set_changes(&mut self, c: bool) {
 		self.m_expense_types.set_changes(c);
 		self.m_income_types.set_changes(c);
+		self.m_exchange_rates.set_changes(c);
+		self.m_accounts.set_changes(c);
+		self.m_budgets.set_changes(c);
+		self.m_savings_goals.set_changes(c);
+		self.m_recurring_templates.set_changes(c);
 		self.set_changes_activities(c);
 	}
 }
@@ -0,0 +1,289 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::economy::all_activities::AllActivities;
+use crate::economy::fields;
+use crate::economy::money::Money;
+
+use crate::time::date::{Date, Month};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BudgetPeriod {
+	Monthly,
+	Yearly,
+}
+
+impl fmt::Display for BudgetPeriod {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			BudgetPeriod::Monthly => write!(f, "monthly"),
+			BudgetPeriod::Yearly => write!(f, "yearly"),
+		}
+	}
+}
+
+impl FromStr for BudgetPeriod {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"monthly" => Ok(BudgetPeriod::Monthly),
+			"yearly" => Ok(BudgetPeriod::Yearly),
+			_ => Err(()),
+		}
+	}
+}
+
+/**
+ * @brief Amount that may be spent on a node of the expense concept tree in
+ * every month or year.
+ *
+ * The spending of a node includes the spending of all the nodes below it.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Budget {
+	pub concepts: Vec<String>,
+	pub period: BudgetPeriod,
+	pub amount: Money,
+	pub currency: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseBudgetError {
+	pub reason: String,
+	// Position, starting at 1, of the first wrong character of the line.
+	pub column: usize,
+}
+
+impl fmt::Display for ParseBudgetError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} (column {})", self.reason, self.column)
+	}
+}
+
+impl std::error::Error for ParseBudgetError {}
+
+impl FromStr for Budget {
+	type Err = ParseBudgetError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parts = fields::split_fields(s);
+		let [concept_list, period, amount, currency] = parts.as_slice() else {
+			return Err(ParseBudgetError {
+				reason: format!("expected 4 fields, found {}", parts.len()),
+				column: 1,
+			});
+		};
+
+		let concepts: Vec<String> = concept_list
+			.value
+			.split_terminator(';')
+			.map(str::trim)
+			.filter(|s| !s.is_empty())
+			.map(|s| s.to_string())
+			.collect();
+		if concepts.is_empty() {
+			return Err(ParseBudgetError {
+				reason: "a budget needs at least one concept".to_string(),
+				column: concept_list.column,
+			});
+		}
+
+		Ok(Budget {
+			concepts,
+			period: period
+				.value
+				.parse::<BudgetPeriod>()
+				.map_err(|_| ParseBudgetError {
+					reason: format!("'{}' is not 'monthly' nor 'yearly'", period.value),
+					column: period.column,
+				})?,
+			amount: amount
+				.value
+				.parse::<Money>()
+				.map_err(|_| ParseBudgetError {
+					reason: format!("'{}' is not a valid amount", amount.value),
+					column: amount.column,
+				})?
+				.round_to_currency(currency.value),
+			currency: currency.value.to_string(),
+		})
+	}
+}
+
+#[derive(Debug)]
+pub struct Budgets {
+	m_changes: bool,
+	m_budgets: Vec<Budget>,
+}
+
+impl Budgets {
+	pub fn new() -> Budgets {
+		Budgets {
+			m_changes: false,
+			m_budgets: Vec::new(),
+		}
+	}
+
+	pub fn iter(&self) -> std::slice::Iter<'_, Budget> {
+		self.m_budgets.iter()
+	}
+
+	fn find(&self, concepts: &[String], period: &BudgetPeriod) -> Result<usize, usize> {
+		self.m_budgets
+			.binary_search_by(|b| (b.concepts.as_slice(), &b.period).cmp(&(concepts, period)))
+	}
+
+	// Adds a budget, replacing the budget of the same concepts and period.
+	pub fn add(&mut self, budget: Budget) {
+		match self.find(&budget.concepts, &budget.period) {
+			Ok(idx) => {
+				self.m_budgets[idx] = budget;
+			},
+			Err(idx) => {
+				self.m_budgets.insert(idx, budget);
+			},
+		}
+	}
+
+	pub fn remove(&mut self, concepts: &[String], period: &BudgetPeriod) -> bool {
+		match self.find(concepts, period) {
+			Ok(idx) => {
+				self.m_budgets.remove(idx);
+				true
+			},
+			Err(_) => false,
+		}
+	}
+
+	pub fn size(&self) -> usize {
+		self.m_budgets.len()
+	}
+
+	pub fn has_changes(&self) -> bool {
+		self.m_changes
+	}
+	pub fn set_changes(&mut self, c: bool) {
+		self.m_changes = c;
+	}
+}
+
+/**
+ * @brief A budget compared with the spending of a month or a year.
+ *
+ * All amounts are in the reporting currency.
+ */
+#[derive(Debug)]
+pub struct BudgetStatus {
+	pub concepts: Vec<String>,
+	pub budget: Money,
+	pub spent: Money,
+	// Expenses that could not be converted into the reporting currency.
+	pub num_unconverted: u32,
+}
+
+impl BudgetStatus {
+	pub fn get_remaining(&self) -> Money {
+		self.budget - self.spent
+	}
+	pub fn is_over_budget(&self) -> bool {
+		self.spent > self.budget
+	}
+}
+
+/**
+ * @brief The budgets of a month, or of a year if no month is given, against
+ * what was spent.
+ *
+ * The budgets of a month are the monthly budgets. The budgets of a year are
+ * the yearly budgets and twelve times the monthly budgets of the concepts
+ * without a yearly one. Budgets that can't be converted into the reporting
+ * currency are left out.
+ */
+pub fn get_budget_statuses(
+	all_data: &AllActivities,
+	year: u32,
+	month: Option<&Month>,
+) -> Vec<BudgetStatus> {
+	let rates = all_data.get_exchange_rates();
+	let first_day = Date {
+		year,
+		month: month.cloned().unwrap_or(Month::January),
+		day: 1,
+	};
+	let budgets = all_data.get_budgets();
+
+	let mut statuses = Vec::new();
+	for b in budgets.iter() {
+		let budget = match (month, &b.period) {
+			(Some(_), BudgetPeriod::Monthly) | (None, BudgetPeriod::Yearly) => b.amount,
+			(None, BudgetPeriod::Monthly) => {
+				if budgets.find(&b.concepts, &BudgetPeriod::Yearly).is_ok() {
+					continue;
+				}
+				b.amount.multiply(12.0)
+			},
+			(Some(_), BudgetPeriod::Yearly) => continue,
+		};
+		let Some(budget) = rates.convert(budget, &b.currency, &first_day) else {
+			continue;
+		};
+
+		let mut spent = Money::new();
+		let mut num_unconverted = 0;
+		let months = all_data
+			.get_year(&year)
+			.into_iter()
+			.flat_map(|y| y.iter_expenses())
+			.filter(|m| month.is_none_or(|mo| mo == m.get_month()));
+		for e in months.flat_map(|m| m.iter()) {
+			if !e.concepts.starts_with(&b.concepts) {
+				continue;
+			}
+			match rates.convert(e.price, &e.currency, &e.day_of_year) {
+				Some(value) => spent += value,
+				None => num_unconverted += 1,
+			}
+		}
+
+		statuses.push(BudgetStatus {
+			concepts: b.concepts.clone(),
+			budget,
+			spent,
+			num_unconverted,
+		});
+	}
+	statuses
+}
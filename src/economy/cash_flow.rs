@@ -0,0 +1,142 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::collections::BTreeMap;
+
+use crate::economy::all_activities::AllActivities;
+use crate::economy::money::Money;
+
+use crate::time::date::Month;
+
+/**
+ * @brief What came in and what went out in a period.
+ *
+ * All amounts are in the reporting currency.
+ */
+#[derive(Debug)]
+pub struct CashFlow {
+	pub incomes: Money,
+	pub expenses: Money,
+	// Totals of each top-level concept.
+	pub incomes_by_concept: BTreeMap<String, Money>,
+	pub expenses_by_concept: BTreeMap<String, Money>,
+	// Activities that could not be converted into the reporting currency.
+	pub num_unconverted: u32,
+}
+
+impl CashFlow {
+	pub fn new() -> CashFlow {
+		CashFlow {
+			incomes: Money::new(),
+			expenses: Money::new(),
+			incomes_by_concept: BTreeMap::new(),
+			expenses_by_concept: BTreeMap::new(),
+			num_unconverted: 0,
+		}
+	}
+
+	pub fn has_data(&self) -> bool {
+		!self.incomes_by_concept.is_empty()
+			|| !self.expenses_by_concept.is_empty()
+			|| self.num_unconverted > 0
+	}
+
+	pub fn get_net(&self) -> Money {
+		self.incomes - self.expenses
+	}
+
+	// Share of the incomes that was saved, none if there were no incomes.
+	pub fn get_savings_rate(&self) -> Option<f64> {
+		if self.incomes.is_zero() {
+			return None;
+		}
+		Some(self.get_net().to_f64() / self.incomes.to_f64())
+	}
+
+	pub fn merge(&mut self, other: CashFlow) {
+		self.incomes += other.incomes;
+		self.expenses += other.expenses;
+		for (concept, value) in other.incomes_by_concept {
+			*self.incomes_by_concept.entry(concept).or_default() += value;
+		}
+		for (concept, value) in other.expenses_by_concept {
+			*self.expenses_by_concept.entry(concept).or_default() += value;
+		}
+		self.num_unconverted += other.num_unconverted;
+	}
+}
+
+/**
+ * @brief The cash flow of a month.
+ */
+pub fn get_month_cash_flow(all_data: &AllActivities, year: &u32, month: &Month) -> CashFlow {
+	let rates = all_data.get_exchange_rates();
+	let mut cash_flow = CashFlow::new();
+
+	if let Some(month_data) = all_data.get_month_incomes(year, month) {
+		for i in month_data.iter() {
+			let concept = i.concepts.first().cloned().unwrap_or_default();
+			match rates.convert(i.price, &i.currency, &i.day_of_year) {
+				Some(value) => {
+					cash_flow.incomes += value;
+					*cash_flow.incomes_by_concept.entry(concept).or_default() += value;
+				},
+				None => cash_flow.num_unconverted += 1,
+			}
+		}
+	}
+	if let Some(month_data) = all_data.get_month_expenses(year, month) {
+		for e in month_data.iter() {
+			let concept = e.concepts.first().cloned().unwrap_or_default();
+			match rates.convert(e.price, &e.currency, &e.day_of_year) {
+				Some(value) => {
+					cash_flow.expenses += value;
+					*cash_flow.expenses_by_concept.entry(concept).or_default() += value;
+				},
+				None => cash_flow.num_unconverted += 1,
+			}
+		}
+	}
+	cash_flow
+}
+
+/**
+ * @brief The cash flow of a year.
+ */
+pub fn get_year_cash_flow(all_data: &AllActivities, year: &u32) -> CashFlow {
+	let mut cash_flow = CashFlow::new();
+	for m in 0..12 {
+		let month = Month::from_u32(m).expect("Month index is below 12");
+		cash_flow.merge(get_month_cash_flow(all_data, year, &month));
+	}
+	cash_flow
+}
@@ -0,0 +1,136 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+extern crate chrono;
+use chrono::TimeDelta;
+
+extern crate duplicate;
+
+use crate::economy::all_activities::AllActivities;
+use crate::economy::expense::Expense;
+use crate::economy::income::Income;
+use crate::economy::money::Money;
+
+use crate::time::date::Month;
+use crate::utils;
+
+// Largest number of days between the dates of two duplicate activities.
+pub static DAY_TOLERANCE: i64 = 2;
+// Largest difference, in minor units of their currency, between the prices
+// of two duplicate activities.
+pub static PRICE_TOLERANCE: i64 = 1;
+
+/**
+ * @brief Where a duplicate was found: year, month and position within the month.
+ */
+pub type DuplicatePosition = (u32, Month, usize);
+
+// Are the two activities the same one entered twice? When both have a
+// transaction id, only the ids are compared. Otherwise they must be in the
+// same currency, with dates and prices within the tolerances, and the same
+// shop (or origin) regardless of case and accents.
+#[duplicate::duplicate_item(
+	method                   activity  counterpart;
+	[is_duplicate_expense]   [Expense] [shop];
+	[is_duplicate_income]    [Income]  [from];
+)]
+pub fn method(a: &activity, b: &activity) -> bool {
+	if !a.transaction_id.is_empty() && !b.transaction_id.is_empty() {
+		return a.transaction_id == b.transaction_id;
+	}
+
+	// dates that do not exist are only the same as themselves
+	let days = match (a.day_of_year.to_naive_date(), b.day_of_year.to_naive_date()) {
+		(Some(date_a), Some(date_b)) => (date_a - date_b).num_days(),
+		_ if a.day_of_year == b.day_of_year => 0,
+		_ => return false,
+	};
+	let tolerance = Money::from_currency_units(PRICE_TOLERANCE, &a.currency);
+
+	a.currency == b.currency
+		&& days.abs() <= DAY_TOLERANCE
+		&& (a.price - b.price).abs() <= tolerance
+		&& utils::compare_strings(&a.counterpart, &b.counterpart, false, false)
+}
+
+// Finds an activity in the data that the given one duplicates.
+#[duplicate::duplicate_item(
+	method                   activity  get_month_activities  is_duplicate;
+	[find_duplicate_expense] [Expense] [get_month_expenses]  [is_duplicate_expense];
+	[find_duplicate_income]  [Income]  [get_month_incomes]   [is_duplicate_income];
+)]
+pub fn method(all_data: &AllActivities, a: &activity) -> Option<DuplicatePosition> {
+	let tolerance = TimeDelta::days(DAY_TOLERANCE);
+	let mut months = vec![(a.day_of_year.year, a.day_of_year.month.clone())];
+	// the tolerance is shorter than any month
+	if a.day_of_year.is_valid() {
+		let first = a.day_of_year.clone() - tolerance;
+		let last = a.day_of_year.clone() + tolerance;
+		months.insert(0, (first.year, first.month));
+		months.push((last.year, last.month));
+	}
+	months.dedup();
+
+	for (year, month) in months {
+		let Some(month_data) = all_data.get_month_activities(&year, &month) else {
+			continue;
+		};
+		if let Some(idx) = month_data.iter().position(|b| is_duplicate(a, b)) {
+			return Some((year, month, idx));
+		}
+	}
+	None
+}
+
+// Completes an activity with the fields of its duplicate that it lacks.
+#[duplicate::duplicate_item(
+	method           activity  location;
+	[merge_expense]  [Expense] [city];
+	[merge_income]   [Income]  [place];
+)]
+pub fn method(existing: &mut activity, new: activity) {
+	if existing.account.is_empty() {
+		existing.account = new.account;
+	}
+	if existing.concepts.is_empty() {
+		existing.concepts = new.concepts;
+	}
+	if existing.location.is_empty() {
+		existing.location = new.location;
+	}
+	if existing.description.is_empty() {
+		existing.description = new.description;
+	}
+	if existing.transaction_id.is_empty() {
+		existing.transaction_id = new.transaction_id;
+	}
+}
@@ -0,0 +1,143 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::collections::BTreeMap;
+
+use crate::economy::money::Money;
+
+use crate::time::date::Date;
+
+/**
+ * @brief Table of exchange rates between currencies.
+ *
+ * Each rate states how many units of the target currency one unit of the
+ * source currency is worth from a given date onwards. Amounts are converted
+ * into the reporting currency, which by default is the home currency.
+ */
+#[derive(Debug)]
+pub struct ExchangeRates {
+	m_home_currency: String,
+	m_reporting_currency: String,
+	m_rates: BTreeMap<(String, String), Vec<(Date, f64)>>,
+	m_changes: bool,
+}
+
+impl ExchangeRates {
+	pub fn new() -> ExchangeRates {
+		ExchangeRates {
+			m_home_currency: "EUR".to_string(),
+			m_reporting_currency: "EUR".to_string(),
+			m_rates: BTreeMap::new(),
+			m_changes: false,
+		}
+	}
+
+	pub fn get_home_currency(&self) -> &String {
+		&self.m_home_currency
+	}
+	pub fn set_home_currency(&mut self, c: String) {
+		self.m_home_currency = c;
+	}
+
+	pub fn get_reporting_currency(&self) -> &String {
+		&self.m_reporting_currency
+	}
+	pub fn set_reporting_currency(&mut self, c: String) {
+		self.m_reporting_currency = c;
+	}
+
+	pub fn has_changes(&self) -> bool {
+		self.m_changes
+	}
+	pub fn set_changes(&mut self, c: bool) {
+		self.m_changes = c;
+	}
+
+	pub fn add_rate(&mut self, from: String, to: String, date: Date, rate: f64) {
+		let rates = self.m_rates.entry((from, to)).or_default();
+		match rates.binary_search_by(|(d, _)| d.cmp(&date)) {
+			Ok(idx) => {
+				rates[idx].1 = rate;
+			},
+			Err(idx) => {
+				rates.insert(idx, (date, rate));
+			},
+		}
+	}
+
+	pub fn iter_rates(&self) -> impl Iterator<Item = (&String, &String, &Date, f64)> {
+		self.m_rates.iter().flat_map(|((from, to), rates)| {
+			rates
+				.iter()
+				.map(move |(date, rate)| (from, to, date, *rate))
+		})
+	}
+
+	// Latest rate from 'from' to 'to' stated on or before 'date'.
+	fn get_direct_rate(&self, from: &str, to: &str, date: &Date) -> Option<f64> {
+		let rates = self.m_rates.get(&(from.to_string(), to.to_string()))?;
+		let idx = rates.partition_point(|(d, _)| d <= date);
+		if idx == 0 {
+			return None;
+		}
+		Some(rates[idx - 1].1)
+	}
+
+	pub fn get_rate(&self, from: &str, to: &str, date: &Date) -> Option<f64> {
+		if from == to {
+			return Some(1.0);
+		}
+		if let Some(r) = self.get_direct_rate(from, to, date) {
+			return Some(r);
+		}
+		if let Some(r) = self.get_direct_rate(to, from, date) {
+			return Some(1.0 / r);
+		}
+
+		// go through the home currency
+		let home = &self.m_home_currency;
+		if from != home && to != home {
+			let first = self.get_rate(from, home, date)?;
+			let second = self.get_rate(home, to, date)?;
+			return Some(first * second);
+		}
+		None
+	}
+
+	pub fn convert_to(&self, amount: Money, from: &str, to: &str, date: &Date) -> Option<Money> {
+		self.get_rate(from, to, date).map(|r| amount.multiply(r))
+	}
+
+	pub fn convert(&self, amount: Money, from: &str, date: &Date) -> Option<Money> {
+		self.convert_to(amount, from, &self.m_reporting_currency, date)
+	}
+}
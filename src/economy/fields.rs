@@ -0,0 +1,66 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+/**
+ * @brief A field of a line in a data file.
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct Field<'a> {
+	pub value: &'a str,
+	// Position, starting at 1, of the first character of the field.
+	pub column: usize,
+}
+
+// Splits a line made of tab-separated fields enclosed in double quotes.
+pub fn split_fields(line: &str) -> Vec<Field<'_>> {
+	let mut fields = Vec::new();
+	let mut offset = 0;
+	for raw in line.split('\t') {
+		let start = offset + (raw.len() - raw.trim_start().len());
+		offset += raw.len() + 1;
+
+		let trimmed = raw.trim();
+		if trimmed.is_empty() {
+			continue;
+		}
+
+		let (value, start) = match trimmed.strip_prefix('"').and_then(|t| t.strip_suffix('"')) {
+			Some(v) => (v, start + 1),
+			None => (trimmed, start),
+		};
+		fields.push(Field {
+			value,
+			column: line[..start].chars().count() + 1,
+		});
+	}
+	fields
+}
@@ -33,16 +33,25 @@
 use crate::economy::traits::AsReferences;
 use crate::economy::traits::HasConcepts;
 
+use crate::economy::fields;
+use crate::economy::money::Money;
+
 use crate::time::date;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Income {
 	pub day_of_year: date::Date,
-	pub price: f32,
+	pub price: Money,
+	pub currency: String,
+	pub account: String,
 	pub concepts: Vec<String>,
 	pub from: String,
 	pub place: String,
 	pub description: String,
+	// Identifier given by the bank to the movement, empty if typed in by hand.
+	pub transaction_id: String,
+	// Name of the recurring activity that added it, empty if added otherwise.
+	pub recurring: String,
 }
 
 impl Eq for Income {}
@@ -59,35 +68,39 @@ impl PartialOrd for Income {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-pub struct ParseIncomeError;
-
-fn split_string_data(data: &str) -> Vec<&str> {
-	let all: Vec<&str> = data
-		.split_terminator('\t')
-		.map(str::trim)
-		.filter(|&s| s != "")
-		.collect();
-
-	let parts: Vec<&str> = vec![
-		&all[0][1..all[0].len() - 1],
-		&all[1][1..all[1].len() - 1],
-		&all[2][1..all[2].len() - 1],
-		&all[3][1..all[3].len() - 1],
-		&all[4][1..all[4].len() - 1],
-		&all[5][1..all[5].len() - 1],
-	];
-
-	parts
+pub struct ParseIncomeError {
+	pub reason: String,
+	// Position, starting at 1, of the first wrong character of the line.
+	pub column: usize,
 }
 
+impl std::fmt::Display for ParseIncomeError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{} (column {})", self.reason, self.column)
+	}
+}
+
+impl std::error::Error for ParseIncomeError {}
+
 impl std::str::FromStr for Income {
 	type Err = ParseIncomeError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let parts: Vec<&str> = split_string_data(s);
-		let [d, pr, concept_list, fr, pl, descr] = parts.as_slice() else {
-			panic!("Can't segment string '{s}' into 7 parts")
-		};
+		let parts = fields::split_fields(s);
+		if parts.len() < 6 || parts.len() > 10 {
+			return Err(ParseIncomeError {
+				reason: format!("expected 6 to 10 fields, found {}", parts.len()),
+				column: 1,
+			});
+		}
+		let [d, pr, concept_list, fr, pl, descr] =
+			[parts[0], parts[1], parts[2], parts[3], parts[4], parts[5]].map(|f| f.value);
+		// the currency and the account were added later and may be missing
+		// in old files
+		let cur = parts.get(6).map_or("", |f| f.value);
+		let acc = parts.get(7).map_or("", |f| f.value);
+		let tid = parts.get(8).map_or("", |f| f.value);
+		let rec = parts.get(9).map_or("", |f| f.value);
 
 		let concepts: Vec<String> = concept_list
 			.split_terminator(';')
@@ -96,16 +109,26 @@ impl std::str::FromStr for Income {
 			.map(|s| s.to_string())
 			.collect();
 
-		let date_fromstr = d.parse::<date::Date>().map_err(|_| ParseIncomeError)?;
-		let price_fromstr = pr.parse::<f32>().map_err(|_| ParseIncomeError)?;
+		let date_fromstr = d.parse::<date::Date>().map_err(|e| ParseIncomeError {
+			reason: e.reason,
+			column: parts[0].column + e.column - 1,
+		})?;
+		let price_fromstr = pr.parse::<Money>().map_err(|_| ParseIncomeError {
+			reason: format!("'{pr}' is not a valid amount"),
+			column: parts[1].column,
+		})?;
 
 		Ok(Income {
 			day_of_year: date_fromstr,
 			price: price_fromstr,
+			currency: cur.to_string(),
+			account: acc.to_string(),
 			concepts: concepts,
 			from: fr.to_string(),
 			place: pl.to_string(),
 			description: descr.to_string(),
+			transaction_id: tid.to_string(),
+			recurring: rec.to_string(),
 		})
 	}
 }
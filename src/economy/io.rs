@@ -30,177 +30,608 @@
  *
  ********************************************************************/
 
+use crate::economy::accounts::Account;
+use crate::economy::budgets::Budget;
 use crate::economy::all_activities::AllActivities;
 use crate::economy::expense::Expense;
+use crate::economy::fields;
 use crate::economy::income::Income;
-use crate::economy::monthly_activities::MonthlyActivities;
+use crate::economy::recurring::RecurringTemplate;
+use crate::economy::savings_goals::SavingsGoal;
+use crate::economy::transfer::Transfer;
 use crate::economy::yearly_activities::YearlyActivities;
 
+use crate::storage::backups;
+
 use crate::time::date;
 
+use std::fmt;
 use std::io::{BufRead, Result, Write};
 use std::str::FromStr;
 
-pub fn read_expense_file(p: &std::path::PathBuf) -> YearlyActivities {
-	let mut this_file_year = 45;
+/**
+ * @brief A line of a data file that could not be read.
+ */
+#[derive(Debug)]
+pub struct BadLine {
+	pub file: String,
+	// 0 when it is the whole file that could not be read.
+	pub line_number: usize,
+	pub line: String,
+	pub reason: String,
+}
 
-	if let Some(file_name) = p.file_name() {
-		if let Some(file_str) = file_name.to_str() {
-			this_file_year = file_str[..4].parse::<u32>().unwrap();
+impl fmt::Display for BadLine {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		if self.line_number == 0 {
+			write!(f, "{}: {}", self.file, self.reason)
+		} else {
+			write!(f, "{}:{}: {}", self.file, self.line_number, self.reason)
 		}
 	}
+}
 
-	let mut yearly_expenses = YearlyActivities::new_year_changes(this_file_year, false);
-	let mut monthly_expenses = MonthlyActivities::<Expense>::new();
-	let mut previous_month = date::Month::January;
+// Adds the path to an error of reading a file or a directory.
+fn with_path(e: std::io::Error, p: &std::path::Path) -> std::io::Error {
+	std::io::Error::new(e.kind(), format!("'{}': {e}", p.display()))
+}
 
-	let file = std::fs::File::open(p).expect("Failed to open file");
+// Parses every non-empty line of a file. The lines that can't be parsed are
+// added to 'bad_lines'.
+fn read_data_lines<T, E, F>(
+	p: &std::path::Path,
+	parse: F,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<Vec<T>>
+where
+	E: fmt::Display,
+	F: Fn(&str) -> std::result::Result<T, E>,
+{
+	let file = std::fs::File::open(p).map_err(|e| with_path(e, p))?;
 	let reader = std::io::BufReader::new(file);
-	for line in reader.lines() {
-		let l = line.unwrap();
-		if l == "" {
+
+	let mut values = Vec::new();
+	for (idx, line) in reader.split(b'\n').enumerate() {
+		let mut bytes = line.map_err(|e| with_path(e, p))?;
+		if bytes.last() == Some(&b'\r') {
+			bytes.pop();
+		}
+		let l = match String::from_utf8(bytes) {
+			Ok(l) => l,
+			Err(e) => {
+				bad_lines.push(BadLine {
+					file: p.display().to_string(),
+					line_number: idx + 1,
+					line: String::from_utf8_lossy(e.as_bytes()).to_string(),
+					reason: "the line is not valid UTF-8".to_string(),
+				});
+				continue;
+			},
+		};
+		if l.is_empty() {
 			continue;
 		}
 
-		let e = Expense::from_str(&l).expect("Expected expense");
+		match parse(&l) {
+			Ok(v) => values.push(v),
+			Err(e) => bad_lines.push(BadLine {
+				file: p.display().to_string(),
+				line_number: idx + 1,
+				line: l,
+				reason: e.to_string(),
+			}),
+		}
+	}
+	Ok(values)
+}
 
-		if e.day_of_year.month == previous_month {
-			monthly_expenses.push(e);
-		} else {
-			if monthly_expenses.size() > 0 {
-				yearly_expenses.get_expenses_mut().push(monthly_expenses);
-			}
+// Files of activities are named after their year. Those that are not are
+// added to 'bad_lines'.
+fn read_file_year(p: &std::path::Path, bad_lines: &mut Vec<BadLine>) -> Option<u32> {
+	let year = p
+		.file_stem()
+		.and_then(|stem| stem.to_str())
+		.and_then(|stem| stem.parse::<u32>().ok());
+	if year.is_none() {
+		bad_lines.push(BadLine {
+			file: p.display().to_string(),
+			line_number: 0,
+			line: "".to_string(),
+			reason: "the name of the file is not a year".to_string(),
+		});
+	}
+	year
+}
 
-			monthly_expenses = MonthlyActivities::<Expense>::new_month(&e.day_of_year.month);
+pub fn read_expense_file(
+	p: &std::path::Path,
+	home_currency: &str,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<Option<YearlyActivities>> {
+	let Some(this_file_year) = read_file_year(p, bad_lines) else {
+		return Ok(None);
+	};
 
-			previous_month = e.day_of_year.month.clone();
-			monthly_expenses.push(e);
+	let mut yearly_expenses = YearlyActivities::new_year_changes(this_file_year, false);
+
+	for mut e in read_data_lines(p, Expense::from_str, bad_lines)? {
+		if e.currency.is_empty() {
+			e.currency = home_currency.to_string();
 		}
+		e.price = e.price.round_to_currency(&e.currency);
+
+		// lines need not be sorted by month
+		let month = e.day_of_year.month.clone();
+		yearly_expenses.get_expenses_mut().add(&month).push(e);
 	}
 
-	yearly_expenses.get_expenses_mut().push(monthly_expenses);
-	return yearly_expenses;
+	Ok(Some(yearly_expenses))
 }
 
-pub fn read_income_file(p: &std::path::PathBuf) -> YearlyActivities {
-	let mut this_file_year = 45;
+pub fn read_income_file(
+	p: &std::path::Path,
+	home_currency: &str,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<Option<YearlyActivities>> {
+	let Some(this_file_year) = read_file_year(p, bad_lines) else {
+		return Ok(None);
+	};
+
+	let mut yearly_incomes = YearlyActivities::new_year_changes(this_file_year, false);
 
-	if let Some(file_name) = p.file_name() {
-		if let Some(file_str) = file_name.to_str() {
-			this_file_year = file_str[..4].parse::<u32>().unwrap()
+	for mut i in read_data_lines(p, Income::from_str, bad_lines)? {
+		if i.currency.is_empty() {
+			i.currency = home_currency.to_string();
 		}
+		i.price = i.price.round_to_currency(&i.currency);
+
+		// lines need not be sorted by month
+		let month = i.day_of_year.month.clone();
+		yearly_incomes.get_incomes_mut().add(&month).push(i);
 	}
 
-	let mut yearly_incomes = YearlyActivities::new_year_changes(this_file_year, false);
+	Ok(Some(yearly_incomes))
+}
 
-	let mut monthly_income = MonthlyActivities::<Income>::new();
-	let mut previous_month = date::Month::January;
+pub fn read_transfer_file(
+	p: &std::path::Path,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<Option<YearlyActivities>> {
+	let Some(this_file_year) = read_file_year(p, bad_lines) else {
+		return Ok(None);
+	};
 
-	let file = std::fs::File::open(p).expect("Failed to open file");
-	let reader = std::io::BufReader::new(file);
-	for line in reader.lines() {
-		let l = line.unwrap();
-		if l == "" {
+	let mut yearly_transfers = YearlyActivities::new_year_changes(this_file_year, false);
+
+	for t in read_data_lines(p, Transfer::from_str, bad_lines)? {
+		let month = t.day_of_year.month.clone();
+		yearly_transfers.get_transfers_mut().add(&month).push(t);
+	}
+
+	Ok(Some(yearly_transfers))
+}
+
+// Temporary files left behind by an interrupted save are not data files.
+fn is_data_file(p: &std::path::Path) -> bool {
+	p.extension().is_some_and(|ext| ext == "txt")
+}
+
+pub fn read_all_activities_data(
+	data_dir: &String,
+	home_currency: &str,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<AllActivities> {
+	let mut all_data = AllActivities::new();
+	all_data
+		.get_exchange_rates_mut()
+		.set_home_currency(home_currency.to_string());
+
+	let expense_dir = std::path::PathBuf::from(data_dir.to_owned() + "expenses");
+	for path in std::fs::read_dir(&expense_dir).map_err(|e| with_path(e, &expense_dir))? {
+		let path = path?.path();
+		if !is_data_file(&path) {
+			continue;
+		}
+		eprintln!("        Reading '{}'...", path.display());
+		if let Some(r) = read_expense_file(&path, home_currency, bad_lines)? {
+			all_data.merge(r);
+		}
+	}
+	let income_dir = std::path::PathBuf::from(data_dir.to_owned() + "incomes");
+	for path in std::fs::read_dir(&income_dir).map_err(|e| with_path(e, &income_dir))? {
+		let path = path?.path();
+		if !is_data_file(&path) {
 			continue;
 		}
+		eprintln!("        Reading '{}'...", path.display());
+		if let Some(r) = read_income_file(&path, home_currency, bad_lines)? {
+			all_data.merge(r);
+		}
+	}
+	// transfers were added later and may be missing in old data directories
+	let transfer_dir = std::path::PathBuf::from(data_dir.to_owned() + "transfers");
+	if transfer_dir.exists() {
+		for path in std::fs::read_dir(&transfer_dir).map_err(|e| with_path(e, &transfer_dir))? {
+			let path = path?.path();
+			if !is_data_file(&path) {
+				continue;
+			}
+			eprintln!("        Reading '{}'...", path.display());
+			if let Some(r) = read_transfer_file(&path, bad_lines)? {
+				all_data.merge(r);
+			}
+		}
+	}
+	all_data.get_activities_mut().sort();
 
-		let i = Income::from_str(&l).expect("Expected income");
+	Ok(all_data)
+}
 
-		if i.day_of_year.month == previous_month {
-			monthly_income.push(i);
-		} else {
-			if monthly_income.size() > 0 {
-				yearly_incomes.get_incomes_mut().push(monthly_income);
-			}
+fn parse_exchange_rate(
+	line: &str,
+) -> std::result::Result<(String, String, date::Date, f64), String> {
+	let parts = fields::split_fields(line);
+	let [d, from, to, rate] = parts.as_slice() else {
+		return Err(format!("expected 4 fields, found {}", parts.len()));
+	};
 
-			monthly_income = MonthlyActivities::<Income>::new_month(&i.day_of_year.month.clone());
+	let date = d
+		.value
+		.parse::<date::Date>()
+		.map_err(|e| format!("{} (column {})", e.reason, d.column + e.column - 1))?;
+	let rate_fromstr = rate.value.parse::<f64>().map_err(|_| {
+		format!(
+			"'{}' is not a valid exchange rate (column {})",
+			rate.value, rate.column
+		)
+	})?;
+	Ok((
+		from.value.to_string(),
+		to.value.to_string(),
+		date,
+		rate_fromstr,
+	))
+}
 
-			previous_month = i.day_of_year.month.clone();
-			monthly_income.push(i);
-		}
+pub fn read_exchange_rates(
+	data_dir: &String,
+	all_data: &mut AllActivities,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<()> {
+	// the table of exchange rates is optional
+	let path = std::path::PathBuf::from(data_dir.to_owned() + "exchange_rates.txt");
+	if !path.exists() {
+		return Ok(());
 	}
 
-	yearly_incomes.get_incomes_mut().push(monthly_income);
-	return yearly_incomes;
+	let rates = all_data.get_exchange_rates_mut();
+	for (from, to, date, rate) in read_data_lines(&path, parse_exchange_rate, bad_lines)? {
+		rates.add_rate(from, to, date, rate);
+	}
+	Ok(())
 }
 
-pub fn read_all_activities_data(data_dir: &String) -> AllActivities {
-	let mut all_data = AllActivities::new();
+pub fn read_accounts(
+	data_dir: &String,
+	all_data: &mut AllActivities,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<()> {
+	// the registry of accounts is optional
+	let path = std::path::PathBuf::from(data_dir.to_owned() + "accounts.txt");
+	if !path.exists() {
+		return Ok(());
+	}
 
-	let expense_path = std::fs::read_dir(data_dir.to_owned() + &"expenses").unwrap();
-	for path in expense_path {
-		let path = path.unwrap().path();
-		println!("        Reading '{}'...", path.display());
-		let r = read_expense_file(&path);
-		all_data.merge(r);
+	let accounts = all_data.get_accounts_mut();
+	for account in read_data_lines(&path, Account::from_str, bad_lines)? {
+		accounts.add(account);
 	}
-	let income_path = std::fs::read_dir(data_dir.to_owned() + &"incomes").unwrap();
-	for path in income_path {
-		let path = path.unwrap().path();
-		println!("        Reading '{}'...", path.display());
-		let r = read_income_file(&path);
-		all_data.merge(r);
+	Ok(())
+}
+
+fn write_accounts(data_dir: &String, all_data: &AllActivities) -> Result<()> {
+	let filename = data_dir.to_owned() + "accounts.txt";
+	eprintln!("Writing into '{filename}'...");
+
+	let mut file = Vec::new();
+	for Account {
+		name,
+		opening_balance,
+		currency,
+	} in all_data.get_accounts().iter()
+	{
+		writeln!(file, "\"{name}\"\t\"{opening_balance}\"\t\"{currency}\"")?;
 	}
-	all_data.get_activities_mut().sort();
+	backups::write_file(data_dir, "accounts.txt", &file)
+}
 
-	all_data
+pub fn read_budgets(
+	data_dir: &String,
+	all_data: &mut AllActivities,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<()> {
+	// budgets are optional
+	let path = std::path::PathBuf::from(data_dir.to_owned() + "budgets.txt");
+	if !path.exists() {
+		return Ok(());
+	}
+
+	let budgets = all_data.get_budgets_mut();
+	for budget in read_data_lines(&path, Budget::from_str, bad_lines)? {
+		budgets.add(budget);
+	}
+	Ok(())
+}
+
+fn write_budgets(data_dir: &String, all_data: &AllActivities) -> Result<()> {
+	let filename = data_dir.to_owned() + "budgets.txt";
+	eprintln!("Writing into '{filename}'...");
+
+	let mut file = Vec::new();
+	for Budget {
+		concepts,
+		period,
+		amount,
+		currency,
+	} in all_data.get_budgets().iter()
+	{
+		let concept_list = concepts.join(";");
+		writeln!(
+			file,
+			"\"{concept_list}\"\t\"{period}\"\t\"{amount}\"\t\"{currency}\""
+		)?;
+	}
+	backups::write_file(data_dir, "budgets.txt", &file)
+}
+
+pub fn read_savings_goals(
+	data_dir: &String,
+	all_data: &mut AllActivities,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<()> {
+	// savings goals are optional
+	let path = std::path::PathBuf::from(data_dir.to_owned() + "savings_goals.txt");
+	if !path.exists() {
+		return Ok(());
+	}
+
+	let goals = all_data.get_savings_goals_mut();
+	for goal in read_data_lines(&path, SavingsGoal::from_str, bad_lines)? {
+		goals.add(goal);
+	}
+	Ok(())
+}
+
+fn write_savings_goals(data_dir: &String, all_data: &AllActivities) -> Result<()> {
+	let filename = data_dir.to_owned() + "savings_goals.txt";
+	eprintln!("Writing into '{filename}'...");
+
+	let mut file = Vec::new();
+	for SavingsGoal {
+		name,
+		target,
+		currency,
+		start,
+		deadline,
+		expense_concepts,
+		income_concepts,
+		account,
+	} in all_data.get_savings_goals().iter()
+	{
+		let expense_list = expense_concepts.join(";");
+		let income_list = income_concepts.join(";");
+		writeln!(
+			file,
+			"\"{name}\"\t\"{target}\"\t\"{currency}\"\t\"{start}\"\t\"{deadline}\"\t\"{expense_list}\"\t\"{income_list}\"\t\"{account}\""
+		)?;
+	}
+	backups::write_file(data_dir, "savings_goals.txt", &file)
+}
+
+pub fn read_recurring_templates(
+	data_dir: &String,
+	all_data: &mut AllActivities,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<()> {
+	// recurring activities are optional
+	let path = std::path::PathBuf::from(data_dir.to_owned() + "recurring.txt");
+	if !path.exists() {
+		return Ok(());
+	}
+
+	let templates = all_data.get_recurring_templates_mut();
+	for template in read_data_lines(&path, RecurringTemplate::from_str, bad_lines)? {
+		templates.add(template);
+	}
+	Ok(())
+}
+
+fn write_recurring_templates(data_dir: &String, all_data: &AllActivities) -> Result<()> {
+	let filename = data_dir.to_owned() + "recurring.txt";
+	eprintln!("Writing into '{filename}'...");
+
+	let mut file = Vec::new();
+	for template in all_data.get_recurring_templates().iter() {
+		let end = template
+			.end
+			.as_ref()
+			.map_or("".to_string(), |d| d.to_string());
+		let generated_until = template
+			.generated_until
+			.as_ref()
+			.map_or("".to_string(), |d| d.to_string());
+		writeln!(
+			file,
+			"\"{}\"\t\"{}\"\t\"{}\"\t\"{}\"\t\"{end}\"\t\"{}\"\t\"{}\"\t\"{}\"\t\"{}\"\t\"{}\"\t\"{}\"\t\"{}\"\t\"{generated_until}\"",
+			template.name,
+			template.kind,
+			template.frequency,
+			template.start,
+			template.amounts_to_string(),
+			template.currency,
+			template.account,
+			template.concepts.join(";"),
+			template.counterpart,
+			template.place,
+			template.description
+		)?;
+	}
+	backups::write_file(data_dir, "recurring.txt", &file)
+}
+
+fn write_exchange_rates(data_dir: &String, all_data: &AllActivities) -> Result<()> {
+	let filename = data_dir.to_owned() + "exchange_rates.txt";
+	eprintln!("Writing into '{filename}'...");
+
+	let mut file = Vec::new();
+	for (from, to, date, rate) in all_data.get_exchange_rates().iter_rates() {
+		writeln!(file, "\"{date}\"\t\"{from}\"\t\"{to}\"\t\"{rate}\"")?;
+	}
+	backups::write_file(data_dir, "exchange_rates.txt", &file)
+}
+
+// Ends the line of an activity. The identifier of the transaction is only
+// written for imported activities, and the recurring activity only for the
+// activities it added.
+fn write_line_end(file: &mut Vec<u8>, transaction_id: &str, recurring: &str) -> Result<()> {
+	if !recurring.is_empty() {
+		writeln!(file, "\t\"{transaction_id}\"\t\"{recurring}\"")
+	} else if !transaction_id.is_empty() {
+		writeln!(file, "\t\"{transaction_id}\"")
+	} else {
+		writeln!(file)
+	}
 }
 
 pub fn write_all_data(data_dir: &String, all_data: &AllActivities) -> Result<()> {
 	for ye in all_data.iter_activities() {
 		if ye.get_expenses().has_changes() {
-			let expense_filename =
-				data_dir.to_owned() + &format!("expenses/{}.txt", ye.get_year()).to_string();
+			let expense_filename = format!("expenses/{}.txt", ye.get_year());
 
-			println!("Writing into '{expense_filename}'...");
-			let mut expense_file = std::fs::File::create(expense_filename)
-				.expect("I wanted to create an output file for the expenses, but couldn't");
+			eprintln!("Writing into '{data_dir}{expense_filename}'...");
+			let mut expense_file = Vec::new();
 
 			for me in ye.iter_expenses() {
 				for Expense {
 					day_of_year: d,
 					price: pr,
+					currency: cur,
+					account: acc,
 					concepts: cs,
 					shop: pl,
 					city: ci,
 					description: descr,
+					transaction_id: tid,
+					recurring: rec,
 				} in me.get_activities().iter()
 				{
 					let concept_list = cs.join(";");
-					writeln!(
+					write!(
 						expense_file,
-						"\"{d}\"\t\"{pr}\"\t\"{concept_list}\"\t\"{pl}\"\t\"{ci}\"\t\"{descr}\""
+						"\"{d}\"\t\"{pr}\"\t\"{concept_list}\"\t\"{pl}\"\t\"{ci}\"\t\"{descr}\"\t\"{cur}\"\t\"{acc}\""
 					)?;
+					write_line_end(&mut expense_file, tid, rec)?;
 				}
 			}
+			backups::write_file(data_dir, &expense_filename, &expense_file)?;
 		}
 
 		if ye.get_incomes().has_changes() {
-			let income_filename =
-				data_dir.to_owned() + &format!("incomes/{}.txt", ye.get_year()).to_string();
+			let income_filename = format!("incomes/{}.txt", ye.get_year());
 
-			println!("Writing into '{income_filename}'...");
-			let mut income_file =
-				std::fs::File::create(income_filename).expect("I wanted to create a file");
+			eprintln!("Writing into '{data_dir}{income_filename}'...");
+			let mut income_file = Vec::new();
 
 			for me in ye.iter_incomes() {
 				for Income {
 					day_of_year: d,
 					price: pr,
+					currency: cur,
+					account: acc,
 					concepts: cs,
 					from: fr,
 					place: pl,
 					description: descr,
+					transaction_id: tid,
+					recurring: rec,
 				} in me.get_activities().iter()
 				{
 					let concept_list = cs.join(";");
-					writeln!(
+					write!(
 						income_file,
-						"\"{d}\"\t\"{pr}\"\t\"{concept_list}\"\t\"{fr}\"\t\"{pl}\"\t\"{descr}\""
+						"\"{d}\"\t\"{pr}\"\t\"{concept_list}\"\t\"{fr}\"\t\"{pl}\"\t\"{descr}\"\t\"{cur}\"\t\"{acc}\""
+					)?;
+					write_line_end(&mut income_file, tid, rec)?;
+				}
+			}
+			backups::write_file(data_dir, &income_filename, &income_file)?;
+		}
+
+		let transfer_filename = format!("transfers/{}.txt", ye.get_year());
+		// do not create empty files for years without transfers
+		let has_transfers = ye.iter_transfers().any(|mt| mt.size() > 0)
+			|| std::path::Path::new(&(data_dir.to_owned() + &transfer_filename)).exists();
+
+		if ye.get_transfers().has_changes() && has_transfers {
+			std::fs::create_dir_all(data_dir.to_owned() + "transfers")?;
+
+			eprintln!("Writing into '{data_dir}{transfer_filename}'...");
+			let mut transfer_file = Vec::new();
+
+			for mt in ye.iter_transfers() {
+				for Transfer {
+					day_of_year: d,
+					price: pr,
+					currency: cur,
+					from_account: fr,
+					to_account: to,
+					description: descr,
+				} in mt.get_activities().iter()
+				{
+					writeln!(
+						transfer_file,
+						"\"{d}\"\t\"{pr}\"\t\"{cur}\"\t\"{fr}\"\t\"{to}\"\t\"{descr}\""
 					)?;
 				}
 			}
+			backups::write_file(data_dir, &transfer_filename, &transfer_file)?;
 		}
 	}
+
+	if all_data.get_accounts().has_changes() {
+		write_accounts(data_dir, all_data)?;
+	}
+
+	let budgets = all_data.get_budgets();
+	// do not create the optional file of budgets when there is none
+	let has_budgets =
+		budgets.size() > 0 || std::path::Path::new(&(data_dir.to_owned() + "budgets.txt")).exists();
+	if budgets.has_changes() && has_budgets {
+		write_budgets(data_dir, all_data)?;
+	}
+
+	let goals = all_data.get_savings_goals();
+	// do not create the optional file of savings goals when there is none
+	let has_goals = goals.size() > 0
+		|| std::path::Path::new(&(data_dir.to_owned() + "savings_goals.txt")).exists();
+	if goals.has_changes() && has_goals {
+		write_savings_goals(data_dir, all_data)?;
+	}
+
+	let templates = all_data.get_recurring_templates();
+	// do not create the optional file of recurring activities when there is none
+	let has_templates = templates.size() > 0
+		|| std::path::Path::new(&(data_dir.to_owned() + "recurring.txt")).exists();
+	if templates.has_changes() && has_templates {
+		write_recurring_templates(data_dir, all_data)?;
+	}
+
+	let rates = all_data.get_exchange_rates();
+	// do not create the optional table of exchange rates when there is none
+	let has_rates = rates.iter_rates().next().is_some()
+		|| std::path::Path::new(&(data_dir.to_owned() + "exchange_rates.txt")).exists();
+	if rates.has_changes() && has_rates {
+		write_exchange_rates(data_dir, all_data)?;
+	}
 	Ok(())
 }
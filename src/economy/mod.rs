@@ -30,12 +30,24 @@
  *
  ********************************************************************/
 
+pub mod accounts;
 pub mod all_activities;
+pub mod budgets;
+pub mod cash_flow;
+pub mod duplicates;
+pub mod exchange_rates;
 pub mod expense;
+pub mod fields;
 pub mod income;
+pub mod money;
 pub mod monthly_activities;
+pub mod recurring;
+pub mod reminders;
+pub mod savings_goals;
+pub mod transfer;
 pub mod yearly_activities;
 
 pub mod io;
 
 pub mod traits;
+pub mod validation;
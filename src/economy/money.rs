@@ -0,0 +1,309 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::fmt;
+use std::str::FromStr;
+
+// Number of decimal digits of the stored units, those of the smallest minor
+// unit of all currencies (e.g. the fils of the Kuwaiti dinar).
+pub const DECIMALS: u32 = 3;
+// Number of stored units in one major unit.
+pub const SCALE: i64 = 10_i64.pow(DECIMALS);
+
+// Number of decimal digits of the minor unit of most currencies (cents).
+pub const DEFAULT_CURRENCY_DECIMALS: u32 = 2;
+
+// Currencies whose minor unit is not the hundredth of the major unit,
+// following ISO 4217.
+static CURRENCY_DECIMALS: [(&str, u32); 24] = [
+	("BHD", 3),
+	("BIF", 0),
+	("CLP", 0),
+	("DJF", 0),
+	("GNF", 0),
+	("IQD", 3),
+	("ISK", 0),
+	("JOD", 3),
+	("JPY", 0),
+	("KMF", 0),
+	("KRW", 0),
+	("KWD", 3),
+	("LYD", 3),
+	("OMR", 3),
+	("PYG", 0),
+	("RWF", 0),
+	("TND", 3),
+	("UGX", 0),
+	("UYI", 0),
+	("VND", 0),
+	("VUV", 0),
+	("XAF", 0),
+	("XOF", 0),
+	("XPF", 0),
+];
+
+// Number of decimal digits of the minor unit of the given currency.
+pub fn currency_decimals(currency: &str) -> u32 {
+	CURRENCY_DECIMALS
+		.iter()
+		.find(|(code, _)| code.eq_ignore_ascii_case(currency))
+		.map_or(DEFAULT_CURRENCY_DECIMALS, |(_, decimals)| *decimals)
+}
+
+/**
+ * @brief An exact amount of money.
+ *
+ * The amount is stored as an integer number of thousandths of the major
+ * unit, so that adding and subtracting amounts never accumulates rounding
+ * errors, and so that amounts of every currency can be stored exactly.
+ */
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Money {
+	m_units: i64,
+}
+
+impl Money {
+	pub fn new() -> Money {
+		Money { m_units: 0 }
+	}
+	pub fn from_units(units: i64) -> Money {
+		Money { m_units: units }
+	}
+	// Amount of the given number of minor units of the currency.
+	pub fn from_currency_units(units: i64, currency: &str) -> Money {
+		Money {
+			m_units: units * 10_i64.pow(DECIMALS - currency_decimals(currency)),
+		}
+	}
+
+	pub fn get_units(&self) -> i64 {
+		self.m_units
+	}
+
+	pub fn is_zero(&self) -> bool {
+		self.m_units == 0
+	}
+	pub fn is_negative(&self) -> bool {
+		self.m_units < 0
+	}
+
+	pub fn abs(&self) -> Money {
+		Money {
+			m_units: self.m_units.abs(),
+		}
+	}
+
+	// Amount multiplied by a (typically exchange rate) factor, rounded to
+	// the nearest stored unit.
+	pub fn multiply(self, factor: f64) -> Money {
+		Money {
+			m_units: (self.m_units as f64 * factor).round() as i64,
+		}
+	}
+
+	// Amount rounded, half away from zero, to the minor unit of the currency.
+	pub fn round_to_currency(self, currency: &str) -> Money {
+		let step = 10_i64.pow(DECIMALS - currency_decimals(currency));
+		let units = self.m_units.abs();
+		let rounded = (units + step / 2) / step * step;
+		Money {
+			m_units: if self.m_units < 0 { -rounded } else { rounded },
+		}
+	}
+
+	// Amount to be displayed with the decimals of the given currency.
+	pub fn display_in(self, currency: &str) -> CurrencyAmount {
+		CurrencyAmount {
+			m_amount: self.round_to_currency(currency),
+			m_decimals: currency_decimals(currency),
+		}
+	}
+
+	// Approximate value of this amount, only to be used for ratios and
+	// percentages, never for accounting.
+	pub fn to_f64(self) -> f64 {
+		self.m_units as f64 / SCALE as f64
+	}
+}
+
+impl std::ops::Add for Money {
+	type Output = Money;
+	fn add(self, other: Money) -> Money {
+		Money {
+			m_units: self.m_units + other.m_units,
+		}
+	}
+}
+impl std::ops::AddAssign for Money {
+	fn add_assign(&mut self, other: Money) {
+		self.m_units += other.m_units;
+	}
+}
+impl std::ops::Sub for Money {
+	type Output = Money;
+	fn sub(self, other: Money) -> Money {
+		Money {
+			m_units: self.m_units - other.m_units,
+		}
+	}
+}
+impl std::ops::SubAssign for Money {
+	fn sub_assign(&mut self, other: Money) {
+		self.m_units -= other.m_units;
+	}
+}
+impl std::ops::Neg for Money {
+	type Output = Money;
+	fn neg(self) -> Money {
+		Money {
+			m_units: -self.m_units,
+		}
+	}
+}
+
+impl std::iter::Sum for Money {
+	fn sum<I: Iterator<Item = Money>>(iter: I) -> Money {
+		iter.fold(Money::new(), |acc, m| acc + m)
+	}
+}
+impl<'a> std::iter::Sum<&'a Money> for Money {
+	fn sum<I: Iterator<Item = &'a Money>>(iter: I) -> Money {
+		iter.fold(Money::new(), |acc, m| acc + *m)
+	}
+}
+
+// Writes the amount with the given number of decimals, which must not lose
+// any digit of the amount. Width, alignment and sign flags are honoured.
+fn write_units(f: &mut fmt::Formatter, units: i64, decimals: u32) -> fmt::Result {
+	let divisor = 10_u64.pow(DECIMALS - decimals);
+	let absolute = units.unsigned_abs() / divisor;
+	let scale = 10_u64.pow(decimals);
+	let digits = if decimals == 0 {
+		absolute.to_string()
+	} else {
+		format!(
+			"{}.{:0width$}",
+			absolute / scale,
+			absolute % scale,
+			width = decimals as usize
+		)
+	};
+	f.pad_integral(units >= 0, "", &digits)
+}
+
+impl fmt::Display for Money {
+	// The precision is ignored: amounts are printed with the decimals of
+	// most currencies, or with all of them if they have more.
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let step = 10_i64.pow(DECIMALS - DEFAULT_CURRENCY_DECIMALS);
+		let decimals = if self.m_units % step == 0 {
+			DEFAULT_CURRENCY_DECIMALS
+		} else {
+			DECIMALS
+		};
+		write_units(f, self.m_units, decimals)
+	}
+}
+
+/**
+ * @brief An amount of money displayed with the decimals of its currency.
+ */
+pub struct CurrencyAmount {
+	m_amount: Money,
+	m_decimals: u32,
+}
+
+impl fmt::Display for CurrencyAmount {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write_units(f, self.m_amount.m_units, self.m_decimals)
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseMoneyError;
+
+impl fmt::Display for ParseMoneyError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "not a valid amount of money")
+	}
+}
+
+impl FromStr for Money {
+	type Err = ParseMoneyError;
+
+	// Parses amounts like "12", "12.3", "-12.345" or "+.5" exactly. Digits
+	// beyond the stored units are rounded half away from zero.
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let s = s.trim();
+		let (negative, unsigned) = match s.strip_prefix('-') {
+			Some(rest) => (true, rest),
+			None => (false, s.strip_prefix('+').unwrap_or(s)),
+		};
+
+		let (integer_part, fraction_part) = match unsigned.split_once('.') {
+			Some((i, f)) => (i, f),
+			None => (unsigned, ""),
+		};
+		if integer_part.is_empty() && fraction_part.is_empty() {
+			return Err(ParseMoneyError);
+		}
+		let all_digits = |t: &str| t.chars().all(|c| c.is_ascii_digit());
+		if !all_digits(integer_part) || !all_digits(fraction_part) {
+			return Err(ParseMoneyError);
+		}
+
+		let mut units: i64 = 0;
+		for c in integer_part.chars() {
+			units = units
+				.checked_mul(10)
+				.and_then(|u| u.checked_add(c.to_digit(10).unwrap() as i64))
+				.ok_or(ParseMoneyError)?;
+		}
+		units = units.checked_mul(SCALE).ok_or(ParseMoneyError)?;
+
+		let mut fraction_digits = fraction_part
+			.chars()
+			.map(|c| c.to_digit(10).unwrap() as i64);
+		let mut weight = SCALE;
+		for _ in 0..DECIMALS {
+			weight /= 10;
+			units += fraction_digits.next().unwrap_or(0) * weight;
+		}
+		if fraction_digits.next().unwrap_or(0) >= 5 {
+			units = units.checked_add(1).ok_or(ParseMoneyError)?;
+		}
+
+		Ok(Money {
+			m_units: if negative { -units } else { units },
+		})
+	}
+}
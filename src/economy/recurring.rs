@@ -0,0 +1,631 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::TimeDelta;
+
+use crate::economy::all_activities::AllActivities;
+use crate::economy::expense::Expense;
+use crate::economy::fields;
+use crate::economy::income::Income;
+use crate::economy::money::Money;
+
+use crate::time::date::{Date, Month};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Frequency {
+	Weekly,
+	Monthly,
+	Yearly,
+	EveryDays(u32),
+}
+
+impl fmt::Display for Frequency {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Frequency::Weekly => write!(f, "weekly"),
+			Frequency::Monthly => write!(f, "monthly"),
+			Frequency::Yearly => write!(f, "yearly"),
+			Frequency::EveryDays(days) => write!(f, "every {days} days"),
+		}
+	}
+}
+
+impl FromStr for Frequency {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let lower = s.trim().to_lowercase();
+		match lower.as_str() {
+			"weekly" => return Ok(Frequency::Weekly),
+			"monthly" => return Ok(Frequency::Monthly),
+			"yearly" => return Ok(Frequency::Yearly),
+			_ => {},
+		}
+		let days = lower
+			.strip_prefix("every ")
+			.and_then(|rest| rest.strip_suffix(" days"))
+			.and_then(|n| n.trim().parse::<u32>().ok())
+			.ok_or(())?;
+		if days == 0 {
+			return Err(());
+		}
+		Ok(Frequency::EveryDays(days))
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecurringKind {
+	Expense,
+	Income,
+}
+
+impl fmt::Display for RecurringKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			RecurringKind::Expense => write!(f, "expense"),
+			RecurringKind::Income => write!(f, "income"),
+		}
+	}
+}
+
+impl FromStr for RecurringKind {
+	type Err = ();
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.to_lowercase().as_str() {
+			"expense" => Ok(RecurringKind::Expense),
+			"income" => Ok(RecurringKind::Income),
+			_ => Err(()),
+		}
+	}
+}
+
+/**
+ * @brief An expense or an income that repeats over time, such as the rent or
+ * a subscription.
+ *
+ * The occurrences of a template up to today are added to the data, and the
+ * template remembers the last one added. The activities it added carry its
+ * name, so that they are still known after being edited by hand.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurringTemplate {
+	pub name: String,
+	pub kind: RecurringKind,
+	pub frequency: Frequency,
+	pub start: Date,
+	// The template repeats forever if not given.
+	pub end: Option<Date>,
+	// Amount in effect from each date on, sorted by date. The first one is
+	// in effect from the start.
+	pub amounts: Vec<(Date, Money)>,
+	pub currency: String,
+	pub account: String,
+	pub concepts: Vec<String>,
+	// The shop of expenses or the origin of incomes.
+	pub counterpart: String,
+	// The city of expenses or the place of incomes.
+	pub place: String,
+	pub description: String,
+	// Date of the last occurrence added to the data.
+	pub generated_until: Option<Date>,
+}
+
+impl RecurringTemplate {
+	// Date of the n-th occurrence, the first one being the start.
+	fn get_occurrence(&self, n: u32) -> Date {
+		match &self.frequency {
+			Frequency::Weekly => self.start.clone() + TimeDelta::days(7 * n as i64),
+			Frequency::EveryDays(days) => {
+				self.start.clone() + TimeDelta::days(*days as i64 * n as i64)
+			},
+			Frequency::Monthly => {
+				let index = self.start.year * 12 + self.start.month.clone() as u32 + n;
+				let year = index / 12;
+				let month = Month::from_u32(index % 12).expect("Month index is below 12");
+				// days past the end of shorter months fall on their last day
+				let day = self.start.day.min(month.num_days(year));
+				Date { year, month, day }
+			},
+			Frequency::Yearly => {
+				let year = self.start.year + n;
+				let month = self.start.month.clone();
+				let day = self.start.day.min(month.num_days(year));
+				Date { year, month, day }
+			},
+		}
+	}
+
+	// Dates of the occurrences up to the given day, included.
+	pub fn get_occurrences(&self, last: &Date) -> Vec<Date> {
+		let last = match &self.end {
+			Some(end) if end < last => end,
+			_ => last,
+		};
+
+		let mut dates = Vec::new();
+		let mut n = 0;
+		loop {
+			let date = self.get_occurrence(n);
+			if date > *last {
+				break;
+			}
+			dates.push(date);
+			n += 1;
+		}
+		dates
+	}
+
+	pub fn get_amount(&self, date: &Date) -> Money {
+		let idx = self.amounts.partition_point(|(d, _)| d <= date);
+		let (_, amount) = self.amounts[idx.saturating_sub(1)];
+		amount
+	}
+
+	// Sets the amount in effect from the given day on.
+	pub fn set_amount(&mut self, date: Date, amount: Money) {
+		self.amounts.retain(|(d, _)| *d < date);
+		self.amounts.push((date, amount));
+	}
+
+	pub fn amounts_to_string(&self) -> String {
+		self.amounts
+			.iter()
+			.map(|(date, amount)| format!("{date}={amount}"))
+			.collect::<Vec<_>>()
+			.join(";")
+	}
+
+	#[duplicate::duplicate_item(
+		method          activity  counterpart_field place_field;
+		[make_expense]  [Expense] [shop]            [city];
+		[make_income]   [Income]  [from]            [place];
+	)]
+	pub fn method(&self, date: &Date) -> activity {
+		activity {
+			day_of_year: date.clone(),
+			price: self.get_amount(date),
+			currency: self.currency.clone(),
+			account: self.account.clone(),
+			concepts: self.concepts.clone(),
+			counterpart_field: self.counterpart.clone(),
+			place_field: self.place.clone(),
+			description: self.description.clone(),
+			transaction_id: "".to_string(),
+			recurring: self.name.clone(),
+		}
+	}
+
+	// Brings an activity it added up to date with the changes from the old
+	// version of the template. The fields that the template did not change
+	// keep the value they were given by hand.
+	#[duplicate::duplicate_item(
+		method            activity  counterpart_field place_field;
+		[update_expense]  [Expense] [shop]            [city];
+		[update_income]   [Income]  [from]            [place];
+	)]
+	fn method(&self, old: &RecurringTemplate, a: &mut activity) {
+		let old_amount = old.get_amount(&a.day_of_year);
+		let new_amount = self.get_amount(&a.day_of_year);
+		if old_amount != new_amount {
+			a.price = new_amount;
+		}
+		if old.currency != self.currency {
+			a.currency = self.currency.clone();
+		}
+		if old.account != self.account {
+			a.account = self.account.clone();
+		}
+		if old.concepts != self.concepts {
+			a.concepts = self.concepts.clone();
+		}
+		if old.counterpart != self.counterpart {
+			a.counterpart_field = self.counterpart.clone();
+		}
+		if old.place != self.place {
+			a.place_field = self.place.clone();
+		}
+		if old.description != self.description {
+			a.description = self.description.clone();
+		}
+		a.recurring = self.name.clone();
+	}
+}
+
+// Reads the amounts, rounded to the minor unit of the currency.
+pub fn parse_amounts(text: &str, currency: &str) -> Result<Vec<(Date, Money)>, String> {
+	let mut amounts = Vec::new();
+	for part in text.split_terminator(';') {
+		let (date, amount) = part
+			.split_once('=')
+			.ok_or(format!("'{part}' is not a date and an amount"))?;
+		let date = Date::parse_valid(date.trim()).map_err(|e| e.reason)?;
+		let amount = amount
+			.trim()
+			.parse::<Money>()
+			.map_err(|_| format!("'{amount}' is not a valid amount"))?;
+		amounts.push((date, amount.round_to_currency(currency)));
+	}
+	if amounts.is_empty() {
+		return Err("a recurring activity needs at least one amount".to_string());
+	}
+	if !amounts.windows(2).all(|w| w[0].0 < w[1].0) {
+		return Err("the amounts are not sorted by date".to_string());
+	}
+	Ok(amounts)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseRecurringError {
+	pub reason: String,
+	// Position, starting at 1, of the first wrong character of the line.
+	pub column: usize,
+}
+
+impl fmt::Display for ParseRecurringError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} (column {})", self.reason, self.column)
+	}
+}
+
+impl std::error::Error for ParseRecurringError {}
+
+impl FromStr for RecurringTemplate {
+	type Err = ParseRecurringError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parts = fields::split_fields(s);
+		let [name, kind, frequency, start, end, amounts, currency, account, concept_list, counterpart, place, description, generated_until] =
+			parts.as_slice()
+		else {
+			return Err(ParseRecurringError {
+				reason: format!("expected 13 fields, found {}", parts.len()),
+				column: 1,
+			});
+		};
+
+		let parse_date = |field: &fields::Field| {
+			Date::parse_valid(field.value).map_err(|e| ParseRecurringError {
+				reason: e.reason,
+				column: field.column + e.column - 1,
+			})
+		};
+		let parse_optional_date = |field: &fields::Field| {
+			if field.value.is_empty() {
+				Ok(None)
+			} else {
+				parse_date(field).map(Some)
+			}
+		};
+
+		let concepts: Vec<String> = concept_list
+			.value
+			.split_terminator(';')
+			.map(str::trim)
+			.filter(|s| !s.is_empty())
+			.map(|s| s.to_string())
+			.collect();
+		if concepts.is_empty() {
+			return Err(ParseRecurringError {
+				reason: "a recurring activity needs at least one concept".to_string(),
+				column: concept_list.column,
+			});
+		}
+
+		Ok(RecurringTemplate {
+			name: name.value.to_string(),
+			kind: kind
+				.value
+				.parse::<RecurringKind>()
+				.map_err(|_| ParseRecurringError {
+					reason: format!("'{}' is not 'expense' nor 'income'", kind.value),
+					column: kind.column,
+				})?,
+			frequency: frequency
+				.value
+				.parse::<Frequency>()
+				.map_err(|_| ParseRecurringError {
+					reason: format!("'{}' is not a valid frequency", frequency.value),
+					column: frequency.column,
+				})?,
+			start: parse_date(start)?,
+			end: parse_optional_date(end)?,
+			amounts: parse_amounts(amounts.value, currency.value).map_err(|reason| {
+				ParseRecurringError {
+					reason,
+					column: amounts.column,
+				}
+			})?,
+			currency: currency.value.to_string(),
+			account: account.value.to_string(),
+			concepts,
+			counterpart: counterpart.value.to_string(),
+			place: place.value.to_string(),
+			description: description.value.to_string(),
+			generated_until: parse_optional_date(generated_until)?,
+		})
+	}
+}
+
+#[derive(Debug)]
+pub struct RecurringTemplates {
+	m_changes: bool,
+	m_templates: Vec<RecurringTemplate>,
+}
+
+impl RecurringTemplates {
+	pub fn new() -> RecurringTemplates {
+		RecurringTemplates {
+			m_changes: false,
+			m_templates: Vec::new(),
+		}
+	}
+
+	pub fn iter(&self) -> std::slice::Iter<'_, RecurringTemplate> {
+		self.m_templates.iter()
+	}
+
+	pub fn get_names(&self) -> Vec<String> {
+		self.m_templates.iter().map(|t| t.name.clone()).collect()
+	}
+
+	fn find(&self, name: &str) -> Result<usize, usize> {
+		self.m_templates
+			.binary_search_by(|t| t.name.as_str().cmp(name))
+	}
+
+	pub fn get_template(&self, name: &str) -> Option<&RecurringTemplate> {
+		self.find(name).ok().map(|idx| &self.m_templates[idx])
+	}
+
+	// Adds a template, replacing the template of the same name.
+	pub fn add(&mut self, template: RecurringTemplate) {
+		match self.find(&template.name) {
+			Ok(idx) => {
+				self.m_templates[idx] = template;
+			},
+			Err(idx) => {
+				self.m_templates.insert(idx, template);
+			},
+		}
+	}
+
+	pub fn remove(&mut self, name: &str) -> bool {
+		match self.find(name) {
+			Ok(idx) => {
+				self.m_templates.remove(idx);
+				true
+			},
+			Err(_) => false,
+		}
+	}
+
+	pub fn size(&self) -> usize {
+		self.m_templates.len()
+	}
+
+	pub fn has_changes(&self) -> bool {
+		self.m_changes
+	}
+	pub fn set_changes(&mut self, c: bool) {
+		self.m_changes = c;
+	}
+}
+
+/* ------------------------------------------------------------------------- */
+
+/**
+ * @brief Adds to the data the occurrences of the templates up to the given day
+ * that were not added yet.
+ *
+ * Returns the number of activities added.
+ */
+pub fn generate_occurrences(all_data: &mut AllActivities, today: &Date) -> usize {
+	let templates: Vec<RecurringTemplate> =
+		all_data.get_recurring_templates().iter().cloned().collect();
+
+	let mut num_added = 0;
+	for mut template in templates {
+		let pending: Vec<Date> = template
+			.get_occurrences(today)
+			.into_iter()
+			.filter(|date| {
+				template
+					.generated_until
+					.as_ref()
+					.is_none_or(|until| date > until)
+			})
+			.collect();
+		let Some(last) = pending.last().cloned() else {
+			continue;
+		};
+
+		for date in pending.iter() {
+			let year_data = all_data.add_year(date.year);
+			match template.kind {
+				RecurringKind::Expense => year_data
+					.get_expenses_mut()
+					.add(&date.month)
+					.push(template.make_expense(date)),
+				RecurringKind::Income => year_data
+					.get_incomes_mut()
+					.add(&date.month)
+					.push(template.make_income(date)),
+			}
+		}
+		num_added += pending.len();
+
+		template.generated_until = Some(last);
+		all_data.get_recurring_templates_mut().add(template);
+	}
+	num_added
+}
+
+// Positions of the activities added by the template of the given name.
+#[duplicate::duplicate_item(
+	method                        iter_months;
+	[find_generated_expenses]     [iter_expenses];
+	[find_generated_incomes]      [iter_incomes];
+)]
+fn method(all_data: &AllActivities, name: &str) -> Vec<(u32, Month, usize)> {
+	let mut positions = Vec::new();
+	for year_data in all_data.iter_activities() {
+		for month_data in year_data.iter_months() {
+			for (idx, a) in month_data.iter().enumerate() {
+				if a.recurring == name {
+					positions.push((*year_data.get_year(), month_data.get_month().clone(), idx));
+				}
+			}
+		}
+	}
+	positions
+}
+
+// Updates the activities added by the old template with the changes of the
+// new one, and removes those past its end. Without a new template, all of
+// them are removed. Returns the number of activities that changed and that
+// were removed.
+#[duplicate::duplicate_item(
+	method                      find_generated              get_collection_mut   update;
+	[update_generated_expenses] [find_generated_expenses]   [get_expenses_mut]   [update_expense];
+	[update_generated_incomes]  [find_generated_incomes]    [get_incomes_mut]    [update_income];
+)]
+fn method(
+	all_data: &mut AllActivities,
+	old: &RecurringTemplate,
+	new: Option<&RecurringTemplate>,
+) -> (usize, usize) {
+	let positions = find_generated(all_data, &old.name);
+
+	let mut num_changed = 0;
+	let mut num_removed = 0;
+	// from the last to the first so that removals do not shift the positions
+	for (year, month, idx) in positions.iter().rev() {
+		let month_data = all_data
+			.get_year_mut(year)
+			.expect("The year should exist")
+			.get_collection_mut()
+			.get_month_mut(month)
+			.expect("The month should exist");
+		let a = month_data.get_mut(*idx);
+		match new {
+			Some(template)
+				if template
+					.end
+					.as_ref()
+					.is_none_or(|end| a.day_of_year <= *end) =>
+			{
+				let before = a.clone();
+				template.update(old, a);
+				if *a != before {
+					num_changed += 1;
+				}
+			},
+			_ => {
+				month_data.remove(*idx);
+				num_removed += 1;
+			},
+		}
+	}
+	(num_changed, num_removed)
+}
+
+// Makes the activities added by the template of the given name look as if
+// they had been added by hand.
+#[duplicate::duplicate_item(
+	method                      find_generated              get_collection_mut;
+	[forget_generated_expenses] [find_generated_expenses]   [get_expenses_mut];
+	[forget_generated_incomes]  [find_generated_incomes]    [get_incomes_mut];
+)]
+fn method(all_data: &mut AllActivities, name: &str) {
+	for (year, month, idx) in find_generated(all_data, name) {
+		all_data
+			.get_year_mut(&year)
+			.expect("The year should exist")
+			.get_collection_mut()
+			.get_month_mut(&month)
+			.expect("The month should exist")
+			.get_mut(idx)
+			.recurring = "".to_string();
+	}
+}
+
+/**
+ * @brief Replaces a template with a new version of it, or removes it if no new
+ * one is given, together with the activities it added.
+ *
+ * The activities added by the old template take the changes made to it, but
+ * keep what was edited by hand in them, and those past the end of the new one
+ * are removed. Returns the number of activities that changed and that were
+ * removed.
+ */
+pub fn replace_template(
+	all_data: &mut AllActivities,
+	old: &RecurringTemplate,
+	mut new: Option<RecurringTemplate>,
+) -> (usize, usize) {
+	// occurrences past a new end were not added by the new template
+	if let Some(template) = new.as_mut() {
+		if let (Some(end), Some(until)) = (&template.end, &template.generated_until) {
+			if end < until {
+				template.generated_until = template.get_occurrences(end).last().cloned();
+			}
+		}
+	}
+
+	let counts = match old.kind {
+		RecurringKind::Expense => update_generated_expenses(all_data, old, new.as_ref()),
+		RecurringKind::Income => update_generated_incomes(all_data, old, new.as_ref()),
+	};
+
+	let templates = all_data.get_recurring_templates_mut();
+	templates.remove(&old.name);
+	if let Some(template) = new {
+		templates.add(template);
+	}
+	counts
+}
+
+/**
+ * @brief Removes a template but keeps the activities it added, which are then
+ * no longer linked to it.
+ */
+pub fn forget_template(all_data: &mut AllActivities, old: &RecurringTemplate) {
+	match old.kind {
+		RecurringKind::Expense => forget_generated_expenses(all_data, &old.name),
+		RecurringKind::Income => forget_generated_incomes(all_data, &old.name),
+	}
+	all_data.get_recurring_templates_mut().remove(&old.name);
+}
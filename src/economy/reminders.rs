@@ -0,0 +1,165 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use chrono::{Months, TimeDelta};
+
+use crate::economy::all_activities::AllActivities;
+use crate::economy::expense::Expense;
+use crate::economy::income::Income;
+use crate::economy::money::Money;
+use crate::economy::recurring::{RecurringKind, RecurringTemplate};
+
+use crate::time::date::Date;
+
+// Number of months in a row an activity has to appear in to be expected in
+// the current month.
+static NUM_MONTHS: u32 = 3;
+
+/**
+ * @brief Occurrences of the recurring activities after the given day and up
+ * to the given number of days later, sorted by date.
+ */
+pub fn get_upcoming<'a>(
+	all_data: &'a AllActivities,
+	today: &Date,
+	num_days: u32,
+) -> Vec<(Date, &'a RecurringTemplate)> {
+	let last = today.clone() + TimeDelta::days(num_days as i64);
+
+	let mut upcoming: Vec<(Date, &RecurringTemplate)> = all_data
+		.get_recurring_templates()
+		.iter()
+		.flat_map(|t| {
+			t.get_occurrences(&last)
+				.into_iter()
+				.filter(|date| date > today)
+				.map(move |date| (date, t))
+		})
+		.collect();
+	upcoming.sort_by(|(d1, t1), (d2, t2)| (d1, &t1.name).cmp(&(d2, &t2.name)));
+	upcoming
+}
+
+/**
+ * @brief An activity recorded every month that was not recorded yet in the
+ * current one.
+ */
+#[derive(Debug)]
+pub struct ExpectedActivity {
+	pub kind: RecurringKind,
+	pub concepts: Vec<String>,
+	// The shop of expenses or the origin of incomes.
+	pub counterpart: String,
+	// Day of the month it was recorded on last month.
+	pub day: u8,
+	// Amount it was recorded with last month.
+	pub price: Money,
+	pub currency: String,
+}
+
+#[duplicate::duplicate_item(
+	method                       activity  get_month_activities  counterpart_field activity_kind;
+	[find_expected_expenses]     [Expense] [get_month_expenses]  [shop]            [RecurringKind::Expense];
+	[find_expected_incomes]      [Income]  [get_month_incomes]   [from]            [RecurringKind::Income];
+)]
+fn method(all_data: &AllActivities, today: &Date, expected: &mut Vec<ExpectedActivity>) {
+	let first_day = Date {
+		year: today.year,
+		month: today.month.clone(),
+		day: 1,
+	};
+	let previous: Vec<Date> = (1..=NUM_MONTHS)
+		.map(|n| first_day.clone() - Months::new(n))
+		.collect();
+
+	let Some(last_month) = all_data.get_month_activities(&previous[0].year, &previous[0].month)
+	else {
+		return;
+	};
+	for a in last_month.iter() {
+		let same = |b: &activity| {
+			b.concepts == a.concepts
+				&& b.counterpart_field == a.counterpart_field
+				&& b.currency == a.currency
+		};
+		let recorded_in = |date: &Date| {
+			all_data
+				.get_month_activities(&date.year, &date.month)
+				.is_some_and(|month_data| month_data.iter().any(same))
+		};
+
+		if !previous[1..].iter().all(recorded_in) || recorded_in(&first_day) {
+			continue;
+		}
+		// recurring activities add their own occurrences
+		let has_template = !a.recurring.is_empty()
+			|| all_data.get_recurring_templates().iter().any(|t| {
+				t.kind == activity_kind
+					&& t.concepts == a.concepts
+					&& t.counterpart == a.counterpart_field
+					&& t.end.as_ref().is_none_or(|end| end >= &first_day)
+			});
+		let already_expected = expected.iter().any(|e| {
+			e.kind == activity_kind
+				&& e.concepts == a.concepts
+				&& e.counterpart == a.counterpart_field
+				&& e.currency == a.currency
+		});
+		if has_template || already_expected {
+			continue;
+		}
+
+		expected.push(ExpectedActivity {
+			kind: activity_kind,
+			concepts: a.concepts.clone(),
+			counterpart: a.counterpart_field.clone(),
+			day: a.day_of_year.day,
+			price: a.price,
+			currency: a.currency.clone(),
+		});
+	}
+}
+
+/**
+ * @brief Expenses and incomes recorded in each of the last months that were
+ * not recorded yet in the month of the given day, sorted by day.
+ *
+ * Those of recurring activities are left out since they are added on their
+ * own.
+ */
+pub fn get_expected(all_data: &AllActivities, today: &Date) -> Vec<ExpectedActivity> {
+	let mut expected = Vec::new();
+	find_expected_expenses(all_data, today, &mut expected);
+	find_expected_incomes(all_data, today, &mut expected);
+	expected.sort_by_key(|e| e.day);
+	expected
+}
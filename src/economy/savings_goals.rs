@@ -0,0 +1,396 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::economy::all_activities::AllActivities;
+use crate::economy::fields;
+use crate::economy::money::Money;
+
+use crate::time::date::{self, Date, Month, YearMonth};
+
+/**
+ * @brief An amount of money to be saved between two dates.
+ *
+ * What is saved in a month is what was earned minus what was spent. Linked
+ * concepts restrict the activities that count to those under them, and a
+ * linked account to those of that account, together with the transfers into
+ * and out of it.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavingsGoal {
+	pub name: String,
+	pub target: Money,
+	pub currency: String,
+	pub start: Date,
+	pub deadline: Date,
+	// All expenses count if empty.
+	pub expense_concepts: Vec<String>,
+	// All incomes count if empty.
+	pub income_concepts: Vec<String>,
+	// Activities of all accounts count if empty.
+	pub account: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseSavingsGoalError {
+	pub reason: String,
+	// Position, starting at 1, of the first wrong character of the line.
+	pub column: usize,
+}
+
+impl fmt::Display for ParseSavingsGoalError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} (column {})", self.reason, self.column)
+	}
+}
+
+impl std::error::Error for ParseSavingsGoalError {}
+
+fn split_concepts(concept_list: &str) -> Vec<String> {
+	concept_list
+		.split_terminator(';')
+		.map(str::trim)
+		.filter(|s| !s.is_empty())
+		.map(|s| s.to_string())
+		.collect()
+}
+
+impl FromStr for SavingsGoal {
+	type Err = ParseSavingsGoalError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parts = fields::split_fields(s);
+		let [name, target, currency, start, deadline, expense_list, income_list, account] =
+			parts.as_slice()
+		else {
+			return Err(ParseSavingsGoalError {
+				reason: format!("expected 8 fields, found {}", parts.len()),
+				column: 1,
+			});
+		};
+
+		let parse_date = |field: &fields::Field| {
+			Date::parse_valid(field.value).map_err(|e| ParseSavingsGoalError {
+				reason: e.reason,
+				column: field.column + e.column - 1,
+			})
+		};
+
+		let start_date = parse_date(start)?;
+		let deadline_date = parse_date(deadline)?;
+		if deadline_date < start_date {
+			return Err(ParseSavingsGoalError {
+				reason: "the deadline is earlier than the start".to_string(),
+				column: deadline.column,
+			});
+		}
+
+		Ok(SavingsGoal {
+			name: name.value.to_string(),
+			target: target
+				.value
+				.parse::<Money>()
+				.map_err(|_| ParseSavingsGoalError {
+					reason: format!("'{}' is not a valid amount", target.value),
+					column: target.column,
+				})?
+				.round_to_currency(currency.value),
+			currency: currency.value.to_string(),
+			start: start_date,
+			deadline: deadline_date,
+			expense_concepts: split_concepts(expense_list.value),
+			income_concepts: split_concepts(income_list.value),
+			account: account.value.to_string(),
+		})
+	}
+}
+
+#[derive(Debug)]
+pub struct SavingsGoals {
+	m_changes: bool,
+	m_goals: Vec<SavingsGoal>,
+}
+
+impl SavingsGoals {
+	pub fn new() -> SavingsGoals {
+		SavingsGoals {
+			m_changes: false,
+			m_goals: Vec::new(),
+		}
+	}
+
+	pub fn iter(&self) -> std::slice::Iter<'_, SavingsGoal> {
+		self.m_goals.iter()
+	}
+
+	pub fn get_names(&self) -> Vec<String> {
+		self.m_goals.iter().map(|g| g.name.clone()).collect()
+	}
+
+	fn find(&self, name: &str) -> Result<usize, usize> {
+		self.m_goals.binary_search_by(|g| g.name.as_str().cmp(name))
+	}
+
+	pub fn get_goal(&self, name: &str) -> Option<&SavingsGoal> {
+		self.find(name).ok().map(|idx| &self.m_goals[idx])
+	}
+
+	// Adds a goal, replacing the goal of the same name.
+	pub fn add(&mut self, goal: SavingsGoal) {
+		match self.find(&goal.name) {
+			Ok(idx) => {
+				self.m_goals[idx] = goal;
+			},
+			Err(idx) => {
+				self.m_goals.insert(idx, goal);
+			},
+		}
+	}
+
+	pub fn remove(&mut self, name: &str) -> bool {
+		match self.find(name) {
+			Ok(idx) => {
+				self.m_goals.remove(idx);
+				true
+			},
+			Err(_) => false,
+		}
+	}
+
+	pub fn size(&self) -> usize {
+		self.m_goals.len()
+	}
+
+	pub fn has_changes(&self) -> bool {
+		self.m_changes
+	}
+	pub fn set_changes(&mut self, c: bool) {
+		self.m_changes = c;
+	}
+}
+
+/* ------------------------------------------------------------------------- */
+
+/**
+ * @brief What was earned and spent towards a goal in a month.
+ *
+ * Amounts are in the currency of the goal.
+ */
+#[derive(Debug)]
+pub struct MonthSavings {
+	pub year: u32,
+	pub month: Month,
+	pub incomes: Money,
+	pub expenses: Money,
+	// Transferred into the linked account minus transferred out of it.
+	pub transfers: Money,
+}
+
+impl MonthSavings {
+	pub fn get_saved(&self) -> Money {
+		self.incomes - self.expenses + self.transfers
+	}
+}
+
+#[derive(Debug)]
+pub struct GoalProgress {
+	// From the month of the start to the current month, or to the month of
+	// the deadline if it has passed.
+	pub months: Vec<MonthSavings>,
+	// Months at the beginning of the above that are over.
+	pub num_complete_months: usize,
+	// Index in the above of the first month the goal covers entirely: 1 if
+	// the goal starts after the first day of its month, 0 otherwise.
+	pub first_full_month: usize,
+	pub saved: Money,
+	// Months left after the current one until the month of the deadline.
+	pub remaining_months: u32,
+	// Activities that could not be converted into the currency of the goal.
+	pub num_unconverted: u32,
+}
+
+impl GoalProgress {
+	// Number of months the goal covers entirely that are over.
+	pub fn get_num_rated_months(&self) -> usize {
+		self.num_complete_months
+			.saturating_sub(self.first_full_month)
+	}
+
+	// Average amount saved per month over the months the goal covers
+	// entirely that are over, zero before the first one is.
+	pub fn get_monthly_rate(&self) -> Money {
+		let num_months = self.get_num_rated_months();
+		if num_months == 0 {
+			return Money::new();
+		}
+		let mut saved = Money::new();
+		for month_savings in self.months[self.first_full_month..self.num_complete_months].iter() {
+			saved += month_savings.get_saved();
+		}
+		saved.multiply(1.0 / num_months as f64)
+	}
+
+	// Amount that will have been saved by the deadline at the current rate.
+	pub fn get_projection(&self) -> Money {
+		self.saved
+			+ self
+				.get_monthly_rate()
+				.multiply(self.remaining_months as f64)
+	}
+}
+
+fn count_months(from: &YearMonth, to: &YearMonth) -> u32 {
+	let from_index = from.year * 12 + from.month.clone() as u32;
+	let to_index = to.year * 12 + to.month.clone() as u32;
+	to_index.saturating_sub(from_index)
+}
+
+/**
+ * @brief Progress of a goal month by month up to the given day.
+ *
+ * Only the activities between the start of the goal, its deadline and the
+ * given day count.
+ */
+pub fn get_goal_progress(
+	all_data: &AllActivities,
+	goal: &SavingsGoal,
+	today: &Date,
+) -> GoalProgress {
+	let rates = all_data.get_exchange_rates();
+	let last_day = std::cmp::min(today, &goal.deadline).clone();
+	let counts = |day: &Date, account: &str| {
+		goal.start <= *day
+			&& *day <= last_day
+			&& (goal.account.is_empty() || goal.account == account)
+	};
+
+	let first = YearMonth {
+		year: goal.start.year,
+		month: goal.start.month.clone(),
+	};
+	let last = YearMonth {
+		year: last_day.year,
+		month: last_day.month.clone(),
+	};
+	let deadline = YearMonth {
+		year: goal.deadline.year,
+		month: goal.deadline.month.clone(),
+	};
+	let current = YearMonth {
+		year: today.year,
+		month: today.month.clone(),
+	};
+
+	let mut progress = GoalProgress {
+		months: Vec::new(),
+		num_complete_months: 0,
+		first_full_month: if goal.start.day > 1 { 1 } else { 0 },
+		saved: Money::new(),
+		remaining_months: count_months(&last, &deadline),
+		num_unconverted: 0,
+	};
+	if last_day < goal.start {
+		progress.remaining_months = count_months(&first, &deadline) + 1;
+		return progress;
+	}
+
+	for YearMonth { year, month } in date::month_range(first, last) {
+		let mut incomes = Money::new();
+		let mut expenses = Money::new();
+		let mut transfers = Money::new();
+
+		if let Some(month_data) = all_data.get_month_incomes(&year, &month) {
+			for i in month_data.iter() {
+				if !counts(&i.day_of_year, &i.account)
+					|| !i.concepts.starts_with(&goal.income_concepts)
+				{
+					continue;
+				}
+				match rates.convert_to(i.price, &i.currency, &goal.currency, &i.day_of_year) {
+					Some(value) => incomes += value,
+					None => progress.num_unconverted += 1,
+				}
+			}
+		}
+		if let Some(month_data) = all_data.get_month_expenses(&year, &month) {
+			for e in month_data.iter() {
+				if !counts(&e.day_of_year, &e.account)
+					|| !e.concepts.starts_with(&goal.expense_concepts)
+				{
+					continue;
+				}
+				match rates.convert_to(e.price, &e.currency, &goal.currency, &e.day_of_year) {
+					Some(value) => expenses += value,
+					None => progress.num_unconverted += 1,
+				}
+			}
+		}
+
+		// money moved between the linked account and the others
+		if !goal.account.is_empty() {
+			if let Some(month_data) = all_data.get_month_transfers(&year, &month) {
+				for t in month_data.iter() {
+					let into = t.to_account == goal.account;
+					let out_of = t.from_account == goal.account;
+					if into == out_of || !counts(&t.day_of_year, &goal.account) {
+						continue;
+					}
+					match rates.convert_to(t.price, &t.currency, &goal.currency, &t.day_of_year) {
+						Some(value) if into => transfers += value,
+						Some(value) => transfers -= value,
+						None => progress.num_unconverted += 1,
+					}
+				}
+			}
+		}
+
+		let this_month = YearMonth {
+			year,
+			month: month.clone(),
+		};
+		if count_months(&this_month, &current) > 0 {
+			progress.num_complete_months += 1;
+		}
+		let month_savings = MonthSavings {
+			year,
+			month,
+			incomes,
+			expenses,
+			transfers,
+		};
+		progress.saved += month_savings.get_saved();
+		progress.months.push(month_savings);
+	}
+	progress
+}
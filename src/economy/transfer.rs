@@ -0,0 +1,121 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::economy::traits::AsReferences;
+
+use crate::economy::fields;
+use crate::economy::money::Money;
+
+use crate::time::date;
+
+// A movement of money between two accounts. It is neither spending nor
+// earning, and therefore does not appear in the expense or income summaries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transfer {
+	pub day_of_year: date::Date,
+	pub price: Money,
+	pub currency: String,
+	pub from_account: String,
+	pub to_account: String,
+	pub description: String,
+}
+
+impl Eq for Transfer {}
+
+impl Ord for Transfer {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.day_of_year.cmp(&other.day_of_year)
+	}
+}
+impl PartialOrd for Transfer {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseTransferError {
+	pub reason: String,
+	// Position, starting at 1, of the first wrong character of the line.
+	pub column: usize,
+}
+
+impl std::fmt::Display for ParseTransferError {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "{} (column {})", self.reason, self.column)
+	}
+}
+
+impl std::error::Error for ParseTransferError {}
+
+impl std::str::FromStr for Transfer {
+	type Err = ParseTransferError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let parts = fields::split_fields(s);
+		if parts.len() != 6 {
+			return Err(ParseTransferError {
+				reason: format!("expected 6 fields, found {}", parts.len()),
+				column: 1,
+			});
+		}
+		let [d, pr, cur, fr, to, descr] =
+			[parts[0], parts[1], parts[2], parts[3], parts[4], parts[5]].map(|f| f.value);
+
+		let date_fromstr = d.parse::<date::Date>().map_err(|e| ParseTransferError {
+			reason: e.reason,
+			column: parts[0].column + e.column - 1,
+		})?;
+		let price_fromstr = pr.parse::<Money>().map_err(|_| ParseTransferError {
+			reason: format!("'{pr}' is not a valid amount"),
+			column: parts[1].column,
+		})?;
+
+		Ok(Transfer {
+			day_of_year: date_fromstr,
+			price: price_fromstr.round_to_currency(cur),
+			currency: cur.to_string(),
+			from_account: fr.to_string(),
+			to_account: to.to_string(),
+			description: descr.to_string(),
+		})
+	}
+}
+
+impl AsReferences<Transfer> for Transfer {
+	fn as_ref(&self) -> &Transfer {
+		self
+	}
+	fn as_mut(&mut self) -> &mut Transfer {
+		self
+	}
+}
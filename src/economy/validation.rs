@@ -0,0 +1,213 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::fmt;
+
+use crate::economy::accounts::Accounts;
+use crate::economy::all_activities::AllActivities;
+use crate::economy::money::Money;
+
+use crate::concepts::tree::Tree;
+
+use crate::time::date::{Date, Month};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActivityKind {
+	Expense,
+	Income,
+	Transfer,
+}
+
+impl fmt::Display for ActivityKind {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			ActivityKind::Expense => write!(f, "Expense"),
+			ActivityKind::Income => write!(f, "Income"),
+			ActivityKind::Transfer => write!(f, "Transfer"),
+		}
+	}
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Problem {
+	// The day does not exist in the month.
+	InvalidDate(Date),
+	NegativePrice(Money),
+	// The concepts are not a branch of the tree of concepts.
+	UnknownConcepts(Vec<String>),
+	// The date does not fall in the year and month the activity is filed in.
+	MisfiledDate(Date),
+	UnknownAccount(String),
+	// A transfer from an account to itself.
+	SameAccounts(String),
+}
+
+/**
+ * @brief An inconsistency found in an activity.
+ *
+ * The activity is the one at position 'index' within the month 'month' of
+ * year 'year' of the activities of its kind.
+ */
+#[derive(Debug, Clone, PartialEq)]
+pub struct Issue {
+	pub kind: ActivityKind,
+	pub year: u32,
+	pub month: Month,
+	pub index: usize,
+	pub problem: Problem,
+}
+
+impl fmt::Display for Issue {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(
+			f,
+			"{} with id {} of {} {}: ",
+			self.kind, self.index, self.month, self.year
+		)?;
+		match &self.problem {
+			Problem::InvalidDate(d) => write!(f, "date '{d}' does not exist"),
+			Problem::NegativePrice(p) => write!(f, "price '{p}' is negative"),
+			Problem::UnknownConcepts(cs) => {
+				write!(f, "concepts '{}' do not exist", cs.join(";"))
+			},
+			Problem::MisfiledDate(d) => {
+				write!(f, "date '{d}' is not in {} {}", self.month, self.year)
+			},
+			Problem::UnknownAccount(a) => write!(f, "account '{a}' does not exist"),
+			Problem::SameAccounts(a) => write!(f, "transfer from account '{a}' to itself"),
+		}
+	}
+}
+
+// Whether the concepts are a branch, starting at the root, of the tree.
+pub fn has_concepts(tree: &Tree, concepts: &[String]) -> bool {
+	let mut subtree = Some(tree);
+	for c in concepts.iter() {
+		let Some(t) = subtree else {
+			return false;
+		};
+		if !t.get_keys().contains(&c) {
+			return false;
+		}
+		subtree = t.get_child(c);
+	}
+	true
+}
+
+fn check_account(account: &str, accounts: &Accounts, problems: &mut Vec<Problem>) {
+	if !account.is_empty() && !accounts.has_account(account) {
+		problems.push(Problem::UnknownAccount(account.to_string()));
+	}
+}
+
+fn check_common(year: u32, month: &Month, date: &Date, price: Money, problems: &mut Vec<Problem>) {
+	if !date.is_valid() {
+		problems.push(Problem::InvalidDate(date.clone()));
+	}
+	if date.year != year || date.month != *month {
+		problems.push(Problem::MisfiledDate(date.clone()));
+	}
+	if price.is_negative() {
+		problems.push(Problem::NegativePrice(price));
+	}
+}
+
+#[duplicate::duplicate_item(
+	method              iter_year       concept_types          activity_kind;
+	[validate_expenses] [iter_expenses] [get_expense_concepts] [ActivityKind::Expense];
+	[validate_incomes]  [iter_incomes]  [get_income_concepts]  [ActivityKind::Income];
+)]
+fn method(all_data: &AllActivities, issues: &mut Vec<Issue>) {
+	let tree = all_data.concept_types().get_tree();
+	let accounts = all_data.get_accounts();
+
+	for ye in all_data.iter_activities() {
+		let year = *ye.get_year();
+		for me in ye.iter_year() {
+			let month = me.get_month();
+			for (index, a) in me.iter().enumerate() {
+				let mut problems = Vec::new();
+				check_common(year, month, &a.day_of_year, a.price, &mut problems);
+				if !has_concepts(tree, &a.concepts) {
+					problems.push(Problem::UnknownConcepts(a.concepts.clone()));
+				}
+				check_account(&a.account, accounts, &mut problems);
+
+				issues.extend(problems.into_iter().map(|problem| Issue {
+					kind: activity_kind,
+					year,
+					month: month.clone(),
+					index,
+					problem,
+				}));
+			}
+		}
+	}
+}
+
+fn validate_transfers(all_data: &AllActivities, issues: &mut Vec<Issue>) {
+	let accounts = all_data.get_accounts();
+
+	for ye in all_data.iter_activities() {
+		let year = *ye.get_year();
+		for mt in ye.iter_transfers() {
+			let month = mt.get_month();
+			for (index, t) in mt.iter().enumerate() {
+				let mut problems = Vec::new();
+				check_common(year, month, &t.day_of_year, t.price, &mut problems);
+				check_account(&t.from_account, accounts, &mut problems);
+				check_account(&t.to_account, accounts, &mut problems);
+				if t.from_account == t.to_account {
+					problems.push(Problem::SameAccounts(t.from_account.clone()));
+				}
+
+				issues.extend(problems.into_iter().map(|problem| Issue {
+					kind: ActivityKind::Transfer,
+					year,
+					month: month.clone(),
+					index,
+					problem,
+				}));
+			}
+		}
+	}
+}
+
+// All the inconsistencies in the activities, sorted by kind of activity and
+// then by the place where the activities are filed.
+pub fn validate(all_data: &AllActivities) -> Vec<Issue> {
+	let mut issues = Vec::new();
+	validate_expenses(all_data, &mut issues);
+	validate_incomes(all_data, &mut issues);
+	validate_transfers(all_data, &mut issues);
+	issues
+}
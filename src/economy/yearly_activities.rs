@@ -35,6 +35,7 @@ use crate::economy::monthly_activities::MonthlyActivitiesCollection;
 
 use crate::economy::expense::Expense;
 use crate::economy::income::Income;
+use crate::economy::transfer::Transfer;
 
 #[derive(Debug)]
 pub struct YearlyActivities {
@@ -42,6 +43,7 @@ pub struct YearlyActivities {
 
 	m_expenses: MonthlyActivitiesCollection<Expense>,
 	m_incomes: MonthlyActivitiesCollection<Income>,
+	m_transfers: MonthlyActivitiesCollection<Transfer>,
 }
 
 impl YearlyActivities {
@@ -50,6 +52,7 @@ impl YearlyActivities {
 			m_year: 0,
 			m_expenses: MonthlyActivitiesCollection::new(),
 			m_incomes: MonthlyActivitiesCollection::new(),
+			m_transfers: MonthlyActivitiesCollection::new(),
 		}
 	}
 	pub fn new_year(y: u32) -> YearlyActivities {
@@ -57,6 +60,7 @@ impl YearlyActivities {
 			m_year: y,
 			m_expenses: MonthlyActivitiesCollection::new_changes(true),
 			m_incomes: MonthlyActivitiesCollection::new_changes(true),
+			m_transfers: MonthlyActivitiesCollection::new_changes(true),
 		}
 	}
 	pub fn new_changes(c: bool) -> YearlyActivities {
@@ -64,6 +68,7 @@ impl YearlyActivities {
 			m_year: 0,
 			m_expenses: MonthlyActivitiesCollection::new_changes(c),
 			m_incomes: MonthlyActivitiesCollection::new_changes(c),
+			m_transfers: MonthlyActivitiesCollection::new_changes(c),
 		}
 	}
 	pub fn new_year_changes(y: u32, c: bool) -> YearlyActivities {
@@ -71,6 +76,7 @@ impl YearlyActivities {
 			m_year: y,
 			m_expenses: MonthlyActivitiesCollection::new_changes(c),
 			m_incomes: MonthlyActivitiesCollection::new_changes(c),
+			m_transfers: MonthlyActivitiesCollection::new_changes(c),
 		}
 	}
 
@@ -106,6 +112,22 @@ impl YearlyActivities {
 		&mut self.m_incomes
 	}
 
+	pub fn iter_transfers(&self) -> std::slice::Iter<'_, MonthlyActivities<Transfer>> {
+		self.m_transfers.iter()
+	}
+	pub fn iter_mut_transfers(&mut self) -> std::slice::IterMut<'_, MonthlyActivities<Transfer>> {
+		self.m_transfers.set_changes(true);
+		self.m_transfers.iter_mut()
+	}
+
+	pub fn get_transfers(&self) -> &MonthlyActivitiesCollection<Transfer> {
+		&self.m_transfers
+	}
+	pub fn get_transfers_mut(&mut self) -> &mut MonthlyActivitiesCollection<Transfer> {
+		self.m_transfers.set_changes(true);
+		&mut self.m_transfers
+	}
+
 	pub fn get_year(&self) -> &u32 {
 		&self.m_year
 	}
@@ -124,11 +146,18 @@ impl YearlyActivities {
 	pub fn merge(&mut self, year_acts: YearlyActivities) {
 		self.m_expenses.merge(year_acts.m_expenses);
 		self.m_incomes.merge(year_acts.m_incomes);
+		self.m_transfers.merge(year_acts.m_transfers);
 	}
 
+	pub fn has_changes(&self) -> bool {
+		self.m_expenses.has_changes()
+			|| self.m_incomes.has_changes()
+			|| self.m_transfers.has_changes()
+	}
 	pub fn set_changes(&mut self, c: bool) {
 		self.m_expenses.set_changes(c);
 		self.m_incomes.set_changes(c);
+		self.m_transfers.set_changes(c);
 	}
 }
 
@@ -0,0 +1,152 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind, Result, Write};
+
+use crate::economy::all_activities::AllActivities;
+
+use crate::export::DateRange;
+use crate::export::Transaction;
+
+// Beancount account names are made of components that start with a capital
+// letter or a digit and contain only letters, digits and dashes.
+fn account_component(name: &str) -> String {
+	let mut component = String::new();
+	for c in name.chars() {
+		if c.is_alphanumeric() {
+			component.push(c);
+		} else if !component.is_empty() && !component.ends_with('-') {
+			component.push('-');
+		}
+	}
+	while component.ends_with('-') {
+		component.pop();
+	}
+
+	let mut chars = component.chars();
+	match chars.next() {
+		Some(first) => first.to_uppercase().chain(chars).collect(),
+		None => "Unnamed".to_string(),
+	}
+}
+
+fn category_account(t: &Transaction) -> String {
+	let root = if t.is_expense { "Expenses" } else { "Income" };
+	if t.concepts.is_empty() {
+		return format!("{root}:Uncategorized");
+	}
+	let components: Vec<String> = t.concepts.iter().map(|c| account_component(c)).collect();
+	format!("{root}:{}", components.join(":"))
+}
+
+fn asset_account(t: &Transaction) -> String {
+	if t.account.is_empty() {
+		"Assets:Unassigned".to_string()
+	} else {
+		format!("Assets:{}", account_component(t.account))
+	}
+}
+
+fn quote(s: &str) -> String {
+	format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/**
+ * @brief Writes the expenses and incomes as a Beancount ledger.
+ *
+ * Every account used is opened on the date of its first transaction. Expenses
+ * move money from the account of the activity to 'Expenses:<concepts>' and
+ * incomes from 'Income:<concepts>' to the account. Returns the number of
+ * transactions written.
+ */
+pub fn write_beancount<W: Write>(
+	out: &mut W,
+	all_data: &AllActivities,
+	range: &DateRange,
+	operating_currency: &str,
+) -> Result<usize> {
+	let rates = all_data.get_exchange_rates();
+	let transactions = super::collect_transactions(all_data, range, rates.get_home_currency());
+
+	if let Some(t) = transactions.iter().find(|t| !t.date.is_valid()) {
+		return Err(Error::new(
+			ErrorKind::InvalidData,
+			format!("date '{}' does not exist", t.date),
+		));
+	}
+
+	writeln!(
+		out,
+		"option \"operating_currency\" {}",
+		quote(operating_currency)
+	)?;
+	writeln!(out)?;
+
+	// transactions are sorted, so the first date seen is the earliest
+	let mut opened = BTreeMap::new();
+	for t in transactions.iter() {
+		for account in [category_account(t), asset_account(t)] {
+			opened.entry(account).or_insert(t.date);
+		}
+	}
+	let mut opened: Vec<_> = opened.into_iter().collect();
+	opened.sort_by(|(a1, d1), (a2, d2)| d1.cmp(d2).then(a1.cmp(a2)));
+	for (account, date) in opened.iter() {
+		writeln!(out, "{date:#} open {account}")?;
+	}
+
+	for t in transactions.iter() {
+		let (to, from) = if t.is_expense {
+			(category_account(t), asset_account(t))
+		} else {
+			(asset_account(t), category_account(t))
+		};
+
+		writeln!(out)?;
+		writeln!(
+			out,
+			"{:#} * {} {}",
+			t.date,
+			quote(t.payee),
+			quote(t.narration)
+		)?;
+		writeln!(
+			out,
+			"  {to}  {} {}",
+			t.amount.display_in(t.currency),
+			t.currency
+		)?;
+		writeln!(out, "  {from}")?;
+	}
+	Ok(transactions.len())
+}
@@ -0,0 +1,187 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::fmt::Write;
+
+use crate::export::table::Field;
+use crate::export::table::Table;
+
+static STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; color: #222; }
+h1 { border-bottom: 2px solid #444; }
+h2 { margin-top: 1.5em; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border: 1px solid #bbb; padding: 0.25em 0.6em; }
+th { background: #eee; }
+td.number { text-align: right; }
+svg text { font-size: 12px; }
+";
+
+static LABEL_WIDTH: usize = 180;
+static BAR_WIDTH: usize = 360;
+static VALUE_WIDTH: usize = 120;
+static BAR_HEIGHT: usize = 18;
+static BAR_SPACING: usize = 6;
+
+fn escape(s: &str) -> String {
+	let mut escaped = String::with_capacity(s.len());
+	for c in s.chars() {
+		match c {
+			'&' => escaped.push_str("&amp;"),
+			'<' => escaped.push_str("&lt;"),
+			'>' => escaped.push_str("&gt;"),
+			'"' => escaped.push_str("&quot;"),
+			'\'' => escaped.push_str("&#39;"),
+			_ => escaped.push(c),
+		}
+	}
+	escaped
+}
+
+/**
+ * @brief A self-contained HTML document made of headings, paragraphs, tables
+ * and bar charts.
+ *
+ * The style and the charts (as SVG) are written inline, so the document needs
+ * no other file nor any network resource.
+ */
+pub struct HtmlReport {
+	m_title: String,
+	m_body: String,
+}
+
+impl HtmlReport {
+	pub fn new(title: &str) -> HtmlReport {
+		HtmlReport {
+			m_title: title.to_string(),
+			m_body: String::new(),
+		}
+	}
+
+	pub fn add_heading(&mut self, text: &str) {
+		writeln!(self.m_body, "<h2>{}</h2>", escape(text)).unwrap();
+	}
+
+	pub fn add_paragraph(&mut self, text: &str) {
+		writeln!(self.m_body, "<p>{}</p>", escape(text)).unwrap();
+	}
+
+	pub fn add_table(&mut self, table: &Table) {
+		if table.num_rows() == 0 {
+			self.add_paragraph("Nothing to show.");
+			return;
+		}
+
+		self.m_body.push_str("<table>\n<tr>");
+		for name in table.get_header().iter() {
+			// 'transaction_id' is shown as 'Transaction id'
+			let mut chars = name.chars();
+			let title: String = match chars.next() {
+				Some(first) => first.to_uppercase().chain(chars).collect(),
+				None => String::new(),
+			};
+			write!(self.m_body, "<th>{}</th>", escape(&title.replace('_', " "))).unwrap();
+		}
+		self.m_body.push_str("</tr>\n");
+
+		for row in table.get_rows().iter() {
+			self.m_body.push_str("<tr>");
+			for field in row.iter() {
+				match field {
+					Field::Text(s) => write!(self.m_body, "<td>{}</td>", escape(s)),
+					Field::Number(s) => {
+						write!(self.m_body, "<td class=\"number\">{}</td>", escape(s))
+					},
+					Field::List(l) => write!(self.m_body, "<td>{}</td>", escape(&l.join(" ; "))),
+				}
+				.unwrap();
+			}
+			self.m_body.push_str("</tr>\n");
+		}
+		self.m_body.push_str("</table>\n");
+	}
+
+	// A horizontal bar for every label, as long as its value relative to the
+	// largest one. Negative values are drawn as empty bars.
+	pub fn add_bar_chart(&mut self, bars: &[(String, f64)], unit: &str) {
+		if bars.is_empty() {
+			return;
+		}
+
+		let max_value = bars.iter().map(|(_, v)| *v).fold(0.0, f64::max);
+		let width = LABEL_WIDTH + BAR_WIDTH + VALUE_WIDTH;
+		let height = bars.len() * (BAR_HEIGHT + BAR_SPACING) + BAR_SPACING;
+
+		writeln!(
+			self.m_body,
+			"<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\">"
+		)
+		.unwrap();
+		for (i, (label, value)) in bars.iter().enumerate() {
+			let y = BAR_SPACING + i * (BAR_HEIGHT + BAR_SPACING);
+			let text_y = y + BAR_HEIGHT - 5;
+			let length = if max_value > 0.0 {
+				(value.max(0.0) / max_value * BAR_WIDTH as f64).round() as usize
+			} else {
+				0
+			};
+			writeln!(
+				self.m_body,
+				"<text x=\"{}\" y=\"{text_y}\" text-anchor=\"end\">{}</text>",
+				LABEL_WIDTH - 6,
+				escape(label)
+			)
+			.unwrap();
+			writeln!(
+				self.m_body,
+				"<rect x=\"{LABEL_WIDTH}\" y=\"{y}\" width=\"{length}\" height=\"{BAR_HEIGHT}\" fill=\"#4a7ebb\"/>"
+			)
+			.unwrap();
+			writeln!(
+				self.m_body,
+				"<text x=\"{}\" y=\"{text_y}\">{value:.2} {}</text>",
+				LABEL_WIDTH + length + 6,
+				escape(unit)
+			)
+			.unwrap();
+		}
+		self.m_body.push_str("</svg>\n");
+	}
+
+	pub fn to_html(&self) -> String {
+		format!(
+			"<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>{STYLE}</style>\n</head>\n<body>\n<h1>{title}</h1>\n{}</body>\n</html>\n",
+			self.m_body,
+			title = escape(&self.m_title)
+		)
+	}
+}
@@ -0,0 +1,115 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::io::{Error, ErrorKind, Result, Write};
+
+use crate::economy::all_activities::AllActivities;
+
+use crate::export::DateRange;
+use crate::export::Transaction;
+
+// Ledger account names may contain spaces, but not colons, which separate
+// the components, nor two spaces in a row, which end the name.
+fn account_component(name: &str) -> String {
+	let component = name
+		.replace(':', "-")
+		.split_whitespace()
+		.collect::<Vec<_>>()
+		.join(" ");
+	if component.is_empty() {
+		"Unnamed".to_string()
+	} else {
+		component
+	}
+}
+
+fn category_account(t: &Transaction) -> String {
+	let root = if t.is_expense { "Expenses" } else { "Income" };
+	if t.concepts.is_empty() {
+		return format!("{root}:Uncategorized");
+	}
+	let components: Vec<String> = t.concepts.iter().map(|c| account_component(c)).collect();
+	format!("{root}:{}", components.join(":"))
+}
+
+fn asset_account(t: &Transaction) -> String {
+	if t.account.is_empty() {
+		"Assets:Unassigned".to_string()
+	} else {
+		format!("Assets:{}", account_component(t.account))
+	}
+}
+
+/**
+ * @brief Writes the expenses and incomes as a Ledger journal.
+ *
+ * The journal can also be read by hledger. The description of every activity
+ * is written as a comment of its transaction. Returns the number of
+ * transactions written.
+ */
+pub fn write_ledger<W: Write>(
+	out: &mut W,
+	all_data: &AllActivities,
+	range: &DateRange,
+) -> Result<usize> {
+	let rates = all_data.get_exchange_rates();
+	let transactions = super::collect_transactions(all_data, range, rates.get_home_currency());
+
+	for t in transactions.iter() {
+		if !t.date.is_valid() {
+			return Err(Error::new(
+				ErrorKind::InvalidData,
+				format!("date '{}' does not exist", t.date),
+			));
+		}
+
+		let (to, from) = if t.is_expense {
+			(category_account(t), asset_account(t))
+		} else {
+			(asset_account(t), category_account(t))
+		};
+
+		writeln!(out, "{:#} {}", t.date, t.payee.trim())?;
+		if !t.narration.is_empty() {
+			writeln!(out, "    ; {}", t.narration)?;
+		}
+		writeln!(
+			out,
+			"    {to}  {} {}",
+			t.amount.display_in(t.currency),
+			t.currency
+		)?;
+		writeln!(out, "    {from}")?;
+		writeln!(out)?;
+	}
+	Ok(transactions.len())
+}
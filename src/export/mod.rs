@@ -0,0 +1,132 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::economy::all_activities::AllActivities;
+use crate::economy::money::Money;
+
+use crate::time::date::Date;
+
+pub mod beancount;
+pub mod html;
+pub mod ledger;
+pub mod qif;
+pub mod table;
+
+/**
+ * @brief Dates between 'first' and 'last', both included.
+ *
+ * A missing end leaves the range open on that side.
+ */
+#[derive(Debug, Clone)]
+pub struct DateRange {
+	pub first: Option<Date>,
+	pub last: Option<Date>,
+}
+
+impl DateRange {
+	pub fn contains(&self, date: &Date) -> bool {
+		self.first.as_ref().is_none_or(|f| f <= date)
+			&& self.last.as_ref().is_none_or(|l| date <= l)
+	}
+}
+
+/**
+ * @brief An expense or income as a double-entry transaction.
+ *
+ * The money moves from the account of the activity to its category for
+ * expenses, and from its category to the account for incomes.
+ */
+pub struct Transaction<'a> {
+	pub date: &'a Date,
+	pub amount: Money,
+	pub currency: &'a str,
+	pub is_expense: bool,
+	pub concepts: &'a [String],
+	pub account: &'a str,
+	pub payee: &'a str,
+	pub narration: &'a str,
+}
+
+/**
+ * @brief The expenses and incomes within the range, sorted by date.
+ *
+ * Activities without a currency are in 'home_currency'.
+ */
+pub fn collect_transactions<'a>(
+	all_data: &'a AllActivities,
+	range: &DateRange,
+	home_currency: &'a str,
+) -> Vec<Transaction<'a>> {
+	let currency = |c: &'a String| {
+		if c.is_empty() {
+			home_currency
+		} else {
+			c.as_str()
+		}
+	};
+
+	let mut transactions = Vec::new();
+	for year_data in all_data.iter_activities() {
+		for e in year_data.iter_expenses().flat_map(|m| m.iter()) {
+			if !range.contains(&e.day_of_year) {
+				continue;
+			}
+			transactions.push(Transaction {
+				date: &e.day_of_year,
+				amount: e.price,
+				currency: currency(&e.currency),
+				is_expense: true,
+				concepts: &e.concepts,
+				account: &e.account,
+				payee: &e.shop,
+				narration: &e.description,
+			});
+		}
+		for i in year_data.iter_incomes().flat_map(|m| m.iter()) {
+			if !range.contains(&i.day_of_year) {
+				continue;
+			}
+			transactions.push(Transaction {
+				date: &i.day_of_year,
+				amount: i.price,
+				currency: currency(&i.currency),
+				is_expense: false,
+				concepts: &i.concepts,
+				account: &i.account,
+				payee: &i.from,
+				narration: &i.description,
+			});
+		}
+	}
+	transactions.sort_by(|a, b| a.date.cmp(b.date));
+	transactions
+}
@@ -0,0 +1,110 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::io::{Error, ErrorKind, Result, Write};
+
+use crate::economy::all_activities::AllActivities;
+use crate::economy::money::Money;
+
+use crate::time::date::Date;
+
+// A transaction of the file. Money paid is negative.
+struct Transaction<'a> {
+	date: &'a Date,
+	amount: Money,
+	currency: &'a str,
+	payee: &'a str,
+	description: &'a str,
+	concepts: &'a [String],
+}
+
+/**
+ * @brief Writes the expenses and incomes as a bank section of a QIF file.
+ *
+ * Only the activities of 'year' are written, if given. Amounts are written in
+ * the currency of each activity. Returns the number of transactions written.
+ */
+pub fn write_qif<W: Write>(
+	out: &mut W,
+	all_data: &AllActivities,
+	year: Option<u32>,
+	date_format: &str,
+) -> Result<usize> {
+	let mut transactions = Vec::new();
+	for year_data in all_data.iter_activities() {
+		if year.is_some_and(|y| y != *year_data.get_year()) {
+			continue;
+		}
+		for e in year_data.iter_expenses().flat_map(|m| m.iter()) {
+			transactions.push(Transaction {
+				date: &e.day_of_year,
+				amount: -e.price,
+				currency: &e.currency,
+				payee: &e.shop,
+				description: &e.description,
+				concepts: &e.concepts,
+			});
+		}
+		for i in year_data.iter_incomes().flat_map(|m| m.iter()) {
+			transactions.push(Transaction {
+				date: &i.day_of_year,
+				amount: i.price,
+				currency: &i.currency,
+				payee: &i.from,
+				description: &i.description,
+				concepts: &i.concepts,
+			});
+		}
+	}
+	transactions.sort_by(|a, b| a.date.cmp(b.date));
+
+	writeln!(out, "!Type:Bank")?;
+	for t in transactions.iter() {
+		let Some(date) = t.date.to_naive_date() else {
+			return Err(Error::new(
+				ErrorKind::InvalidData,
+				format!("date '{}' does not exist", t.date),
+			));
+		};
+		writeln!(out, "D{}", date.format(date_format))?;
+		writeln!(out, "T{}", t.amount.display_in(t.currency))?;
+		writeln!(out, "P{}", t.payee)?;
+		if !t.description.is_empty() {
+			writeln!(out, "M{}", t.description)?;
+		}
+		if !t.concepts.is_empty() {
+			writeln!(out, "L{}", t.concepts.join(":"))?;
+		}
+		writeln!(out, "^")?;
+	}
+	Ok(transactions.len())
+}
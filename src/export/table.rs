@@ -0,0 +1,162 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::io::{Result, Write};
+
+/**
+ * @brief A value of a table.
+ *
+ * Lists are joined with ';' in CSV files and written as arrays in JSON files.
+ * Numbers are written without quotes in JSON files.
+ */
+#[derive(Debug, Clone)]
+pub enum Field {
+	Text(String),
+	Number(String),
+	List(Vec<String>),
+}
+
+/**
+ * @brief Format of the file a table is written into.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+	Csv,
+	Json,
+}
+
+impl TableFormat {
+	// The format stated by the extension of the file name.
+	pub fn from_filename(filename: &str) -> Option<TableFormat> {
+		let extension = std::path::Path::new(filename).extension()?.to_str()?;
+		match extension.to_lowercase().as_str() {
+			"csv" => Some(TableFormat::Csv),
+			"json" => Some(TableFormat::Json),
+			_ => None,
+		}
+	}
+}
+
+/**
+ * @brief Rows of values under named columns.
+ */
+#[derive(Debug)]
+pub struct Table {
+	m_header: Vec<String>,
+	m_rows: Vec<Vec<Field>>,
+}
+
+fn csv_field(s: &str) -> String {
+	if s.contains([',', '"', '\n', '\r']) {
+		format!("\"{}\"", s.replace('"', "\"\""))
+	} else {
+		s.to_string()
+	}
+}
+
+fn json_string(s: &str) -> String {
+	serde_json::to_string(s).expect("Strings can always be serialized")
+}
+
+impl Table {
+	pub fn new(header: &[&str]) -> Table {
+		Table {
+			m_header: header.iter().map(|h| h.to_string()).collect(),
+			m_rows: Vec::new(),
+		}
+	}
+
+	pub fn add_row(&mut self, row: Vec<Field>) {
+		assert_eq!(row.len(), self.m_header.len());
+		self.m_rows.push(row);
+	}
+
+	pub fn get_header(&self) -> &Vec<String> {
+		&self.m_header
+	}
+	pub fn get_rows(&self) -> &Vec<Vec<Field>> {
+		&self.m_rows
+	}
+
+	pub fn num_rows(&self) -> usize {
+		self.m_rows.len()
+	}
+
+	pub fn write<W: Write>(&self, out: &mut W, format: TableFormat) -> Result<()> {
+		match format {
+			TableFormat::Csv => self.write_csv(out),
+			TableFormat::Json => self.write_json(out),
+		}
+	}
+
+	// A header line followed by one line per row.
+	fn write_csv<W: Write>(&self, out: &mut W) -> Result<()> {
+		let header: Vec<String> = self.m_header.iter().map(|h| csv_field(h)).collect();
+		writeln!(out, "{}", header.join(","))?;
+
+		for row in self.m_rows.iter() {
+			let fields: Vec<String> = row
+				.iter()
+				.map(|f| match f {
+					Field::Text(s) | Field::Number(s) => csv_field(s),
+					Field::List(l) => csv_field(&l.join(";")),
+				})
+				.collect();
+			writeln!(out, "{}", fields.join(","))?;
+		}
+		Ok(())
+	}
+
+	// An array with one object per row, keyed by the names of the columns.
+	fn write_json<W: Write>(&self, out: &mut W) -> Result<()> {
+		writeln!(out, "[")?;
+		for (i, row) in self.m_rows.iter().enumerate() {
+			writeln!(out, "  {{")?;
+			for (j, (name, field)) in self.m_header.iter().zip(row.iter()).enumerate() {
+				let value = match field {
+					Field::Text(s) => json_string(s),
+					Field::Number(s) => s.clone(),
+					Field::List(l) => {
+						let items: Vec<String> = l.iter().map(|s| json_string(s)).collect();
+						format!("[{}]", items.join(", "))
+					},
+				};
+				let separator = if j + 1 < row.len() { "," } else { "" };
+				writeln!(out, "    {}: {value}{separator}", json_string(name))?;
+			}
+			let separator = if i + 1 < self.m_rows.len() { "," } else { "" };
+			writeln!(out, "  }}{separator}")?;
+		}
+		writeln!(out, "]")?;
+		Ok(())
+	}
+}
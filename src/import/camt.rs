@@ -0,0 +1,167 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::io::{Error, ErrorKind, Result};
+
+use crate::economy::io::BadLine;
+use crate::economy::money::Money;
+
+use crate::import::xml::XmlElement;
+use crate::import::{xml, ImportDefaults, ImportedActivities, Movement};
+
+use crate::time::date::Date;
+
+// Whether the entry is booked. Pending entries may still change.
+fn is_booked(entry: &XmlElement) -> bool {
+	match entry.get_path(&["Sts"]) {
+		// the status is either the text itself or, since version 8, a code
+		Some(status) => {
+			let code = status.get_text(&["Cd"]).unwrap_or(status.text.trim());
+			code == "BOOK"
+		},
+		None => true,
+	}
+}
+
+// Name of the other party: the creditor of money paid, the debtor of money
+// received.
+fn get_counterparty(entry: &XmlElement, debit: bool) -> String {
+	let party = if debit { "Cdtr" } else { "Dbtr" };
+	let mut details = Vec::new();
+	entry.find_all("RltdPties", &mut details);
+	details
+		.iter()
+		.find_map(|parties| {
+			parties
+				.get_text(&[party, "Nm"])
+				.or(parties.get_text(&[party, "Pty", "Nm"]))
+		})
+		.unwrap_or("")
+		.to_string()
+}
+
+// The unstructured remittance information, or else the additional
+// information of the entry.
+fn get_remittance_info(entry: &XmlElement) -> String {
+	let mut unstructured = Vec::new();
+	entry.find_all("Ustrd", &mut unstructured);
+	let lines: Vec<&str> = unstructured
+		.iter()
+		.map(|u| u.text.trim())
+		.filter(|t| !t.is_empty())
+		.collect();
+	if !lines.is_empty() {
+		return lines.join(" ");
+	}
+	entry.get_text(&["AddtlNtryInf"]).unwrap_or("").to_string()
+}
+
+fn parse_entry(entry: &XmlElement) -> std::result::Result<(Movement, Option<String>), String> {
+	let booking_date = entry
+		.get_text(&["BookgDt", "Dt"])
+		.or(entry.get_text(&["BookgDt", "DtTm"]))
+		.ok_or("the entry has no booking date")?;
+	let date = booking_date
+		.get(..10)
+		.and_then(|d| d.parse::<Date>().ok())
+		.ok_or(format!("'{booking_date}' is not a valid date"))?;
+
+	let amount_element = entry.get_path(&["Amt"]).ok_or("the entry has no amount")?;
+	let amount_text = amount_element.text.trim();
+	let amount = amount_text
+		.parse::<Money>()
+		.map_err(|_| format!("'{amount_text}' is not a valid amount"))?;
+	let currency = amount_element.get_attribute("Ccy").map(str::to_uppercase);
+
+	let debit = match entry.get_text(&["CdtDbtInd"]) {
+		Some("DBIT") => true,
+		Some("CRDT") => false,
+		Some(indicator) => return Err(format!("'{indicator}' is not a credit or debit indicator")),
+		None => return Err("the entry has no credit or debit indicator".to_string()),
+	};
+
+	let transaction_id = entry
+		.get_text(&["AcctSvcrRef"])
+		.or(entry.get_text(&["NtryRef"]))
+		.unwrap_or("")
+		.to_string();
+
+	let movement = Movement {
+		date,
+		amount: if debit { -amount.abs() } else { amount.abs() },
+		payee: get_counterparty(entry, debit),
+		description: get_remittance_info(entry),
+		transaction_id,
+		concepts: Vec::new(),
+	};
+	Ok((movement, currency))
+}
+
+// Reads the booked entries (Ntry) of the statements of a camt.053 file. The
+// entries that can't be read are added to 'bad_lines'. The currency of each
+// amount replaces the default currency.
+pub fn read_camt_file(
+	filename: &str,
+	defaults: &ImportDefaults,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<ImportedActivities> {
+	let contents = std::fs::read_to_string(filename)?;
+	let document = xml::parse(&contents).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+	let lines: Vec<&str> = contents.lines().collect();
+
+	let mut entries = Vec::new();
+	document.find_all("Ntry", &mut entries);
+
+	let mut imported = ImportedActivities::new();
+	for entry in entries.into_iter().filter(|e| is_booked(e)) {
+		match parse_entry(entry) {
+			Ok((movement, currency)) => {
+				let mut entry_defaults = defaults.clone();
+				if let Some(currency) = currency {
+					entry_defaults.currency = currency;
+				}
+				imported.add_movement(movement, &entry_defaults);
+			},
+			Err(reason) => bad_lines.push(BadLine {
+				file: filename.to_string(),
+				line_number: entry.line_number,
+				line: lines
+					.get(entry.line_number - 1)
+					.unwrap_or(&"")
+					.trim()
+					.to_string(),
+				reason,
+			}),
+		}
+	}
+	Ok(imported)
+}
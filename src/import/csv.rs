@@ -0,0 +1,267 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::fmt;
+use std::io::{BufRead, Error, ErrorKind, Result};
+
+use crate::economy::io::BadLine;
+use crate::economy::money::Money;
+
+use crate::import::{parse_amount, ImportDefaults, ImportedActivities, Movement};
+
+use crate::storage::backups;
+
+use crate::time::date::Date;
+
+pub const PROFILES_FILE: &str = "csv_profiles.json";
+
+/**
+ * @brief How the sign of the amounts of a statement is to be read.
+ */
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignConvention {
+	// Negative amounts are expenses, positive amounts are incomes.
+	#[default]
+	NegativeExpenses,
+	// Positive amounts are expenses, negative amounts are incomes.
+	PositiveExpenses,
+	// The amount column holds the expenses, this other column the incomes.
+	SeparateColumns {
+		income_column: usize,
+	},
+}
+
+impl fmt::Display for SignConvention {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			SignConvention::NegativeExpenses => write!(f, "negative amounts are expenses"),
+			SignConvention::PositiveExpenses => write!(f, "positive amounts are expenses"),
+			SignConvention::SeparateColumns { income_column } => {
+				write!(f, "incomes are in column {income_column}")
+			},
+		}
+	}
+}
+
+/**
+ * @brief Mapping of the columns of the CSV export of a bank.
+ *
+ * Columns are numbered starting at 1.
+ */
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CsvProfile {
+	pub name: String,
+	#[serde(default = "default_delimiter")]
+	pub delimiter: char,
+	// Number of lines at the top of the file to ignore, such as headers.
+	#[serde(default)]
+	pub skip_lines: usize,
+	pub date_column: usize,
+	// Format of the dates as in chrono, e.g. "%d.%m.%Y".
+	pub date_format: String,
+	pub amount_column: usize,
+	#[serde(default)]
+	pub sign: SignConvention,
+	#[serde(default = "default_decimal_separator")]
+	pub decimal_separator: char,
+	#[serde(default)]
+	pub payee_column: Option<usize>,
+	#[serde(default)]
+	pub description_column: Option<usize>,
+	// Currency of the account, the home currency if not given.
+	#[serde(default)]
+	pub currency: Option<String>,
+	#[serde(default)]
+	pub account: Option<String>,
+	#[serde(default)]
+	pub expense_concepts: Vec<String>,
+	#[serde(default)]
+	pub income_concepts: Vec<String>,
+}
+
+fn default_delimiter() -> char {
+	','
+}
+
+fn default_decimal_separator() -> char {
+	'.'
+}
+
+impl CsvProfile {
+	pub fn get_defaults(&self, home_currency: &str) -> ImportDefaults {
+		ImportDefaults {
+			currency: self.currency.clone().unwrap_or(home_currency.to_string()),
+			account: self.account.clone().unwrap_or_default(),
+			expense_concepts: self.expense_concepts.clone(),
+			income_concepts: self.income_concepts.clone(),
+		}
+	}
+}
+
+pub fn read_profiles(data_dir: &str) -> Result<Vec<CsvProfile>> {
+	let filename = data_dir.to_owned() + PROFILES_FILE;
+	if !std::path::Path::new(&filename).exists() {
+		return Ok(Vec::new());
+	}
+	let contents = std::fs::read_to_string(&filename)?;
+	serde_json::from_str(&contents).map_err(|e| Error::new(ErrorKind::InvalidData, e.to_string()))
+}
+
+pub fn write_profiles(data_dir: &str, profiles: &[CsvProfile]) -> Result<()> {
+	eprintln!("Writing into '{data_dir}{PROFILES_FILE}'...");
+	let contents = serde_json::to_vec_pretty(profiles).map_err(Error::other)?;
+	backups::write_file(data_dir, PROFILES_FILE, &contents)
+}
+
+// Splits a line into its fields. Fields may be enclosed in double quotes, and
+// double quotes within them are written twice.
+fn split_line(line: &str, delimiter: char) -> Vec<String> {
+	let mut fields = Vec::new();
+	let mut field = String::new();
+	let mut quoted = false;
+
+	let mut chars = line.chars().peekable();
+	while let Some(c) = chars.next() {
+		if quoted {
+			if c != '"' {
+				field.push(c);
+			} else if chars.peek() == Some(&'"') {
+				field.push('"');
+				chars.next();
+			} else {
+				quoted = false;
+			}
+		} else if c == '"' {
+			quoted = true;
+		} else if c == delimiter {
+			fields.push(std::mem::take(&mut field));
+		} else {
+			field.push(c);
+		}
+	}
+	fields.push(field);
+	fields
+}
+
+fn get_field(fields: &[String], column: usize) -> std::result::Result<&str, String> {
+	if column == 0 || column > fields.len() {
+		return Err(format!("there is no column {column}"));
+	}
+	Ok(fields[column - 1].trim())
+}
+
+fn get_amount(
+	fields: &[String],
+	column: usize,
+	decimal_separator: char,
+) -> std::result::Result<Money, String> {
+	let value = get_field(fields, column)?;
+	parse_amount(value, decimal_separator).ok_or(format!(
+		"'{value}' in column {column} is not a valid amount"
+	))
+}
+
+fn parse_row(fields: &[String], profile: &CsvProfile) -> std::result::Result<Movement, String> {
+	let date_value = get_field(fields, profile.date_column)?;
+	let date: Date = chrono::NaiveDate::parse_from_str(date_value, &profile.date_format)
+		.map_err(|_| {
+			format!(
+				"'{date_value}' in column {} is not a date of the form '{}'",
+				profile.date_column, profile.date_format
+			)
+		})?
+		.into();
+
+	let separator = profile.decimal_separator;
+	let amount = match &profile.sign {
+		SignConvention::NegativeExpenses => get_amount(fields, profile.amount_column, separator)?,
+		SignConvention::PositiveExpenses => -get_amount(fields, profile.amount_column, separator)?,
+		SignConvention::SeparateColumns { income_column } => {
+			if get_field(fields, profile.amount_column)?.is_empty() {
+				get_amount(fields, *income_column, separator)?.abs()
+			} else {
+				-get_amount(fields, profile.amount_column, separator)?.abs()
+			}
+		},
+	};
+
+	let payee = match profile.payee_column {
+		Some(column) => get_field(fields, column)?.to_string(),
+		None => "".to_string(),
+	};
+	let description = match profile.description_column {
+		Some(column) => get_field(fields, column)?.to_string(),
+		None => "".to_string(),
+	};
+
+	Ok(Movement {
+		date,
+		amount,
+		payee,
+		description,
+		transaction_id: "".to_string(),
+		concepts: Vec::new(),
+	})
+}
+
+// Reads the movements of a CSV file. The rows that can't be read are added
+// to 'bad_lines'.
+pub fn read_csv_file(
+	filename: &str,
+	profile: &CsvProfile,
+	defaults: &ImportDefaults,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<ImportedActivities> {
+	let file = std::fs::File::open(filename)?;
+	let reader = std::io::BufReader::new(file);
+
+	let mut imported = ImportedActivities::new();
+	for (i, line) in reader.lines().enumerate().skip(profile.skip_lines) {
+		let line = line?;
+		let line = line.trim_start_matches('\u{feff}').trim_end();
+		if line.is_empty() {
+			continue;
+		}
+
+		match parse_row(&split_line(line, profile.delimiter), profile) {
+			Ok(movement) => imported.add_movement(movement, defaults),
+			Err(reason) => bad_lines.push(BadLine {
+				file: filename.to_string(),
+				line_number: i + 1,
+				line: line.to_string(),
+				reason,
+			}),
+		}
+	}
+	Ok(imported)
+}
@@ -0,0 +1,155 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::economy::expense::Expense;
+use crate::economy::income::Income;
+use crate::economy::money::Money;
+
+use crate::time::date::Date;
+
+pub mod camt;
+pub mod csv;
+pub mod ofx;
+pub mod qif;
+pub mod xml;
+
+// Reads amounts like "-1'234.50" or "1.234,50", depending on the separator.
+pub fn parse_amount(s: &str, decimal_separator: char) -> Option<Money> {
+	let number: String = s
+		.chars()
+		.filter(|c| !c.is_whitespace() && *c != '\'')
+		.filter(|c| *c == decimal_separator || !matches!(c, '.' | ','))
+		.map(|c| if c == decimal_separator { '.' } else { c })
+		.collect();
+	number.parse::<Money>().ok()
+}
+
+// Tabs and line breaks separate the fields and the lines of the data files.
+fn to_single_field(s: &str) -> String {
+	s.replace(['\t', '\n', '\r'], " ")
+}
+
+/**
+ * @brief A movement of money read from a bank statement.
+ *
+ * Negative amounts are money paid, positive amounts are money received.
+ */
+#[derive(Debug, Clone)]
+pub struct Movement {
+	pub date: Date,
+	pub amount: Money,
+	pub payee: String,
+	pub description: String,
+	// Identifier given by the bank, empty if the statement has none.
+	pub transaction_id: String,
+	// Concepts stated by the statement, if any.
+	pub concepts: Vec<String>,
+}
+
+/**
+ * @brief Fields of the imported activities that bank statements do not state.
+ */
+#[derive(Debug, Clone)]
+pub struct ImportDefaults {
+	pub currency: String,
+	pub account: String,
+	pub expense_concepts: Vec<String>,
+	pub income_concepts: Vec<String>,
+}
+
+/**
+ * @brief Activities read from a bank statement, not yet part of the data.
+ */
+#[derive(Debug)]
+pub struct ImportedActivities {
+	pub expenses: Vec<Expense>,
+	pub incomes: Vec<Income>,
+}
+
+impl ImportedActivities {
+	pub fn new() -> ImportedActivities {
+		ImportedActivities {
+			expenses: Vec::new(),
+			incomes: Vec::new(),
+		}
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.expenses.is_empty() && self.incomes.is_empty()
+	}
+
+	// Money paid becomes an expense, money received becomes an income.
+	pub fn add_movement(&mut self, movement: Movement, defaults: &ImportDefaults) {
+		let concepts = |default_concepts: &Vec<String>| {
+			if movement.concepts.is_empty() {
+				default_concepts.clone()
+			} else {
+				movement
+					.concepts
+					.iter()
+					.map(|c| to_single_field(c))
+					.collect()
+			}
+		};
+		let payee = to_single_field(&movement.payee);
+		let description = to_single_field(&movement.description);
+		let transaction_id = to_single_field(&movement.transaction_id);
+
+		if movement.amount.is_negative() {
+			self.expenses.push(Expense {
+				day_of_year: movement.date,
+				price: movement.amount.abs().round_to_currency(&defaults.currency),
+				currency: defaults.currency.clone(),
+				account: defaults.account.clone(),
+				concepts: concepts(&defaults.expense_concepts),
+				shop: payee,
+				city: "".to_string(),
+				description,
+				transaction_id,
+				recurring: "".to_string(),
+			});
+		} else {
+			self.incomes.push(Income {
+				day_of_year: movement.date,
+				price: movement.amount.round_to_currency(&defaults.currency),
+				currency: defaults.currency.clone(),
+				account: defaults.account.clone(),
+				concepts: concepts(&defaults.income_concepts),
+				from: payee,
+				place: "".to_string(),
+				description,
+				transaction_id,
+				recurring: "".to_string(),
+			});
+		}
+	}
+}
@@ -0,0 +1,182 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::collections::BTreeMap;
+use std::io::Result;
+
+use crate::economy::io::BadLine;
+use crate::economy::money::Money;
+
+use crate::import::{ImportDefaults, ImportedActivities, Movement};
+
+use crate::time::date::Date;
+
+// A tag of the file and the text that follows it.
+struct Element {
+	name: String,
+	closing: bool,
+	value: String,
+	line_number: usize,
+}
+
+fn decode_entities(s: &str) -> String {
+	s.replace("&lt;", "<")
+		.replace("&gt;", ">")
+		.replace("&quot;", "\"")
+		.replace("&apos;", "'")
+		.replace("&amp;", "&")
+}
+
+// Splits the file into its tags. Both the SGML files of OFX 1, where simple
+// elements are not closed, and the XML files of OFX 2 are read this way.
+fn split_elements(contents: &str) -> Vec<Element> {
+	let mut elements = Vec::new();
+	let mut line_number = 1;
+	let mut rest = contents;
+
+	while let Some(start) = rest.find('<') {
+		line_number += rest[..start].matches('\n').count();
+		let Some(end) = rest[start..].find('>') else {
+			break;
+		};
+		let tag = &rest[start + 1..start + end];
+		rest = &rest[start + end + 1..];
+
+		let text_end = rest.find('<').unwrap_or(rest.len());
+		let value = decode_entities(rest[..text_end].trim());
+
+		// skip the XML declaration, processing instructions and comments
+		if tag.starts_with('?') || tag.starts_with('!') {
+			continue;
+		}
+		let (closing, name) = match tag.strip_prefix('/') {
+			Some(name) => (true, name),
+			None => (false, tag),
+		};
+		elements.push(Element {
+			name: name.trim().to_uppercase(),
+			closing,
+			value,
+			line_number,
+		});
+	}
+	elements
+}
+
+// Dates are of the form YYYYMMDD, possibly followed by the time.
+fn parse_date(value: &str) -> Option<Date> {
+	let day = value.get(..8)?;
+	chrono::NaiveDate::parse_from_str(day, "%Y%m%d")
+		.ok()
+		.map(Date::from)
+}
+
+fn parse_transaction(fields: &BTreeMap<String, String>) -> std::result::Result<Movement, String> {
+	let posted = fields
+		.get("DTPOSTED")
+		.ok_or("the transaction has no date")?;
+	let date = parse_date(posted).ok_or(format!("'{posted}' is not a valid date"))?;
+
+	let amount = fields
+		.get("TRNAMT")
+		.ok_or("the transaction has no amount")?;
+	let amount = amount
+		.replace(',', ".")
+		.parse::<Money>()
+		.map_err(|_| format!("'{amount}' is not a valid amount"))?;
+
+	let get = |name: &str| fields.get(name).cloned().unwrap_or_default();
+	let payee = if fields.contains_key("NAME") {
+		get("NAME")
+	} else {
+		get("PAYEEID")
+	};
+
+	Ok(Movement {
+		date,
+		amount,
+		payee,
+		description: get("MEMO"),
+		transaction_id: get("FITID"),
+		concepts: Vec::new(),
+	})
+}
+
+// Reads the transactions (STMTTRN) of an OFX or QFX file. The transactions
+// that can't be read are added to 'bad_lines'. The currency of the
+// statements (CURDEF) replaces the default currency.
+pub fn read_ofx_file(
+	filename: &str,
+	defaults: &ImportDefaults,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<ImportedActivities> {
+	let contents = std::fs::read_to_string(filename)?;
+	let lines: Vec<&str> = contents.lines().collect();
+
+	let mut imported = ImportedActivities::new();
+	let mut statement_defaults = defaults.clone();
+	let mut transaction: Option<(usize, BTreeMap<String, String>)> = None;
+
+	for element in split_elements(&contents) {
+		match (element.name.as_str(), element.closing) {
+			("CURDEF", false) => {
+				statement_defaults.currency = element.value.to_uppercase();
+			},
+			("STMTTRN", false) => {
+				transaction = Some((element.line_number, BTreeMap::new()));
+			},
+			("STMTTRN", true) => {
+				let Some((line_number, fields)) = transaction.take() else {
+					continue;
+				};
+				match parse_transaction(&fields) {
+					Ok(movement) => imported.add_movement(movement, &statement_defaults),
+					Err(reason) => bad_lines.push(BadLine {
+						file: filename.to_string(),
+						line_number,
+						line: lines.get(line_number - 1).unwrap_or(&"").to_string(),
+						reason,
+					}),
+				}
+			},
+			(name, false) => {
+				if let Some((_, fields)) = transaction.as_mut() {
+					if !element.value.is_empty() {
+						fields.entry(name.to_string()).or_insert(element.value);
+					}
+				}
+			},
+			_ => {},
+		}
+	}
+	Ok(imported)
+}
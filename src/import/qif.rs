@@ -0,0 +1,187 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::io::Result;
+
+use chrono::Datelike;
+
+use crate::economy::io::BadLine;
+
+use crate::import::{parse_amount, ImportDefaults, ImportedActivities, Movement};
+
+use crate::time::date::Date;
+
+pub const DEFAULT_DATE_FORMAT: &str = "%m/%d/%Y";
+pub const DEFAULT_DECIMAL_SEPARATOR: char = '.';
+
+// Sections of a QIF file whose transactions are read.
+fn is_read_section(header: &str) -> bool {
+	matches!(
+		header.trim().to_lowercase().as_str(),
+		"!type:bank" | "!type:cash"
+	)
+}
+
+// Reads dates written with 'date_format'. The apostrophe some programs write
+// before the year is read as a '/', and years may have two digits.
+fn parse_date(value: &str, date_format: &str) -> Option<Date> {
+	let value = value.trim().replace('\'', "/");
+	let date = chrono::NaiveDate::parse_from_str(&value, date_format).ok()?;
+	if date.year() >= 100 {
+		return Some(date.into());
+	}
+	chrono::NaiveDate::parse_from_str(&value, &date_format.replace("%Y", "%y"))
+		.ok()
+		.map(Date::from)
+}
+
+// Categories are written as 'Category:Subcategory'. Transfers to other
+// accounts are written as '[Account]' and have no category.
+fn parse_category(value: &str) -> Vec<String> {
+	if value.starts_with('[') {
+		return Vec::new();
+	}
+	value
+		.split(':')
+		.map(str::trim)
+		.filter(|s| !s.is_empty())
+		.map(|s| s.to_string())
+		.collect()
+}
+
+fn parse_record(
+	lines: &[&str],
+	date_format: &str,
+	decimal_separator: char,
+) -> std::result::Result<Movement, String> {
+	let mut date = None;
+	let mut amount = None;
+	let mut payee = String::new();
+	let mut description = String::new();
+	let mut concepts = Vec::new();
+
+	for line in lines {
+		let mut chars = line.chars();
+		let Some(code) = chars.next() else {
+			continue;
+		};
+		let value = chars.as_str().trim();
+		match code {
+			'D' => {
+				date = Some(parse_date(value, date_format).ok_or(format!(
+					"'{value}' is not a date of the form '{date_format}'"
+				))?);
+			},
+			'T' => {
+				amount = Some(parse_amount(value, decimal_separator).ok_or(format!(
+					"'{value}' is not an amount with '{decimal_separator}' as decimal separator"
+				))?);
+			},
+			'P' => payee = value.to_string(),
+			'M' => description = value.to_string(),
+			'L' => concepts = parse_category(value),
+			_ => {},
+		}
+	}
+
+	Ok(Movement {
+		date: date.ok_or("the transaction has no date")?,
+		amount: amount.ok_or("the transaction has no amount")?,
+		payee,
+		description,
+		transaction_id: "".to_string(),
+		concepts,
+	})
+}
+
+// Reads the transactions of the bank and cash sections of a QIF file. The
+// transactions that can't be read are added to 'bad_lines'.
+pub fn read_qif_file(
+	filename: &str,
+	date_format: &str,
+	decimal_separator: char,
+	defaults: &ImportDefaults,
+	bad_lines: &mut Vec<BadLine>,
+) -> Result<ImportedActivities> {
+	let contents = std::fs::read_to_string(filename)?;
+
+	let mut imported = ImportedActivities::new();
+	let mut in_read_section = false;
+	// lines of the current record, and the number of its first line
+	let mut record: Vec<&str> = Vec::new();
+	let mut record_start = 0;
+
+	for (i, line) in contents.lines().enumerate() {
+		let line = line.trim_start_matches('\u{feff}').trim_end();
+		if line.starts_with('!') {
+			in_read_section = is_read_section(line);
+			record.clear();
+			continue;
+		}
+		if !in_read_section || line.is_empty() {
+			continue;
+		}
+
+		if line != "^" {
+			if record.is_empty() {
+				record_start = i + 1;
+			}
+			record.push(line);
+			continue;
+		}
+
+		if record.is_empty() {
+			continue;
+		}
+		match parse_record(&record, date_format, decimal_separator) {
+			Ok(movement) => imported.add_movement(movement, defaults),
+			Err(reason) => bad_lines.push(BadLine {
+				file: filename.to_string(),
+				line_number: record_start,
+				line: record.first().unwrap_or(&"").to_string(),
+				reason,
+			}),
+		}
+		record.clear();
+	}
+
+	// the last transaction should also end with '^'
+	if !record.is_empty() {
+		bad_lines.push(BadLine {
+			file: filename.to_string(),
+			line_number: record_start,
+			line: record.first().unwrap_or(&"").to_string(),
+			reason: "the last transaction does not end with '^'".to_string(),
+		});
+	}
+	Ok(imported)
+}
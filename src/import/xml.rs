@@ -0,0 +1,208 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+/**
+ * @brief An element of an XML document.
+ *
+ * Names are kept without their namespace prefix.
+ */
+#[derive(Debug)]
+pub struct XmlElement {
+	pub name: String,
+	pub attributes: Vec<(String, String)>,
+	pub text: String,
+	pub children: Vec<XmlElement>,
+	pub line_number: usize,
+}
+
+impl XmlElement {
+	fn new(name: String, attributes: Vec<(String, String)>, line_number: usize) -> XmlElement {
+		XmlElement {
+			name,
+			attributes,
+			text: String::new(),
+			children: Vec::new(),
+			line_number,
+		}
+	}
+
+	pub fn get_attribute(&self, name: &str) -> Option<&str> {
+		self.attributes
+			.iter()
+			.find(|(n, _)| n == name)
+			.map(|(_, v)| v.as_str())
+	}
+
+	// The element found following the names of the children in 'path'.
+	pub fn get_path(&self, path: &[&str]) -> Option<&XmlElement> {
+		let Some((first, rest)) = path.split_first() else {
+			return Some(self);
+		};
+		self.children
+			.iter()
+			.find(|c| c.name == *first)?
+			.get_path(rest)
+	}
+
+	pub fn get_text(&self, path: &[&str]) -> Option<&str> {
+		self.get_path(path).map(|e| e.text.trim())
+	}
+
+	// All the elements called 'name' within this one, in document order.
+	pub fn find_all<'a>(&'a self, name: &str, found: &mut Vec<&'a XmlElement>) {
+		for child in self.children.iter() {
+			if child.name == name {
+				found.push(child);
+			}
+			child.find_all(name, found);
+		}
+	}
+}
+
+fn local_name(name: &str) -> String {
+	match name.split_once(':') {
+		Some((_, local)) => local.to_string(),
+		None => name.to_string(),
+	}
+}
+
+fn decode_entities(s: &str) -> String {
+	s.replace("&lt;", "<")
+		.replace("&gt;", ">")
+		.replace("&quot;", "\"")
+		.replace("&apos;", "'")
+		.replace("&amp;", "&")
+}
+
+// Splits the contents of a tag into the name and the attributes.
+fn parse_tag(tag: &str) -> (String, Vec<(String, String)>) {
+	let tag = tag.trim();
+	let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+	let name = local_name(&tag[..name_end]);
+
+	let mut attributes = Vec::new();
+	let mut rest = &tag[name_end..];
+	while let Some(eq) = rest.find('=') {
+		let key = local_name(rest[..eq].trim());
+		let value_part = rest[eq + 1..].trim_start();
+		let Some(quote) = value_part.chars().next() else {
+			break;
+		};
+		let Some(end) = value_part[1..].find(quote) else {
+			break;
+		};
+		attributes.push((key, decode_entities(&value_part[1..end + 1])));
+		rest = &value_part[end + 2..];
+	}
+	(name, attributes)
+}
+
+// Parses a whole document, returning its root element.
+pub fn parse(contents: &str) -> Result<XmlElement, String> {
+	// the bottom of the stack holds the elements at the top level
+	let mut stack = vec![XmlElement::new(String::new(), Vec::new(), 0)];
+	let mut line_number = 1;
+	let mut rest = contents;
+
+	while let Some(start) = rest.find('<') {
+		let text = &rest[..start];
+		line_number += text.matches('\n').count();
+		stack
+			.last_mut()
+			.expect("The stack is never empty")
+			.text
+			.push_str(&decode_entities(text));
+		rest = &rest[start..];
+
+		let (end_marker, skip) = if rest.starts_with("<!--") {
+			("-->", true)
+		} else if rest.starts_with("<![CDATA[") {
+			("]]>", false)
+		} else {
+			(">", rest.starts_with("<?") || rest.starts_with("<!"))
+		};
+		let Some(end) = rest.find(end_marker) else {
+			return Err(format!("unterminated tag at line {line_number}"));
+		};
+		let tag = &rest[1..end];
+		line_number += tag.matches('\n').count();
+		rest = &rest[end + end_marker.len()..];
+
+		if skip {
+			continue;
+		}
+		if let Some(data) = tag.strip_prefix("![CDATA[") {
+			stack
+				.last_mut()
+				.expect("The stack is never empty")
+				.text
+				.push_str(data);
+		} else if let Some(closing) = tag.strip_prefix('/') {
+			let name = local_name(closing.trim());
+			if stack.len() == 1 {
+				return Err(format!("unexpected '</{name}>' at line {line_number}"));
+			}
+			let element = stack.pop().expect("The stack is never empty");
+			if element.name != name {
+				return Err(format!(
+					"expected '</{}>', found '</{name}>' at line {line_number}",
+					element.name
+				));
+			}
+			stack
+				.last_mut()
+				.expect("The stack is never empty")
+				.children
+				.push(element);
+		} else if let Some(empty) = tag.strip_suffix('/') {
+			let (name, attributes) = parse_tag(empty);
+			stack
+				.last_mut()
+				.expect("The stack is never empty")
+				.children
+				.push(XmlElement::new(name, attributes, line_number));
+		} else {
+			let (name, attributes) = parse_tag(tag);
+			stack.push(XmlElement::new(name, attributes, line_number));
+		}
+	}
+
+	if stack.len() > 1 {
+		let name = &stack.last().expect("The stack is never empty").name;
+		return Err(format!("element '{name}' is not closed"));
+	}
+	let mut top = stack.pop().expect("The stack is never empty");
+	if top.children.len() != 1 {
+		return Err("the document does not have a single root element".to_string());
+	}
+	Ok(top.children.remove(0))
+}
@@ -33,6 +33,7 @@
 use std::str::FromStr;
 
 use crate::concepts::tree::Tree;
+use crate::economy::money::Money;
 
 pub fn read_input_string() -> String {
 	let mut s = String::new();
@@ -71,6 +72,7 @@ impl Numeric for i64 {}
 impl Numeric for usize {}
 impl Numeric for f32 {}
 impl Numeric for f64 {}
+impl Numeric for Money {}
 
 pub fn read_num_or_empty<T>() -> Option<T>
 where
@@ -122,6 +124,7 @@ where
 pub trait Decimal: Numeric {}
 impl Decimal for f32 {}
 impl Decimal for f64 {}
+impl Decimal for Money {}
 
 pub fn read_float_or_empty<T: FromStr>() -> Option<T>
 where
@@ -156,6 +159,17 @@ pub fn read_from_options_or_empty(options: &Vec<String>) -> Option<String> {
 	}
 }
 
+// Asks until the answer is either yes or no.
+pub fn read_yes_no() -> bool {
+	loop {
+		match read_string().to_lowercase().as_str() {
+			"y" | "yes" => break true,
+			"n" | "no" => break false,
+			_ => {},
+		}
+	}
+}
+
 pub fn read_from_tree_options(options: &Tree) -> Vec<String> {
 	let mut res: Vec<String> = Vec::new();
 
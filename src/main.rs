@@ -35,15 +35,20 @@ extern crate serde_json;
 
 use std::io::Read;
 
+mod commands;
 mod concepts;
 mod economy;
+mod export;
+mod import;
 mod menus;
+mod storage;
 mod time;
 
 mod io;
 mod utils;
 
 use crate::economy::all_activities::AllActivities;
+use crate::storage::Storage;
 
 fn print_main_menu() {
 	println!("What menu do you want to access?");
@@ -56,13 +61,24 @@ fn print_main_menu() {
 	println!("    6. Income statistics menu");
 	println!("    7. Save changes");
 	println!("    8. Force data overwrite");
+	println!("    9. Change reporting currency");
+	println!("   10. Accounts menu");
+	println!("   11. Transfers menu");
+	println!("   12. Backups menu");
+	println!("   13. Validate data");
+	println!("   14. Import menu");
+	println!("   15. Export menu");
+	println!("   16. Budgets menu");
+	println!("   17. Savings goals menu");
+	println!("   18. Recurring activities menu");
+	println!("   19. Cash flow menu");
 	println!("    0. Leave");
 }
 
-fn main_menu(all_data: &mut AllActivities, data_dir: &String) {
+fn main_menu(all_data: &mut AllActivities, project: &ProjectData, storage: &mut dyn Storage) {
 	let print_function = print_main_menu;
 	let min_option = 0;
-	let max_option = 8;
+	let max_option = 19;
 
 	let mut option = menus::utils::read_option(print_function, min_option, max_option);
 	while option != 0 {
@@ -74,20 +90,32 @@ fn main_menu(all_data: &mut AllActivities, data_dir: &String) {
 			5 => menus::statistics::menu_expenses(all_data),
 			6 => menus::statistics::menu_incomes(all_data),
 			7 => {
-				economy::io::write_all_data(&data_dir, all_data).expect("Could not write data");
-				concepts::io::write_all_data(&data_dir, all_data).expect("Could not write data");
-
+				storage.save_all(all_data).expect("Could not write data");
 				all_data.set_changes(false);
 			},
 			8 => {
 				// set changes to true to force overwrite
 				all_data.set_changes(true);
 
-				economy::io::write_all_data(&data_dir, all_data).expect("Could not write data");
-				concepts::io::write_all_data(&data_dir, all_data).expect("Could not write data");
-
+				storage.save_all(all_data).expect("Could not write data");
 				all_data.set_changes(false);
 			},
+			9 => menus::currencies::change_reporting_currency(all_data),
+			10 => menus::accounts::menu_accounts(all_data),
+			11 => menus::transfers::menu_transfers(all_data),
+			12 => {
+				if menus::backups::menu_backups(&project.base_path, all_data.has_changes()) {
+					// the data on disk is no longer the data in memory
+					*all_data = read_data(project, storage);
+				}
+			},
+			13 => menus::validation::menu_validation(all_data),
+			14 => menus::import::menu_import(all_data, &project.base_path),
+			15 => menus::export::menu_export(all_data, &project.base_path),
+			16 => menus::budgets::menu_budgets(all_data),
+			17 => menus::savings_goals::menu_savings_goals(all_data),
+			18 => menus::recurring::menu_recurring(all_data),
+			19 => menus::cash_flow::menu_cash_flow(all_data),
 			_ => println!("Nothing to do..."),
 		}
 
@@ -100,36 +128,151 @@ fn main_menu(all_data: &mut AllActivities, data_dir: &String) {
 #[derive(serde::Serialize, serde::Deserialize)]
 struct ProjectData {
 	pub base_path: String,
+	#[serde(default = "default_currency")]
+	pub home_currency: String,
+	#[serde(default)]
+	pub reporting_currency: Option<String>,
+	#[serde(default)]
+	pub storage: Option<String>,
+	#[serde(default)]
+	pub database: Option<String>,
+	#[serde(default)]
+	pub lenient_loading: bool,
+	#[serde(default = "default_reminder_days")]
+	pub reminder_days: u32,
 }
 
-fn main() {
-	println!("Welcome to the expenses manager!");
-	println!("");
+impl ProjectData {
+	// Name of the database file when the SQLite backend is selected.
+	fn get_database_name(&self) -> Option<String> {
+		if self.storage.as_deref() != Some("sqlite") {
+			return None;
+		}
+		Some(self.database.clone().unwrap_or("finances.db".to_string()))
+	}
+}
+
+fn default_currency() -> String {
+	"EUR".to_string()
+}
+
+fn default_reminder_days() -> u32 {
+	7
+}
+
+fn make_text_storage(project: &ProjectData) -> storage::text::TextStorage {
+	let mut text_storage = storage::text::TextStorage::new(project.base_path.clone());
+	text_storage.set_lenient(project.lenient_loading);
+	text_storage
+}
+
+fn make_storage(project: &ProjectData) -> Box<dyn Storage> {
+	if let Some(db_name) = project.get_database_name() {
+		let data_dir = project.base_path.clone();
+		return Box::new(storage::sqlite::SqliteStorage::new(data_dir, db_name));
+	}
+	match project.storage.as_deref() {
+		Some("memory") => Box::new(storage::memory::MemoryStorage::new()),
+		Some("text") | None => Box::new(make_text_storage(project)),
+		Some(s) => panic!("Unknown storage backend '{s}'"),
+	}
+}
+
+fn read_data(project: &ProjectData, storage: &mut dyn Storage) -> AllActivities {
+	let home_currency = &project.home_currency;
 
+	// the first time the database is used, copy the data from the text files
+	let text_storage = make_text_storage(project);
+	let needs_migration = match project.get_database_name() {
+		Some(db_name) => {
+			let db_file = project.base_path.clone() + &db_name;
+			!std::path::Path::new(&db_file).exists() && text_storage.has_data()
+		},
+		None => false,
+	};
+
+	let loaded = if needs_migration {
+		storage::migrate(&text_storage, storage, home_currency)
+	} else {
+		storage.load_all(home_currency)
+	};
+	let mut all_data = loaded.unwrap_or_else(|e| {
+		eprintln!("Could not read data: {e}");
+		std::process::exit(1);
+	});
+
+	let reporting_currency = project
+		.reporting_currency
+		.clone()
+		.unwrap_or(home_currency.clone());
+	all_data
+		.get_exchange_rates_mut()
+		.set_reporting_currency(reporting_currency);
+
+	let today = time::date::Date::today();
+	let num_added = economy::recurring::generate_occurrences(&mut all_data, &today);
+	if num_added > 0 {
+		eprintln!("Added {num_added} occurrences of recurring activities up to today.");
+	}
+
+	all_data
+}
+
+// Runs a single command and returns the exit status of the program.
+fn run_command(args: &[String], project: &ProjectData, storage: &mut dyn Storage) -> i32 {
+	let arguments = match commands::arguments::Arguments::parse(args) {
+		Ok(arguments) => arguments,
+		Err(e) => {
+			eprintln!("{e}");
+			return e.exit_code();
+		},
+	};
+	if arguments.get_command() == "help" {
+		commands::print_usage();
+		return commands::EXIT_SUCCESS;
+	}
+	if let Err(e) = commands::check_command(&arguments) {
+		eprintln!("{e}");
+		return e.exit_code();
+	}
+
+	let mut all_data = read_data(project, storage);
+	if let Err(e) = commands::run(&arguments, &mut all_data) {
+		eprintln!("{e}");
+		return e.exit_code();
+	}
+
+	if let Err(e) = storage.save_all(&all_data) {
+		eprintln!("Could not write data: {e}");
+		return commands::EXIT_IO_ERROR;
+	}
+	commands::EXIT_SUCCESS
+}
+
+fn main() {
 	let mut file = std::fs::File::open("project_configuration.json").unwrap();
 	let mut data = String::new();
 	file.read_to_string(&mut data).unwrap();
 
-	let json: ProjectData = serde_json::from_str(&data).unwrap();
-	let data_dir = json.base_path;
+	let project: ProjectData = serde_json::from_str(&data).unwrap();
+	let mut storage = make_storage(&project);
 
-	println!("Reading data from directory '{data_dir}'...");
-	println!("    Reading activities data...");
-	let mut all_data = economy::io::read_all_activities_data(&data_dir);
+	let args: Vec<String> = std::env::args().skip(1).collect();
+	if !args.is_empty() {
+		std::process::exit(run_command(&args, &project, storage.as_mut()));
+	}
 
-	println!("    Reading expense types...");
-	concepts::io::read_expense_types(&data_dir, &mut all_data);
-	println!("    Reading income types...");
-	concepts::io::read_income_types(&data_dir, &mut all_data);
+	println!("Welcome to the expenses manager!");
+	println!("");
 
-	all_data.set_changes(false);
+	let mut all_data = read_data(&project, storage.as_mut());
 
 	println!("");
+	menus::reminders::display_reminders(&all_data, project.reminder_days);
 	println!("");
 	println!("");
 	println!("");
-	main_menu(&mut all_data, &data_dir);
+	main_menu(&mut all_data, &project, storage.as_mut());
 
-	economy::io::write_all_data(&data_dir, &all_data).expect("Could not write data");
-	concepts::io::write_all_data(&data_dir, &all_data).expect("Could not write data");
+	storage.save_all(&all_data).expect("Could not write data");
 }
@@ -0,0 +1,279 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+
+use crate::economy;
+use crate::menus;
+use crate::time;
+
+type Account = economy::accounts::Account;
+type Accounts = economy::accounts::Accounts;
+type AllActivities = economy::all_activities::AllActivities;
+type Money = economy::money::Money;
+type Month = time::date::Month;
+
+static MONTH_WIDTH: usize = 14;
+static MONEY_WIDTH: usize = 12;
+
+pub fn read_account(accounts: &Accounts) -> String {
+	if accounts.size() == 0 {
+		return "".to_string();
+	}
+	println!("Account (leave blank for none, '?' to list them):");
+	io::read_from_options_or_empty(&accounts.get_names()).unwrap_or("".to_string())
+}
+
+fn print_accounts_all(all_data: &AllActivities) {
+	for Account {
+		name,
+		opening_balance,
+		currency,
+	} in all_data.get_accounts().iter()
+	{
+		println!(
+			"    {name}: opening balance {} {currency}",
+			opening_balance.display_in(currency)
+		);
+	}
+	println!();
+}
+
+fn add_account(all_data: &mut AllActivities) {
+	println!("Name of the new account:");
+	let name = io::read_string();
+	if all_data.get_accounts().has_account(&name) {
+		println!("Account '{name}' already exists.");
+		return;
+	}
+
+	println!("Opening balance:");
+	let opening_balance: Money = io::read_float();
+
+	let currency = menus::currencies::read_currency(all_data.get_exchange_rates());
+
+	all_data.get_accounts_mut().add(Account {
+		name,
+		opening_balance: opening_balance.round_to_currency(&currency),
+		currency,
+	});
+}
+
+fn remove_account(all_data: &mut AllActivities) {
+	println!("Account to be removed:");
+	let account = read_account(all_data.get_accounts());
+	if account.is_empty() {
+		return;
+	}
+	all_data.get_accounts_mut().remove(&account);
+}
+
+#[duplicate::duplicate_item(
+	method                   get;
+	[account_month_expenses] [get_month_expenses];
+	[account_month_incomes]  [get_month_incomes];
+)]
+fn method(
+	all_data: &AllActivities,
+	year: &u32,
+	month: &Month,
+	account: &Account,
+	num_unconverted: &mut u32,
+) -> Option<Money> {
+	let month_data = all_data.get(year, month)?;
+	let rates = all_data.get_exchange_rates();
+
+	let mut moved = false;
+	let mut total = Money::new();
+	for activity in month_data.iter().filter(|a| a.account == account.name) {
+		moved = true;
+		match rates.convert_to(
+			activity.price,
+			&activity.currency,
+			&account.currency,
+			&activity.day_of_year,
+		) {
+			Some(value) => total += value,
+			None => *num_unconverted += 1,
+		}
+	}
+
+	if moved {
+		Some(total)
+	} else {
+		None
+	}
+}
+
+// Net amount transferred into the account in a month.
+fn account_month_transfers(
+	all_data: &AllActivities,
+	year: &u32,
+	month: &Month,
+	account: &Account,
+	num_unconverted: &mut u32,
+) -> Option<Money> {
+	let month_data = all_data.get_month_transfers(year, month)?;
+	let rates = all_data.get_exchange_rates();
+
+	let mut moved = false;
+	let mut total = Money::new();
+	for transfer in month_data.iter() {
+		let incoming = transfer.to_account == account.name;
+		let outgoing = transfer.from_account == account.name;
+		if !incoming && !outgoing {
+			continue;
+		}
+		moved = true;
+		match rates.convert_to(
+			transfer.price,
+			&transfer.currency,
+			&account.currency,
+			&transfer.day_of_year,
+		) {
+			Some(value) if incoming => total += value,
+			Some(value) => total -= value,
+			None => *num_unconverted += 1,
+		}
+	}
+
+	if moved {
+		Some(total)
+	} else {
+		None
+	}
+}
+
+fn display_running_balance(all_data: &AllActivities, account: &Account) {
+	let month_divider = "—".repeat(MONTH_WIDTH);
+	let money_divider = "—".repeat(MONEY_WIDTH);
+	let currency = &account.currency;
+
+	println!("Account: {} ({currency})", account.name);
+	println!("    +—{month_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+");
+	println!(
+		"    | {:^MONTH_WIDTH$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} |",
+		"Month", "Incomes", "Expenses", "Transfers", "Balance"
+	);
+	println!("    +—{month_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+");
+
+	let mut balance = account.opening_balance;
+	println!(
+		"    | {:<MONTH_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} |",
+		"Opening",
+		"",
+		"",
+		"",
+		balance.display_in(currency)
+	);
+
+	let mut num_unconverted: u32 = 0;
+	for year_data in all_data.iter_activities() {
+		let year = year_data.get_year();
+		for m in 0..12 {
+			let month = Month::from_u32(m).unwrap();
+
+			let incomes =
+				account_month_incomes(all_data, year, &month, account, &mut num_unconverted);
+			let expenses =
+				account_month_expenses(all_data, year, &month, account, &mut num_unconverted);
+			let transfers =
+				account_month_transfers(all_data, year, &month, account, &mut num_unconverted);
+			if incomes.is_none() && expenses.is_none() && transfers.is_none() {
+				continue;
+			}
+
+			let incomes = incomes.unwrap_or_default();
+			let expenses = expenses.unwrap_or_default();
+			let transfers = transfers.unwrap_or_default();
+			balance += incomes - expenses + transfers;
+
+			let month_text = format!("{year}/{month}");
+			println!(
+				"    | {month_text:<MONTH_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} |",
+				incomes.display_in(currency),
+				expenses.display_in(currency),
+				transfers.display_in(currency),
+				balance.display_in(currency)
+			);
+		}
+	}
+	println!("    +—{month_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+");
+	println!();
+
+	menus::utils::display_unconverted(num_unconverted, currency);
+}
+
+fn print_running_balance_account(all_data: &AllActivities) {
+	println!("Select the account:");
+	let name = read_account(all_data.get_accounts());
+	if let Some(account) = all_data.get_accounts().get_account(&name) {
+		display_running_balance(all_data, account);
+	}
+}
+
+fn print_running_balance_all(all_data: &AllActivities) {
+	for account in all_data.get_accounts().iter() {
+		display_running_balance(all_data, account);
+	}
+}
+
+fn print_accounts_menu() {
+	println!("Query and edit the accounts:");
+	println!();
+	println!("    1. Show all accounts");
+	println!("    2. Add a new account");
+	println!("    3. Remove an account");
+	println!("    4. Show the running balance of an account");
+	println!("    5. Show the running balance of all accounts");
+	println!("    0. Leave");
+}
+
+pub fn menu_accounts(all_data: &mut AllActivities) {
+	let print_function = print_accounts_menu;
+	let min_option = 0;
+	let max_option = 5;
+
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => print_accounts_all(all_data),
+			2 => add_account(all_data),
+			3 => remove_account(all_data),
+			4 => print_running_balance_account(all_data),
+			5 => print_running_balance_all(all_data),
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+}
@@ -42,11 +42,13 @@ use crate::time;
 
 type Expense = economy::expense::Expense;
 type Income = economy::income::Income;
+type Money = economy::money::Money;
 type MonthlyActivities<T> = economy::monthly_activities::MonthlyActivities<T>;
 type YearlyActivities = economy::yearly_activities::YearlyActivities;
 type AllActivities = economy::all_activities::AllActivities;
 
 type ActivitySummary = menus::activity_summary::ActivitySummary;
+type ExchangeRates = economy::exchange_rates::ExchangeRates;
 
 #[duplicate::duplicate_item(
 	method                   display                           iter            activity;
@@ -57,6 +59,9 @@ fn method<F>(all_data: &AllActivities, func: &F)
 where
 	F: Fn(&activity) -> bool,
 {
+	let rates = all_data.get_exchange_rates();
+	let currency = rates.get_reporting_currency();
+
 	let mut all_years = ActivitySummary::new();
 	for year_data in all_data.iter_activities() {
 		println!("Data from year: {}", year_data.get_year());
@@ -65,20 +70,20 @@ where
 
 		let mut current_year = ActivitySummary::new();
 		for month_data in year_data.iter() {
-			let current_month = menus::utils::display(month_data, func, -1);
+			let current_month = menus::utils::display(month_data, func, -1, rates);
 			current_year.merge(current_month);
 		}
 
 		if current_year.has_data() {
 			println!("This year's summary:");
-			menus::utils::display_summary_activity(&current_year, &"");
+			menus::utils::display_summary_activity(&current_year, &"", currency);
 			all_years.merge(current_year);
 		}
 	}
 
 	if all_years.has_data() {
 		println!("Total history:");
-		menus::utils::display_summary_activity(&all_years, &"");
+		menus::utils::display_summary_activity(&all_years, &"", currency);
 	}
 }
 
@@ -104,8 +109,8 @@ fn method(all_data: &AllActivities) {
 	[print_by_price_range_incomes]  [print_incomes_by_func]  [Income];
 )]
 fn method(all_data: &AllActivities) {
-	let lower: f32 = io::read_float();
-	let upper: f32 = io::read_float();
+	let lower: Money = io::read_float();
+	let upper: Money = io::read_float();
 
 	let func = |activity: &Activity| lower <= activity.price && activity.price <= upper;
 	display(all_data, &func);
@@ -152,8 +157,8 @@ fn print_by_place_substring_incomes(
 	[print_data_month_expenses] [display_and_accounting_expenses] [Expense];
 	[print_data_month_incomes]  [display_and_accounting_incomes]  [Income];
 )]
-fn method(month_data: &MonthlyActivities<activity>) -> ActivitySummary {
-	menus::utils::display(month_data, &|_| true, -1)
+pub fn method(month_data: &MonthlyActivities<activity>, rates: &ExchangeRates) -> ActivitySummary {
+	menus::utils::display(month_data, &|_| true, -1, rates)
 }
 
 #[duplicate::duplicate_item(
@@ -161,7 +166,7 @@ fn method(month_data: &MonthlyActivities<activity>) -> ActivitySummary {
 	[print_data_year_expenses] [iter_expenses] [print_data_month_expenses];
 	[print_data_year_incomes]  [iter_incomes]  [print_data_month_incomes];
 )]
-fn method(year_data: &YearlyActivities) -> ActivitySummary {
+pub fn method(year_data: &YearlyActivities, rates: &ExchangeRates) -> ActivitySummary {
 	println!("Data from year: {}", year_data.get_year());
 	println!("--------------------");
 
@@ -175,13 +180,13 @@ fn method(year_data: &YearlyActivities) -> ActivitySummary {
 	println!("    Found {} entries", total_entries);
 	println!("");
 	for month_data in year_data.iterate() {
-		let current_month = print(month_data);
+		let current_month = print(month_data, rates);
 		current_year.merge(current_month);
 	}
 
 	if current_year.has_data() {
 		println!("This year's summary:");
-		menus::utils::display_summary_activity(&current_year, &"");
+		menus::utils::display_summary_activity(&current_year, &"", rates.get_reporting_currency());
 	}
 
 	current_year
@@ -192,40 +197,45 @@ fn method(year_data: &YearlyActivities) -> ActivitySummary {
 	[print_all_expenses] [print_data_year_expenses];
 	[print_all_incomes]  [print_data_year_incomes];
 )]
-fn method(all_data: &AllActivities) {
+pub fn method(all_data: &AllActivities) {
+	let rates = all_data.get_exchange_rates();
 	let mut all_years = ActivitySummary::new();
 
 	for year_expense in all_data.iter_activities() {
-		let current_year = print(&year_expense);
+		let current_year = print(&year_expense, rates);
 		all_years.merge(current_year);
 	}
 
 	println!("Total history:");
 	println!("==============");
-	menus::utils::display_summary_activity(&all_years, &"");
+	menus::utils::display_summary_activity(&all_years, &"", rates.get_reporting_currency());
 }
 
+// Incomes have no budgets.
+fn no_budgets(_: &AllActivities, _: u32, _: Option<&time::date::Month>) {}
+
 #[duplicate::duplicate_item(
-	method                     print;
-	[print_year_user_expenses] [print_data_year_expenses];
-	[print_year_user_incomes]  [print_data_year_incomes];
+	method                     print                      budgets;
+	[print_year_user_expenses] [print_data_year_expenses] [menus::budgets::display_budgets];
+	[print_year_user_incomes]  [print_data_year_incomes]  [no_budgets];
 )]
 fn method(all_data: &AllActivities) {
 	println!("What year do you want to see?");
 	let year: u32 = io::read_int();
 
 	let res = all_data.get_year(&year);
-	if let Some(year) = res {
-		print(year);
+	if let Some(year_data) = res {
+		print(year_data, all_data.get_exchange_rates());
+		budgets(all_data, year, None);
 	} else {
 		println!("Year '{year}' does not exist!");
 	};
 }
 
 #[duplicate::duplicate_item(
-	method                             print;
-	[print_year_current_expenses] [print_data_year_expenses];
-	[print_year_current_incomes]  [print_data_year_incomes];
+	method                        print                      budgets;
+	[print_year_current_expenses] [print_data_year_expenses] [menus::budgets::display_budgets];
+	[print_year_current_incomes]  [print_data_year_incomes]  [no_budgets];
 )]
 fn method(all_data: &AllActivities) {
 	let now = chrono::prelude::Utc::now();
@@ -234,17 +244,18 @@ fn method(all_data: &AllActivities) {
 	let year = local_date.year() as u32;
 
 	let res = all_data.get_year(&year);
-	if let Some(year) = res {
-		print(year);
+	if let Some(year_data) = res {
+		print(year_data, all_data.get_exchange_rates());
+		budgets(all_data, year, None);
 	} else {
 		println!("Year '{year}' does not exist!");
 	};
 }
 
 #[duplicate::duplicate_item(
-	method                      get                  print;
-	[print_month_user_expenses] [get_month_expenses] [print_data_month_expenses];
-	[print_month_user_incomes]  [get_month_incomes]  [print_data_month_incomes];
+	method                      get                  print                       budgets;
+	[print_month_user_expenses] [get_month_expenses] [print_data_month_expenses] [menus::budgets::display_budgets];
+	[print_month_user_incomes]  [get_month_incomes]  [print_data_month_incomes]  [no_budgets];
 )]
 fn method(all_data: &AllActivities) {
 	println!("What year and month do you want to see? Year -> Month");
@@ -262,16 +273,17 @@ fn method(all_data: &AllActivities) {
 
 	let res = all_data.get(&year, &month);
 	if let Some(&ref month_data) = res {
-		print(&month_data);
+		print(&month_data, all_data.get_exchange_rates());
+		budgets(all_data, year, Some(&month));
 	} else {
 		println!("Month '{month}' does not exist in year '{year}'.");
 	}
 }
 
 #[duplicate::duplicate_item(
-	method                              get                  print;
-	[print_year_current_month_expenses] [get_month_expenses] [print_data_month_expenses];
-	[print_year_current_month_incomes]  [get_month_incomes]  [print_data_month_incomes];
+	method                              get                  print                       budgets;
+	[print_year_current_month_expenses] [get_month_expenses] [print_data_month_expenses] [menus::budgets::display_budgets];
+	[print_year_current_month_incomes]  [get_month_incomes]  [print_data_month_incomes]  [no_budgets];
 )]
 fn method(all_data: &AllActivities) {
 	let now = chrono::prelude::Utc::now();
@@ -287,16 +299,17 @@ fn method(all_data: &AllActivities) {
 
 	let res = all_data.get(&year, &month);
 	if let Some(&ref month_data) = res {
-		print(&month_data);
+		print(&month_data, all_data.get_exchange_rates());
+		budgets(all_data, year, Some(&month));
 	} else {
 		println!("Month '{month}' does not exist in year '{year}'.");
 	}
 }
 
 #[duplicate::duplicate_item(
-	method                         get                  print;
-	[print_month_current_expenses] [get_month_expenses] [print_data_month_expenses];
-	[print_month_current_incomes]  [get_month_incomes]  [print_data_month_incomes];
+	method                         get                  print                       budgets;
+	[print_month_current_expenses] [get_month_expenses] [print_data_month_expenses] [menus::budgets::display_budgets];
+	[print_month_current_incomes]  [get_month_incomes]  [print_data_month_incomes]  [no_budgets];
 )]
 fn method(all_data: &AllActivities) {
 	let now = chrono::prelude::Utc::now();
@@ -315,7 +328,8 @@ fn method(all_data: &AllActivities) {
 
 	let res = all_data.get(&year, &month);
 	if let Some(&ref month_data) = res {
-		print(&month_data);
+		print(&month_data, all_data.get_exchange_rates());
+		budgets(all_data, year, Some(&month));
 	} else {
 		println!("Month '{month}' does not exist in year '{year}'.");
 	}
@@ -327,17 +341,23 @@ fn add_new_with_date_expense(
 	month: time::date::Month,
 	day: u8,
 ) {
+	let Some(date) = time::date::Date::new(year, month.clone(), day) else {
+		println!("Day '{day}' does not exist in {month} {year}.");
+		return;
+	};
+
 	println!("Expense concepts:");
 	let expense_type = io::read_from_tree_options(all_data.get_expense_concepts().get_tree());
 	if expense_type.len() == 0 {
 		return;
 	}
 
-	let year_data = all_data.add_year(year);
-	let month_data = year_data.get_expenses_mut().add(&month);
-
 	println!("Price:");
-	let price: f32 = io::read_float();
+	let price: Money = io::read_float();
+
+	let currency = menus::currencies::read_currency(all_data.get_exchange_rates());
+
+	let account = menus::accounts::read_account(all_data.get_accounts());
 
 	println!("Shop:");
 	let shop = io::read_string();
@@ -348,14 +368,21 @@ fn add_new_with_date_expense(
 	println!("Description:");
 	let description = io::read_string_or_empty().unwrap_or("".to_string());
 
-	month_data.push(Expense {
-		day_of_year: time::date::Date { year, month, day },
-		price: price,
-		concepts: expense_type,
-		shop,
-		city,
-		description: description,
-	});
+	menus::duplicates::add_expense(
+		all_data,
+		Expense {
+			day_of_year: date,
+			price: price.round_to_currency(&currency),
+			currency,
+			account,
+			concepts: expense_type,
+			shop,
+			city,
+			description: description,
+			transaction_id: "".to_string(),
+			recurring: "".to_string(),
+		},
+	);
 }
 
 fn add_new_with_date_income(
@@ -364,17 +391,23 @@ fn add_new_with_date_income(
 	month: time::date::Month,
 	day: u8,
 ) {
+	let Some(date) = time::date::Date::new(year, month.clone(), day) else {
+		println!("Day '{day}' does not exist in {month} {year}.");
+		return;
+	};
+
 	println!("Income concepts:");
 	let income_concepts = io::read_from_tree_options(all_data.get_income_concepts().get_tree());
 	if income_concepts.len() == 0 {
 		return;
 	}
 
-	let year_data = all_data.add_year(year);
-	let month_data = year_data.get_incomes_mut().add(&month);
-
 	println!("Price:");
-	let price: f32 = io::read_float();
+	let price: Money = io::read_float();
+
+	let currency = menus::currencies::read_currency(all_data.get_exchange_rates());
+
+	let account = menus::accounts::read_account(all_data.get_accounts());
 
 	println!("From:");
 	let from = io::read_string();
@@ -385,14 +418,21 @@ fn add_new_with_date_income(
 	println!("Description:");
 	let description = io::read_string_or_empty().unwrap_or("".to_string());
 
-	month_data.push(Income {
-		day_of_year: time::date::Date { year, month, day },
-		price: price,
-		concepts: income_concepts,
-		from: from,
-		place: place,
-		description: description,
-	});
+	menus::duplicates::add_income(
+		all_data,
+		Income {
+			day_of_year: date,
+			price: price.round_to_currency(&currency),
+			currency,
+			account,
+			concepts: income_concepts,
+			from: from,
+			place: place,
+			description: description,
+			transaction_id: "".to_string(),
+			recurring: "".to_string(),
+		},
+	);
 }
 
 #[duplicate::duplicate_item(
@@ -423,22 +463,10 @@ fn method(all_data: &mut AllActivities) {
 	[add_new_today_income]  [add_new_with_date_income];
 )]
 fn method(all_data: &mut AllActivities) {
-	let now = chrono::prelude::Utc::now();
-	let local_date = now.with_timezone(&chrono::prelude::Local);
+	let today = time::date::Date::today();
+	println!("Today is {}, {today}.", today.weekday());
 
-	let year = local_date.year() as u32;
-
-	let month_conv = time::date::Month::from_u32(local_date.month() - 1);
-	if month_conv.is_none() {
-		println!(
-			"Retrieval of current date failed for month '{}'.",
-			local_date.month()
-		);
-	}
-	let month = month_conv.expect("This should have worked!");
-	let day = local_date.day() as u8;
-
-	add(all_data, year, month, day);
+	add(all_data, today.year, today.month, today.day);
 }
 
 #[duplicate::duplicate_item(
@@ -521,126 +549,6 @@ fn method(all_data: &mut AllActivities) {
 	}
 }
 
-fn add_monthly_expense(all_data: &mut AllActivities) {
-	println!("Enter expense information first");
-
-	println!("Expense concepts:");
-	let concepts = io::read_from_tree_options(all_data.get_expense_concepts().get_tree());
-	if concepts.len() == 0 {
-		return;
-	}
-
-	println!("Price:");
-	let price: f32 = io::read_float();
-
-	println!("Shop:");
-	let shop = io::read_string();
-
-	println!("City:");
-	let city = io::read_string();
-
-	println!("Description:");
-	let description = io::read_string_or_empty().unwrap_or("".to_string());
-
-	println!("Start year:");
-	let year_start: u32 = io::read_int();
-
-	println!("Start month:");
-	let month_start = time::io::read_correct_month().unwrap();
-
-	println!("Finish year:");
-	let year_end: u32 = io::read_int();
-
-	println!("Finish month:");
-	let month_end = time::io::read_correct_month().unwrap();
-
-	println!("Day:");
-	let day: u8 = io::read_int();
-
-	let start = time::date::YearMonth {
-		year: year_start,
-		month: month_start,
-	};
-	let end = time::date::YearMonth {
-		year: year_end,
-		month: month_end,
-	};
-
-	for time::date::YearMonth { year, month } in time::date::month_range(start, end) {
-		let year_data = all_data.add_year(year);
-		let month_data = year_data.get_expenses_mut().add(&month);
-
-		month_data.push(Expense {
-			day_of_year: time::date::Date { year, month, day },
-			price: price,
-			concepts: concepts.clone(),
-			shop: shop.clone(),
-			city: city.clone(),
-			description: description.clone(),
-		});
-	}
-}
-
-fn add_monthly_income(all_data: &mut AllActivities) {
-	println!("Enter income information first");
-
-	println!("Income Type:");
-	let concepts = io::read_from_tree_options(all_data.get_income_concepts().get_tree());
-	if concepts.len() == 0 {
-		return;
-	}
-
-	println!("Price:");
-	let price: f32 = io::read_float();
-
-	println!("From:");
-	let from = io::read_string();
-
-	println!("Place:");
-	let place = io::read_string();
-
-	println!("Description:");
-	let description = io::read_string_or_empty().unwrap_or("".to_string());
-
-	println!("Start year:");
-	let year_start: u32 = io::read_int();
-
-	println!("Start month:");
-	let month_start = time::io::read_correct_month().unwrap();
-
-	println!("Finish year:");
-	let year_end: u32 = io::read_int();
-
-	println!("Finish month:");
-	let month_end = time::io::read_correct_month().unwrap();
-
-	println!("Day:");
-	let day: u8 = io::read_int();
-
-	let start = time::date::YearMonth {
-		year: year_start,
-		month: month_start,
-	};
-	let end = time::date::YearMonth {
-		year: year_end,
-		month: month_end,
-	};
-
-	for time::date::YearMonth { year, month } in time::date::month_range(start, end) {
-		let year_data = all_data.add_year(year);
-		let month_data = year_data.get_incomes_mut().add(&month);
-
-		month_data.push(Income {
-			day_of_year: time::date::Date { year, month, day },
-			price: price,
-			concepts: concepts.clone(),
-			from: from.clone(),
-			place: place.clone(),
-			description: description.clone(),
-		});
-	}
-}
-
 fn edit_expense(all_data: &mut AllActivities) {
 	println!("Select year:");
 	let year: u32 = io::read_int();
@@ -669,7 +577,12 @@ fn edit_expense(all_data: &mut AllActivities) {
 	{
 		let year_data = all_data.get_year(&year).unwrap();
 		let month_data = year_data.get_expenses().get_month(&month).unwrap();
-		menus::utils::display_and_accounting_expenses(month_data, &|_| true, 100);
+		menus::utils::display_and_accounting_expenses(
+			month_data,
+			&|_| true,
+			100,
+			all_data.get_exchange_rates(),
+		);
 	}
 
 	println!("Id of expense to be edited.");
@@ -681,6 +594,7 @@ fn edit_expense(all_data: &mut AllActivities) {
 
 	println!("Enter new concepts (leave blank to keep the value)");
 	let concepts = io::read_from_tree_options(&all_data.get_expense_concepts().get_tree());
+	let account_names = all_data.get_accounts().get_names();
 
 	// year is ensured to exist above
 	let year_data = all_data.add_year(year);
@@ -691,11 +605,33 @@ fn edit_expense(all_data: &mut AllActivities) {
 		expense.concepts = concepts;
 	}
 
-	println!("Price: {} (leave blank to keep the value)", expense.price);
-	if let Some(price) = io::read_float_or_empty::<f32>() {
+	println!(
+		"Price: {} (leave blank to keep the value)",
+		expense.price.display_in(&expense.currency)
+	);
+	if let Some(price) = io::read_float_or_empty::<Money>() {
 		expense.price = price;
 	}
 
+	println!(
+		"Currency: {} (leave blank to keep the value)",
+		expense.currency
+	);
+	if let Some(currency) = io::read_string_or_empty() {
+		expense.currency = currency.to_uppercase();
+	}
+	expense.price = expense.price.round_to_currency(&expense.currency);
+
+	if !account_names.is_empty() {
+		println!(
+			"Account: {} (leave blank to keep the value)",
+			expense.account
+		);
+		if let Some(account) = io::read_from_options_or_empty(&account_names) {
+			expense.account = account;
+		}
+	}
+
 	println!("Shop: {} (leave blank to keep the value)", expense.shop);
 	if let Some(shop) = io::read_string_or_empty() {
 		expense.shop = shop;
@@ -743,7 +679,12 @@ fn edit_income(all_data: &mut AllActivities) {
 	{
 		let year_data = all_data.get_year(&year).unwrap();
 		let month_data = year_data.get_incomes().get_month(&month).unwrap();
-		menus::utils::display_and_accounting_incomes(month_data, &|_| true, 100);
+		menus::utils::display_and_accounting_incomes(
+			month_data,
+			&|_| true,
+			100,
+			all_data.get_exchange_rates(),
+		);
 	}
 
 	println!("Id of expense to be edited.");
@@ -754,6 +695,7 @@ fn edit_income(all_data: &mut AllActivities) {
 	let id_income = id_income_opt.unwrap();
 
 	let concepts = io::read_from_tree_options(&all_data.get_income_concepts().get_tree());
+	let account_names = all_data.get_accounts().get_names();
 
 	let year_data = all_data.add_year(year);
 	let month_data = year_data.get_incomes_mut().add(&month);
@@ -763,11 +705,33 @@ fn edit_income(all_data: &mut AllActivities) {
 		income.concepts = concepts;
 	}
 
-	println!("Price: {} (leave blank to keep the value)", income.price);
-	if let Some(price) = io::read_float_or_empty::<f32>() {
+	println!(
+		"Price: {} (leave blank to keep the value)",
+		income.price.display_in(&income.currency)
+	);
+	if let Some(price) = io::read_float_or_empty::<Money>() {
 		income.price = price;
 	}
 
+	println!(
+		"Currency: {} (leave blank to keep the value)",
+		income.currency
+	);
+	if let Some(currency) = io::read_string_or_empty() {
+		income.currency = currency.to_uppercase();
+	}
+	income.price = income.price.round_to_currency(&income.currency);
+
+	if !account_names.is_empty() {
+		println!(
+			"Account: {} (leave blank to keep the value)",
+			income.account
+		);
+		if let Some(account) = io::read_from_options_or_empty(&account_names) {
+			income.account = account;
+		}
+	}
+
 	println!("From: {} (leave blank to keep the value)", income.from);
 	if let Some(from) = io::read_string_or_empty() {
 		income.from = from;
@@ -815,7 +779,7 @@ fn method(all_data: &mut AllActivities) {
 	{
 		let year_data = all_data.get_year(&year).unwrap();
 		let month_data = year_data.get().get_month(&month).unwrap();
-		menus::utils::display(month_data, &|_| true, 2);
+		menus::utils::display(month_data, &|_| true, 2, all_data.get_exchange_rates());
 	}
 
 	println!("Id of {} to be deleted.", thing);
@@ -858,7 +822,7 @@ fn method() {
 	println!("    19.     Add another {} this month", thing);
 	println!("    20.     Add another {} this year", thing);
 	println!("    21.     Add many {}s to a year and month", thing);
-	println!("    22. Add a monthly {}", thing);
+	println!("    22. Add a recurring {}", thing);
 	println!("    23. Edit an {}", thing);
 	println!("    24. Remove an {}", thing);
 	println!("     0. Leave");
@@ -893,7 +857,7 @@ pub fn menu_expenses(all_data: &mut AllActivities) {
 			19 => add_new_this_month_expense(all_data),
 			20 => add_new_this_year_expense(all_data),
 			21 => add_many_year_month_expense(all_data),
-			22 => add_monthly_expense(all_data),
+			22 => menus::recurring::add_recurring_expense(all_data),
 			23 => edit_expense(all_data),
 			24 => remove_expense(all_data),
 			_ => println!("Nothing to do..."),
@@ -932,7 +896,7 @@ pub fn menu_incomes(all_data: &mut AllActivities) {
 			19 => add_new_this_month_income(all_data),
 			20 => add_new_this_year_income(all_data),
 			21 => add_many_year_month_income(all_data),
-			22 => add_monthly_income(all_data),
+			22 => menus::recurring::add_recurring_income(all_data),
 			23 => edit_income(all_data),
 			24 => remove_income(all_data),
 			_ => println!("Nothing to do..."),
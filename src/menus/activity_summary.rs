@@ -30,16 +30,18 @@
  *
  ********************************************************************/
 
+use crate::economy::money::Money;
+
 pub struct ActivitySummary {
-	m_activity_to_money: std::collections::BTreeMap<Vec<String>, f32>,
-	m_total_money: f32,
+	m_activity_to_money: std::collections::BTreeMap<Vec<String>, Money>,
+	m_total_money: Money,
 }
 
 impl ActivitySummary {
 	pub fn new() -> ActivitySummary {
 		ActivitySummary {
 			m_activity_to_money: std::collections::BTreeMap::new(),
-			m_total_money: 0.0,
+			m_total_money: Money::new(),
 		}
 	}
 
@@ -63,7 +65,7 @@ impl ActivitySummary {
 		max_widths
 	}
 
-	pub fn iter_summary(&self) -> std::collections::btree_map::Iter<'_, Vec<String>, f32> {
+	pub fn iter_summary(&self) -> std::collections::btree_map::Iter<'_, Vec<String>, Money> {
 		self.m_activity_to_money.iter()
 	}
 
@@ -82,7 +84,7 @@ impl ActivitySummary {
 		self.m_total_money += other.m_total_money;
 	}
 
-	pub fn add(&mut self, types: Vec<String>, price: f32) {
+	pub fn add(&mut self, types: Vec<String>, price: Money) {
 		self.m_total_money += price;
 
 		match self.m_activity_to_money.get_mut(&types) {
@@ -95,7 +97,15 @@ impl ActivitySummary {
 		}
 	}
 
-	pub fn get_total(&self) -> f32 {
+	// Money of the given types, zero if there is none.
+	pub fn get_value(&self, types: &[String]) -> Money {
+		self.m_activity_to_money
+			.get(types)
+			.copied()
+			.unwrap_or_default()
+	}
+
+	pub fn get_total(&self) -> Money {
 		self.m_total_money
 	}
 
@@ -0,0 +1,134 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+extern crate chrono;
+
+use crate::io;
+
+use crate::menus;
+use crate::storage;
+
+type Backup = storage::backups::Backup;
+
+fn display_backups(backups: &[Backup]) {
+	for (i, backup) in backups.iter().enumerate() {
+		let modified: chrono::DateTime<chrono::Local> = backup.modified.into();
+		println!(
+			"    {:>3}. {} (version {}, saved on {})",
+			i + 1,
+			backup.file,
+			backup.version,
+			modified.format("%Y/%m/%d %H:%M:%S")
+		);
+	}
+	println!();
+}
+
+fn list_backups(data_dir: &str) -> Vec<Backup> {
+	let backups = storage::backups::list_backups(data_dir).expect("Could not list backups");
+	if backups.is_empty() {
+		println!("There are no backups.");
+	}
+	backups
+}
+
+fn print_backups(data_dir: &str) {
+	let backups = list_backups(data_dir);
+	if !backups.is_empty() {
+		display_backups(&backups);
+	}
+}
+
+// Restoring a backup reloads the data, which discards the changes that were
+// not saved.
+fn restore_backup(data_dir: &str, has_changes: bool) -> bool {
+	let backups = list_backups(data_dir);
+	if backups.is_empty() {
+		return false;
+	}
+	display_backups(&backups);
+
+	println!("Backup to be restored (leave blank to cancel):");
+	let Some(idx) = io::read_int_or_empty::<usize>() else {
+		return false;
+	};
+	if idx == 0 || idx > backups.len() {
+		println!("Backup '{idx}' does not exist.");
+		return false;
+	}
+
+	if has_changes {
+		println!("There are unsaved changes, which will be discarded. Restore anyway? (yes/no)");
+		if !io::read_yes_no() {
+			return false;
+		}
+	}
+
+	let backup = &backups[idx - 1];
+	storage::backups::restore_backup(data_dir, backup).expect("Could not restore backup");
+	println!(
+		"Restored '{}' from version {}.",
+		backup.file, backup.version
+	);
+	if has_changes {
+		println!("Unsaved changes have been discarded.");
+	}
+	true
+}
+
+fn print_backups_menu() {
+	println!("Query and restore the backups of the data:");
+	println!();
+	println!("    1. Show all backups");
+	println!("    2. Restore a backup");
+	println!("    0. Leave");
+}
+
+// Returns whether some backup was restored.
+pub fn menu_backups(data_dir: &str, has_changes: bool) -> bool {
+	let print_function = print_backups_menu;
+	let min_option = 0;
+	let max_option = 2;
+
+	let mut restored = false;
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => print_backups(data_dir),
+			2 => restored = restore_backup(data_dir, has_changes) || restored,
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+	restored
+}
@@ -0,0 +1,219 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+
+use crate::economy;
+use crate::menus;
+use crate::time;
+
+type Budget = economy::budgets::Budget;
+type BudgetPeriod = economy::budgets::BudgetPeriod;
+type AllActivities = economy::all_activities::AllActivities;
+type Money = economy::money::Money;
+type Month = time::date::Month;
+
+static CONCEPT_WIDTH: usize = 7;
+static MONEY_WIDTH: usize = 12;
+
+fn read_period() -> BudgetPeriod {
+	println!("Period (monthly/yearly):");
+	loop {
+		match io::read_string().parse::<BudgetPeriod>() {
+			Ok(period) => return period,
+			Err(_) => println!("Please, answer 'monthly' or 'yearly'."),
+		}
+	}
+}
+
+/**
+ * @brief Shows the budgets of a month, or of a year, against what was spent.
+ *
+ * Concepts whose spending exceeds their budget are highlighted. Nothing is
+ * shown when there are no budgets.
+ */
+pub fn display_budgets(all_data: &AllActivities, year: u32, month: Option<&Month>) {
+	let statuses = economy::budgets::get_budget_statuses(all_data, year, month);
+	if statuses.is_empty() {
+		return;
+	}
+	let currency = all_data.get_exchange_rates().get_reporting_currency();
+
+	let concept_width = std::cmp::max(
+		CONCEPT_WIDTH,
+		statuses
+			.iter()
+			.map(|s| s.concepts.join(" ; ").chars().count())
+			.max()
+			.unwrap_or(0),
+	);
+	let divider = format!(
+		"    +—{}—+—{}—+—{}—+—{}—+",
+		"—".repeat(concept_width),
+		"—".repeat(MONEY_WIDTH),
+		"—".repeat(MONEY_WIDTH),
+		"—".repeat(MONEY_WIDTH)
+	);
+
+	match month {
+		Some(m) => println!("Budgets of {m} {year} ({currency}):"),
+		None => println!("Budgets of {year} ({currency}):"),
+	}
+	println!("{divider}");
+	println!(
+		"    | {:^concept_width$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} |",
+		"Concept", "Budget", "Actual", "Remaining"
+	);
+	println!("{divider}");
+	let mut num_unconverted = 0;
+	for status in statuses.iter() {
+		let concept_text = status.concepts.join(" ; ");
+		let alert = if status.is_over_budget() {
+			" <- OVER BUDGET"
+		} else {
+			""
+		};
+		println!(
+			"    | {concept_text:<concept_width$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} |{alert}",
+			status.budget.display_in(currency),
+			status.spent.display_in(currency),
+			status.get_remaining().display_in(currency)
+		);
+		num_unconverted += status.num_unconverted;
+	}
+	println!("{divider}");
+	println!();
+	menus::utils::display_unconverted(num_unconverted, currency);
+}
+
+fn print_budgets_all(all_data: &AllActivities) {
+	for Budget {
+		concepts,
+		period,
+		amount,
+		currency,
+	} in all_data.get_budgets().iter()
+	{
+		println!(
+			"    {}: {} {currency} {period}",
+			concepts.join(" ; "),
+			amount.display_in(currency)
+		);
+	}
+	println!();
+}
+
+fn add_budget(all_data: &mut AllActivities) {
+	println!("Expense concepts:");
+	let concepts = io::read_from_tree_options(all_data.get_expense_concepts().get_tree());
+	if concepts.is_empty() {
+		return;
+	}
+
+	let period = read_period();
+
+	println!("Amount:");
+	let amount: Money = io::read_float();
+
+	let currency = menus::currencies::read_currency(all_data.get_exchange_rates());
+
+	all_data.get_budgets_mut().add(Budget {
+		concepts,
+		period,
+		amount: amount.round_to_currency(&currency),
+		currency,
+	});
+}
+
+fn remove_budget(all_data: &mut AllActivities) {
+	println!("Expense concepts of the budget:");
+	let concepts = io::read_from_tree_options(all_data.get_expense_concepts().get_tree());
+	if concepts.is_empty() {
+		return;
+	}
+
+	let period = read_period();
+
+	if !all_data.get_budgets_mut().remove(&concepts, &period) {
+		println!(
+			"There is no {period} budget for '{}'.",
+			concepts.join(" ; ")
+		);
+	}
+}
+
+fn print_budgets_month(all_data: &AllActivities) {
+	println!("Year:");
+	let year: u32 = io::read_int();
+	println!("Month:");
+	let Some(month) = time::io::read_correct_month() else {
+		return;
+	};
+	display_budgets(all_data, year, Some(&month));
+}
+
+fn print_budgets_year(all_data: &AllActivities) {
+	println!("Year:");
+	let year: u32 = io::read_int();
+	display_budgets(all_data, year, None);
+}
+
+fn print_budgets_menu() {
+	println!("Query and edit the budgets:");
+	println!();
+	println!("    1. Show all budgets");
+	println!("    2. Add or replace a budget");
+	println!("    3. Remove a budget");
+	println!("    4. Show the budgets of a month");
+	println!("    5. Show the budgets of a year");
+	println!("    0. Leave");
+}
+
+pub fn menu_budgets(all_data: &mut AllActivities) {
+	let print_function = print_budgets_menu;
+	let min_option = 0;
+	let max_option = 5;
+
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => print_budgets_all(all_data),
+			2 => add_budget(all_data),
+			3 => remove_budget(all_data),
+			4 => print_budgets_month(all_data),
+			5 => print_budgets_year(all_data),
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+}
@@ -0,0 +1,237 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+
+use crate::economy;
+use crate::menus;
+use crate::time;
+
+type AllActivities = economy::all_activities::AllActivities;
+type CashFlow = economy::cash_flow::CashFlow;
+type Money = economy::money::Money;
+type Month = time::date::Month;
+
+static PERIOD_WIDTH: usize = 14;
+static MONEY_WIDTH: usize = 12;
+static RATE_WIDTH: usize = 8;
+
+fn read_breakdown() -> bool {
+	println!("Break down by top-level concept? (yes/no)");
+	io::read_yes_no()
+}
+
+fn format_rate(cash_flow: &CashFlow) -> String {
+	match cash_flow.get_savings_rate() {
+		Some(rate) => format!("{:.1}%", 100.0 * rate),
+		None => "-".to_string(),
+	}
+}
+
+/**
+ * @brief Shows the cash flow of consecutive periods, with the net savings
+ * accumulated since the first one and a total.
+ */
+fn display_cash_flows(rows: Vec<(String, CashFlow)>, currency: &String, breakdown: bool) {
+	// concepts are indented by two spaces below their period
+	let concept_width = rows
+		.iter()
+		.flat_map(|(_, cf)| {
+			cf.incomes_by_concept
+				.keys()
+				.chain(cf.expenses_by_concept.keys())
+		})
+		.map(|concept| concept.chars().count() + 2)
+		.max()
+		.unwrap_or(0);
+	let period_width = if breakdown {
+		std::cmp::max(PERIOD_WIDTH, concept_width)
+	} else {
+		PERIOD_WIDTH
+	};
+
+	let divider = format!(
+		"    +—{}—+—{}—+—{}—+—{}—+—{}—+—{}—+",
+		"—".repeat(period_width),
+		"—".repeat(MONEY_WIDTH),
+		"—".repeat(MONEY_WIDTH),
+		"—".repeat(MONEY_WIDTH),
+		"—".repeat(RATE_WIDTH),
+		"—".repeat(MONEY_WIDTH)
+	);
+
+	println!("Cash flow ({currency}):");
+	println!("{divider}");
+	println!(
+		"    | {:^period_width$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} | {:^RATE_WIDTH$} | {:^MONEY_WIDTH$} |",
+		"Period", "Incomes", "Expenses", "Net", "Rate", "Cumulative"
+	);
+	println!("{divider}");
+
+	let mut total = CashFlow::new();
+	let mut cumulative = Money::new();
+	for (period, cash_flow) in rows {
+		cumulative += cash_flow.get_net();
+		println!(
+			"    | {period:<period_width$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>RATE_WIDTH$} | {:>MONEY_WIDTH$} |",
+			cash_flow.incomes.display_in(currency),
+			cash_flow.expenses.display_in(currency),
+			cash_flow.get_net().display_in(currency),
+			format_rate(&cash_flow),
+			cumulative.display_in(currency)
+		);
+		if breakdown {
+			for (concept, value) in cash_flow.incomes_by_concept.iter() {
+				let value = value.display_in(currency);
+				println!(
+					"    |   {concept:<width$} | {value:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>RATE_WIDTH$} | {:>MONEY_WIDTH$} |",
+					"", "", "", "",
+					width = period_width - 2
+				);
+			}
+			for (concept, value) in cash_flow.expenses_by_concept.iter() {
+				let value = value.display_in(currency);
+				println!(
+					"    |   {concept:<width$} | {:>MONEY_WIDTH$} | {value:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>RATE_WIDTH$} | {:>MONEY_WIDTH$} |",
+					"", "", "", "",
+					width = period_width - 2
+				);
+			}
+		}
+		total.merge(cash_flow);
+	}
+
+	println!("{divider}");
+	println!(
+		"    | {:<period_width$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>RATE_WIDTH$} | {:>MONEY_WIDTH$} |",
+		"Total",
+		total.incomes.display_in(currency),
+		total.expenses.display_in(currency),
+		total.get_net().display_in(currency),
+		format_rate(&total),
+		cumulative.display_in(currency)
+	);
+	println!("{divider}");
+	println!();
+
+	menus::utils::display_unconverted(total.num_unconverted, currency);
+}
+
+fn print_cash_flow_all_months(all_data: &AllActivities) {
+	let breakdown = read_breakdown();
+
+	let mut rows = Vec::new();
+	for year_data in all_data.iter_activities() {
+		let year = year_data.get_year();
+		for m in 0..12 {
+			let month = Month::from_u32(m).unwrap();
+			let cash_flow = economy::cash_flow::get_month_cash_flow(all_data, year, &month);
+			if cash_flow.has_data() {
+				rows.push((format!("{year}/{month}"), cash_flow));
+			}
+		}
+	}
+	display_cash_flows(
+		rows,
+		all_data.get_exchange_rates().get_reporting_currency(),
+		breakdown,
+	);
+}
+
+fn print_cash_flow_all_years(all_data: &AllActivities) {
+	let breakdown = read_breakdown();
+
+	let mut rows = Vec::new();
+	for year_data in all_data.iter_activities() {
+		let year = year_data.get_year();
+		let cash_flow = economy::cash_flow::get_year_cash_flow(all_data, year);
+		if cash_flow.has_data() {
+			rows.push((year.to_string(), cash_flow));
+		}
+	}
+	display_cash_flows(
+		rows,
+		all_data.get_exchange_rates().get_reporting_currency(),
+		breakdown,
+	);
+}
+
+fn print_cash_flow_year(all_data: &AllActivities) {
+	println!("What year do you want to see?");
+	let year: u32 = io::read_int();
+	if !all_data.has_year(&year) {
+		println!("Year '{year}' does not exist.");
+		return;
+	}
+	let breakdown = read_breakdown();
+
+	let mut rows = Vec::new();
+	for m in 0..12 {
+		let month = Month::from_u32(m).unwrap();
+		let cash_flow = economy::cash_flow::get_month_cash_flow(all_data, &year, &month);
+		if cash_flow.has_data() {
+			rows.push((month.to_string(), cash_flow));
+		}
+	}
+	display_cash_flows(
+		rows,
+		all_data.get_exchange_rates().get_reporting_currency(),
+		breakdown,
+	);
+}
+
+fn print_cash_flow_menu() {
+	println!("Query the cash flow:");
+	println!();
+	println!("    1. Show the cash flow of every month");
+	println!("    2. Show the cash flow of every year");
+	println!("    3. Show the cash flow of the months of a year");
+	println!("    0. Leave");
+}
+
+pub fn menu_cash_flow(all_data: &AllActivities) {
+	let print_function = print_cash_flow_menu;
+	let min_option = 0;
+	let max_option = 3;
+
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => print_cash_flow_all_months(all_data),
+			2 => print_cash_flow_all_years(all_data),
+			3 => print_cash_flow_year(all_data),
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+}
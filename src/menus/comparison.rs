@@ -0,0 +1,271 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::collections::BTreeSet;
+
+use crate::io;
+
+use crate::economy;
+use crate::export;
+use crate::menus;
+use crate::time;
+
+type ActivitySummary = menus::activity_summary::ActivitySummary;
+type AllActivities = economy::all_activities::AllActivities;
+type Date = time::date::Date;
+type DateRange = export::DateRange;
+type Money = economy::money::Money;
+type Month = time::date::Month;
+
+static CONCEPT_WIDTH: usize = 7;
+static MONEY_WIDTH: usize = 12;
+static CHANGE_WIDTH: usize = 8;
+
+// A period to compare, with the name it is shown with.
+struct Period {
+	name: String,
+	range: DateRange,
+}
+
+fn read_date() -> Option<Date> {
+	let text = io::read_string();
+	match Date::parse_valid(&text) {
+		Ok(date) => Some(date),
+		Err(e) => {
+			println!("'{text}' is not a valid date: {}", e.reason);
+			None
+		},
+	}
+}
+
+fn read_period() -> Option<Period> {
+	println!("Month, year or range of dates? (month/year/range)");
+	loop {
+		match io::read_string().to_lowercase().as_str() {
+			"month" => {
+				println!("Year:");
+				let year: u32 = io::read_int();
+				let month = time::io::read_correct_month()?;
+				let last_day = month.num_days(year);
+				return Some(Period {
+					name: format!("{year}/{month}"),
+					range: DateRange {
+						first: Some(Date {
+							year,
+							month: month.clone(),
+							day: 1,
+						}),
+						last: Some(Date {
+							year,
+							month,
+							day: last_day,
+						}),
+					},
+				});
+			},
+			"year" => {
+				println!("Year:");
+				let year: u32 = io::read_int();
+				return Some(Period {
+					name: year.to_string(),
+					range: DateRange {
+						first: Some(Date {
+							year,
+							month: Month::January,
+							day: 1,
+						}),
+						last: Some(Date {
+							year,
+							month: Month::December,
+							day: 31,
+						}),
+					},
+				});
+			},
+			"range" => {
+				println!("First day:");
+				let first = read_date()?;
+				println!("Last day:");
+				let last = read_date()?;
+				if last < first {
+					println!("The last day cannot be earlier than the first.");
+					return None;
+				}
+				return Some(Period {
+					name: format!("{first} - {last}"),
+					range: DateRange {
+						first: Some(first),
+						last: Some(last),
+					},
+				});
+			},
+			_ => println!("Please, answer 'month', 'year' or 'range'."),
+		}
+	}
+}
+
+// Sums the prices, in the reporting currency, of the activities of the period
+// by their first 'depth' concepts. Also returns the number of activities that
+// could not be converted.
+#[duplicate::duplicate_item(
+	method                         iter_thing;
+	[summarize_expenses_in_period] [iter_expenses];
+	[summarize_incomes_in_period]  [iter_incomes];
+)]
+fn method(all_data: &AllActivities, period: &Period, depth: usize) -> (ActivitySummary, u32) {
+	let rates = all_data.get_exchange_rates();
+
+	let mut summary = ActivitySummary::new();
+	let mut num_unconverted: u32 = 0;
+	for year_data in all_data.iter_activities() {
+		for month_data in year_data.iter_thing() {
+			for a in month_data
+				.iter()
+				.filter(|a| period.range.contains(&a.day_of_year))
+			{
+				match rates.convert(a.price, &a.currency, &a.day_of_year) {
+					Some(value) => summary.add(menus::statistics::concept(depth, a), value),
+					None => num_unconverted += 1,
+				}
+			}
+		}
+	}
+	(summary, num_unconverted)
+}
+
+fn format_change(before: Money, after: Money) -> String {
+	if before.is_zero() {
+		return "-".to_string();
+	}
+	let change = (after - before).to_f64() / before.to_f64();
+	format!("{:+.1}%", 100.0 * change)
+}
+
+/**
+ * @brief Shows the totals of every concept in two periods side by side with
+ * the absolute and relative change from the first to the second.
+ */
+fn display_comparison(
+	title: &str,
+	first: (&Period, &ActivitySummary),
+	second: (&Period, &ActivitySummary),
+	currency: &String,
+) {
+	let (first_period, first_summary) = first;
+	let (second_period, second_summary) = second;
+
+	let concepts: BTreeSet<&Vec<String>> = first_summary
+		.iter_summary()
+		.chain(second_summary.iter_summary())
+		.map(|(concepts, _)| concepts)
+		.collect();
+	let concept_width = concepts.iter().map(|c| c.join(" ; ").chars().count()).fold(
+		std::cmp::max(CONCEPT_WIDTH, title.chars().count()),
+		std::cmp::max,
+	);
+	let first_width = std::cmp::max(MONEY_WIDTH, first_period.name.chars().count());
+	let second_width = std::cmp::max(MONEY_WIDTH, second_period.name.chars().count());
+
+	let divider = format!(
+		"    +—{}—+—{}—+—{}—+—{}—+—{}—+",
+		"—".repeat(concept_width),
+		"—".repeat(first_width),
+		"—".repeat(second_width),
+		"—".repeat(MONEY_WIDTH),
+		"—".repeat(CHANGE_WIDTH)
+	);
+
+	println!("Comparison ({currency}):");
+	println!("{divider}");
+	println!(
+		"    | {title:^concept_width$} | {:^first_width$} | {:^second_width$} | {:^MONEY_WIDTH$} | {:^CHANGE_WIDTH$} |",
+		first_period.name, second_period.name, "Change", "Change %"
+	);
+	println!("{divider}");
+	for c in concepts.iter() {
+		let before = first_summary.get_value(c);
+		let after = second_summary.get_value(c);
+		println!(
+			"    | {:<concept_width$} | {:>first_width$} | {:>second_width$} | {:>MONEY_WIDTH$} | {:>CHANGE_WIDTH$} |",
+			c.join(" ; "),
+			before.display_in(currency),
+			after.display_in(currency),
+			(after - before).display_in(currency),
+			format_change(before, after)
+		);
+	}
+	println!("{divider}");
+
+	let before = first_summary.get_total();
+	let after = second_summary.get_total();
+	println!(
+		"    | {:<concept_width$} | {:>first_width$} | {:>second_width$} | {:>MONEY_WIDTH$} | {:>CHANGE_WIDTH$} |",
+		"Total",
+		before.display_in(currency),
+		after.display_in(currency),
+		(after - before).display_in(currency),
+		format_change(before, after)
+	);
+	println!("{divider}");
+	println!();
+}
+
+#[duplicate::duplicate_item(
+	method              title            summarize;
+	[compare_expenses]  ["Expense type"] [summarize_expenses_in_period];
+	[compare_incomes]   ["Income type"]  [summarize_incomes_in_period];
+)]
+pub fn method(all_data: &AllActivities) {
+	println!("How many types?");
+	let depth: usize = io::read_int();
+
+	println!("First period:");
+	let Some(first) = read_period() else {
+		return;
+	};
+	println!("Second period:");
+	let Some(second) = read_period() else {
+		return;
+	};
+
+	let (first_summary, first_unconverted) = summarize(all_data, &first, depth);
+	let (second_summary, second_unconverted) = summarize(all_data, &second, depth);
+
+	let currency = all_data.get_exchange_rates().get_reporting_currency();
+	display_comparison(
+		title,
+		(&first, &first_summary),
+		(&second, &second_summary),
+		currency,
+	);
+	menus::utils::display_unconverted(first_unconverted + second_unconverted, currency);
+}
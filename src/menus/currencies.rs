@@ -0,0 +1,57 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+
+use crate::economy;
+
+type AllActivities = economy::all_activities::AllActivities;
+type ExchangeRates = economy::exchange_rates::ExchangeRates;
+
+pub fn read_currency(rates: &ExchangeRates) -> String {
+	println!("Currency (leave blank for {}):", rates.get_home_currency());
+	match io::read_string_or_empty() {
+		Some(currency) => currency.to_uppercase(),
+		None => rates.get_home_currency().clone(),
+	}
+}
+
+pub fn change_reporting_currency(all_data: &mut AllActivities) {
+	let rates = all_data.get_exchange_rates_mut();
+	println!(
+		"Reporting currency: {} (leave blank to keep the value)",
+		rates.get_reporting_currency()
+	);
+	if let Some(currency) = io::read_string_or_empty() {
+		rates.set_reporting_currency(currency.to_uppercase());
+	}
+}
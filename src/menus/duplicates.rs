@@ -0,0 +1,119 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+
+use crate::economy;
+
+type Expense = economy::expense::Expense;
+type Income = economy::income::Income;
+type AllActivities = economy::all_activities::AllActivities;
+
+enum DuplicateAction {
+	Keep,
+	Merge,
+	Skip,
+}
+
+fn read_duplicate_action() -> DuplicateAction {
+	println!("Keep both, merge the new one into the existing one, or skip the new one? (keep/merge/skip)");
+	loop {
+		match io::read_string().to_lowercase().as_str() {
+			"keep" | "k" => return DuplicateAction::Keep,
+			"merge" | "m" => return DuplicateAction::Merge,
+			"skip" | "s" => return DuplicateAction::Skip,
+			_ => println!("Please, answer 'keep', 'merge' or 'skip'."),
+		}
+	}
+}
+
+#[duplicate::duplicate_item(
+	method                  activity  counterpart;
+	[display_line_expense]  [Expense] [shop];
+	[display_line_income]   [Income]  [from];
+)]
+fn method(a: &activity) {
+	println!(
+		"    {:<17} | {:>12} {} | {} | {}",
+		a.day_of_year.to_string(),
+		a.price.display_in(&a.currency),
+		a.currency,
+		a.counterpart,
+		a.description
+	);
+}
+
+// Adds the activity to the data. If it looks like one already there, asks
+// whether to keep both, merge them or skip it. Returns whether a new activity
+// was added.
+#[duplicate::duplicate_item(
+	method         activity  name        find_duplicate           merge           display_line           get_month_activities get_activities_mut;
+	[add_expense]  [Expense] ["expense"] [find_duplicate_expense] [merge_expense] [display_line_expense] [get_month_expenses] [get_expenses_mut];
+	[add_income]   [Income]  ["income"]  [find_duplicate_income]  [merge_income]  [display_line_income]  [get_month_incomes]  [get_incomes_mut];
+)]
+pub fn method(all_data: &mut AllActivities, a: activity) -> bool {
+	let action = match economy::duplicates::find_duplicate(all_data, &a) {
+		Some((year, month, idx)) => {
+			let existing = all_data
+				.get_month_activities(&year, &month)
+				.expect("The duplicate should exist")
+				.get(idx);
+
+			println!("This {} looks like one already added:", name);
+			display_line(existing);
+			println!("The new one is:");
+			display_line(&a);
+
+			Some((year, month, idx, read_duplicate_action()))
+		},
+		None => None,
+	};
+
+	match action {
+		Some((_, _, _, DuplicateAction::Skip)) => {
+			println!("The new {} was skipped.", name);
+			false
+		},
+		Some((year, month, idx, DuplicateAction::Merge)) => {
+			let year_data = all_data.add_year(year);
+			let month_data = year_data.get_activities_mut().add(&month);
+			economy::duplicates::merge(month_data.get_mut(idx), a);
+			false
+		},
+		Some((_, _, _, DuplicateAction::Keep)) | None => {
+			let date = a.day_of_year.clone();
+			let year_data = all_data.add_year(date.year);
+			year_data.get_activities_mut().add(&date.month).push(a);
+			true
+		},
+	}
+}
@@ -0,0 +1,693 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+use crate::utils;
+
+use crate::economy;
+use crate::export;
+use crate::import;
+use crate::menus;
+use crate::time;
+
+type Expense = economy::expense::Expense;
+type Income = economy::income::Income;
+type Money = economy::money::Money;
+type AllActivities = economy::all_activities::AllActivities;
+type Date = time::date::Date;
+type Month = time::date::Month;
+type DateRange = export::DateRange;
+type Field = export::table::Field;
+type Table = export::table::Table;
+type TableFormat = export::table::TableFormat;
+type ActivitySummary = menus::activity_summary::ActivitySummary;
+type Cell = menus::utils::Cell;
+type HtmlReport = export::html::HtmlReport;
+type SortFunction = fn(&(Vec<String>, Cell), &(Vec<String>, Cell)) -> std::cmp::Ordering;
+
+// Directory of the base path the HTML reports are written into.
+static REPORTS_DIRECTORY: &str = "reports";
+// Largest number of bars of the charts of statistics.
+static MAX_CHART_BARS: usize = 10;
+
+// Reads the format of the dates of a QIF file.
+pub fn read_qif_date_format() -> String {
+	println!(
+		"Format of the dates (leave blank for '{}'):",
+		import::qif::DEFAULT_DATE_FORMAT
+	);
+	io::read_string_or_empty().unwrap_or(import::qif::DEFAULT_DATE_FORMAT.to_string())
+}
+
+fn export_qif_file(all_data: &AllActivities) {
+	println!("QIF file:");
+	let filename = io::read_string();
+
+	println!("Year to export (leave blank for all):");
+	let year = io::read_int_or_empty::<u32>();
+
+	let date_format = read_qif_date_format();
+
+	let mut contents = Vec::new();
+	let num_transactions = match export::qif::write_qif(&mut contents, all_data, year, &date_format)
+	{
+		Ok(n) => n,
+		Err(e) => {
+			println!("Could not export the data: {e}");
+			return;
+		},
+	};
+	if let Err(e) = std::fs::write(&filename, contents) {
+		println!("Could not write '{filename}': {e}");
+		return;
+	}
+	println!("Exported {num_transactions} transactions into '{filename}'.");
+}
+
+// Reads a date, in the format of the data files or ISO-8601.
+fn read_date_or_empty() -> Option<Option<Date>> {
+	let Some(text) = io::read_string_or_empty() else {
+		return Some(None);
+	};
+	match Date::parse_valid(&text) {
+		Ok(date) => Some(Some(date)),
+		Err(e) => {
+			println!("'{text}' is not a valid date: {}", e.reason);
+			None
+		},
+	}
+}
+
+fn read_date_range() -> Option<DateRange> {
+	println!("First date to export (leave blank for the first activity):");
+	let first = read_date_or_empty()?;
+	println!("Last date to export (leave blank for the last activity):");
+	let last = read_date_or_empty()?;
+	Some(DateRange { first, last })
+}
+
+#[duplicate::duplicate_item(
+	method                    format        write_contents;
+	[export_beancount_file]   ["Beancount"] [write_beancount_contents];
+	[export_ledger_file]      ["Ledger"]    [write_ledger_contents];
+)]
+fn method(all_data: &AllActivities) {
+	println!("{} file:", format);
+	let filename = io::read_string();
+
+	let Some(range) = read_date_range() else {
+		return;
+	};
+
+	let mut contents = Vec::new();
+	let num_transactions = match write_contents(&mut contents, all_data, &range) {
+		Ok(n) => n,
+		Err(e) => {
+			println!("Could not export the data: {e}");
+			return;
+		},
+	};
+	if let Err(e) = std::fs::write(&filename, contents) {
+		println!("Could not write '{filename}': {e}");
+		return;
+	}
+	println!("Exported {num_transactions} transactions into '{filename}'.");
+}
+
+fn write_beancount_contents(
+	contents: &mut Vec<u8>,
+	all_data: &AllActivities,
+	range: &DateRange,
+) -> std::io::Result<usize> {
+	let operating_currency = all_data.get_exchange_rates().get_reporting_currency();
+	export::beancount::write_beancount(contents, all_data, range, operating_currency)
+}
+
+fn write_ledger_contents(
+	contents: &mut Vec<u8>,
+	all_data: &AllActivities,
+	range: &DateRange,
+) -> std::io::Result<usize> {
+	export::ledger::write_ledger(contents, all_data, range)
+}
+
+// Conditions the exported activities must meet. Missing conditions are met
+// by every activity.
+struct ActivityFilter {
+	year: Option<u32>,
+	month: Option<Month>,
+	concepts: Vec<String>,
+	counterpart: Option<String>,
+	lower: Option<Money>,
+	upper: Option<Money>,
+}
+
+impl ActivityFilter {
+	// Every activity of the year, or of the month of the year if given.
+	fn period(year: u32, month: Option<Month>) -> ActivityFilter {
+		ActivityFilter {
+			year: Some(year),
+			month,
+			concepts: Vec::new(),
+			counterpart: None,
+			lower: None,
+			upper: None,
+		}
+	}
+}
+
+#[duplicate::duplicate_item(
+	method                  get_concepts           counterpart_title;
+	[read_filter_expenses]  [get_expense_concepts] ["Shop"];
+	[read_filter_incomes]   [get_income_concepts]  ["From"];
+)]
+fn method(all_data: &AllActivities) -> ActivityFilter {
+	println!("Year (leave blank for all):");
+	let year = io::read_int_or_empty::<u32>();
+
+	println!("Month (leave blank for all):");
+	let month = time::io::read_correct_month_or_empty();
+
+	println!("Concepts (leave blank for all):");
+	let concepts = io::read_from_tree_options(all_data.get_concepts().get_tree());
+
+	println!("{} containing (leave blank for any):", counterpart_title);
+	let counterpart = io::read_string_or_empty();
+
+	println!("Lowest price (leave blank for none):");
+	let lower = io::read_float_or_empty::<Money>();
+	println!("Highest price (leave blank for none):");
+	let upper = io::read_float_or_empty::<Money>();
+
+	ActivityFilter {
+		year,
+		month,
+		concepts,
+		counterpart,
+		lower,
+		upper,
+	}
+}
+
+#[duplicate::duplicate_item(
+	method            activity  counterpart_field;
+	[matches_expense] [Expense] [shop];
+	[matches_income]  [Income]  [from];
+)]
+fn method(filter: &ActivityFilter, a: &activity) -> bool {
+	filter.year.is_none_or(|y| y == a.day_of_year.year)
+		&& filter
+			.month
+			.as_ref()
+			.is_none_or(|m| *m == a.day_of_year.month)
+		&& utils::vector_includes(&a.concepts, &filter.concepts)
+		&& filter
+			.counterpart
+			.as_ref()
+			.is_none_or(|c| utils::string_contains(c, &a.counterpart_field, false, false))
+		&& filter.lower.is_none_or(|l| l <= a.price)
+		&& filter.upper.is_none_or(|u| a.price <= u)
+}
+
+// Reads the name of the file to export a table into. Its extension states the
+// format of the file.
+fn read_table_filename() -> Option<(String, TableFormat)> {
+	println!("CSV or JSON file:");
+	let filename = io::read_string();
+	match TableFormat::from_filename(&filename) {
+		Some(format) => Some((filename, format)),
+		None => {
+			println!("The name of the file must end in '.csv' or '.json'.");
+			None
+		},
+	}
+}
+
+fn write_table_file(filename: &str, format: TableFormat, table: &Table) {
+	let mut contents = Vec::new();
+	table
+		.write(&mut contents, format)
+		.expect("Writing into memory should not fail");
+	if let Err(e) = std::fs::write(filename, contents) {
+		println!("Could not write '{filename}': {e}");
+		return;
+	}
+	println!("Exported {} rows into '{filename}'.", table.num_rows());
+}
+
+#[duplicate::duplicate_item(
+	method           iter_thing      matches           counterpart_field location_field counterpart_name location_name;
+	[expenses_table] [iter_expenses] [matches_expense] [shop]            [city]         ["shop"]         ["city"];
+	[incomes_table]  [iter_incomes]  [matches_income]  [from]            [place]        ["from"]         ["place"];
+)]
+fn method(all_data: &AllActivities, filter: &ActivityFilter) -> Table {
+	let mut table = Table::new(&[
+		"date",
+		"price",
+		"currency",
+		"account",
+		"concepts",
+		counterpart_name,
+		location_name,
+		"description",
+		"transaction_id",
+	]);
+	for year_data in all_data.iter_activities() {
+		for month_data in year_data.iter_thing() {
+			for a in month_data.iter().filter(|a| matches(filter, a)) {
+				table.add_row(vec![
+					Field::Text(format!("{:#}", a.day_of_year)),
+					Field::Number(a.price.display_in(&a.currency).to_string()),
+					Field::Text(a.currency.clone()),
+					Field::Text(a.account.clone()),
+					Field::List(a.concepts.clone()),
+					Field::Text(a.counterpart_field.clone()),
+					Field::Text(a.location_field.clone()),
+					Field::Text(a.description.clone()),
+					Field::Text(a.transaction_id.clone()),
+				]);
+			}
+		}
+	}
+	table
+}
+
+#[duplicate::duplicate_item(
+	method                   read_filter            make_table;
+	[export_expenses_table]  [read_filter_expenses] [expenses_table];
+	[export_incomes_table]   [read_filter_incomes]  [incomes_table];
+)]
+fn method(all_data: &AllActivities) {
+	let Some((filename, format)) = read_table_filename() else {
+		return;
+	};
+	let filter = read_filter(all_data);
+
+	write_table_file(&filename, format, &make_table(all_data, &filter));
+}
+
+// Sums the prices, in the reporting currency, of the activities that meet
+// the filter by their first 'depth' concepts. Also returns the number of
+// activities that could not be converted.
+#[duplicate::duplicate_item(
+	method               iter_thing      matches;
+	[summarize_expenses] [iter_expenses] [matches_expense];
+	[summarize_incomes]  [iter_incomes]  [matches_income];
+)]
+fn method(
+	all_data: &AllActivities,
+	filter: &ActivityFilter,
+	depth: usize,
+) -> (ActivitySummary, u32) {
+	let rates = all_data.get_exchange_rates();
+
+	let mut summary = ActivitySummary::new();
+	let mut num_unconverted: u32 = 0;
+	for year_data in all_data.iter_activities() {
+		for month_data in year_data.iter_thing() {
+			for a in month_data.iter().filter(|a| matches(filter, a)) {
+				match rates.convert(a.price, &a.currency, &a.day_of_year) {
+					Some(value) => summary.add(menus::statistics::concept(depth, a), value),
+					None => num_unconverted += 1,
+				}
+			}
+		}
+	}
+	(summary, num_unconverted)
+}
+
+fn summary_table(summary: &ActivitySummary, currency: &str) -> Table {
+	let total = summary.get_total();
+	let mut table = Table::new(&["concepts", "total", "currency", "percentage"]);
+	for (concepts, value) in summary.iter_summary() {
+		let percentage = if total.is_zero() {
+			0.0
+		} else {
+			value.to_f64() / total.to_f64() * 100.0
+		};
+		table.add_row(vec![
+			Field::List(concepts.clone()),
+			Field::Number(value.display_in(currency).to_string()),
+			Field::Text(currency.to_string()),
+			Field::Number(format!("{percentage:.2}")),
+		]);
+	}
+	table
+}
+
+#[duplicate::duplicate_item(
+	method                    read_filter            summarize;
+	[export_expenses_summary] [read_filter_expenses] [summarize_expenses];
+	[export_incomes_summary]  [read_filter_incomes]  [summarize_incomes];
+)]
+fn method(all_data: &AllActivities) {
+	let Some((filename, format)) = read_table_filename() else {
+		return;
+	};
+	let filter = read_filter(all_data);
+
+	println!("How many levels of concepts? (leave blank for all)");
+	let depth = io::read_int_or_empty::<usize>().unwrap_or(usize::MAX);
+
+	let currency = all_data.get_exchange_rates().get_reporting_currency();
+	let (summary, num_unconverted) = summarize(all_data, &filter, depth);
+	menus::utils::display_unconverted(num_unconverted, currency);
+
+	write_table_file(&filename, format, &summary_table(&summary, currency));
+}
+
+fn read_statistics_sort() -> SortFunction {
+	let print_function = || {
+		println!("Sort the rows:");
+		println!("    1. Alphabetically");
+		println!("    2. By times");
+		println!("    3. By value");
+	};
+	match menus::utils::read_option(print_function, 1, 3) {
+		1 => menus::statistics::sort_by_concept,
+		2 => menus::statistics::sort_by_times,
+		_ => menus::statistics::sort_by_value,
+	}
+}
+
+// A table of statistics. The rows are grouped by the values of the first
+// column; the classifier column is left out when 'classifier_name' is empty.
+fn statistics_table(
+	rows: Vec<(Vec<String>, Cell)>,
+	currency: &str,
+	group_name: &str,
+	classifier_name: &str,
+) -> Table {
+	let mut header = vec![group_name];
+	if !classifier_name.is_empty() {
+		header.push(classifier_name);
+	}
+	header.extend(["times", "total", "currency"]);
+
+	let mut table = Table::new(&header);
+	for (group, cell) in rows.into_iter() {
+		let mut row = vec![Field::List(group)];
+		if !classifier_name.is_empty() {
+			row.push(Field::Text(cell.classifier));
+		}
+		row.extend([
+			Field::Number(cell.num_times.to_string()),
+			Field::Number(cell.total_value.display_in(currency).to_string()),
+			Field::Text(currency.to_string()),
+		]);
+		table.add_row(row);
+	}
+	table
+}
+
+fn write_statistics_file(
+	all_data: &AllActivities,
+	filename: &str,
+	format: TableFormat,
+	(rows, num_unconverted): (Vec<(Vec<String>, Cell)>, u32),
+	group_name: &str,
+	classifier_name: &str,
+) {
+	let currency = all_data.get_exchange_rates().get_reporting_currency();
+	menus::utils::display_unconverted(num_unconverted, currency);
+
+	let table = statistics_table(rows, currency, group_name, classifier_name);
+	write_table_file(filename, format, &table);
+}
+
+fn export_expenses_statistics(all_data: &AllActivities) {
+	let Some((filename, format)) = read_table_filename() else {
+		return;
+	};
+
+	let print_function = || {
+		println!("Group the expenses:");
+		println!("    1. By concepts");
+		println!("    2. By shop");
+	};
+	let group = menus::utils::read_option(print_function, 1, 2);
+	let sort = read_statistics_sort();
+
+	match group {
+		1 => {
+			println!("How many levels of concepts? (leave blank for all)");
+			let depth = io::read_int_or_empty::<usize>().unwrap_or(usize::MAX);
+			let rows = menus::statistics::collect_expenses_concepts(
+				all_data,
+				|_| true,
+				sort,
+				|e| menus::statistics::concept(depth, e),
+			);
+			write_statistics_file(all_data, &filename, format, rows, "concepts", "");
+		},
+		_ => {
+			let rows = menus::statistics::collect_expenses_shops(all_data, |_| true, sort);
+			write_statistics_file(all_data, &filename, format, rows, "shop", "city");
+		},
+	}
+}
+
+fn export_incomes_statistics(all_data: &AllActivities) {
+	let Some((filename, format)) = read_table_filename() else {
+		return;
+	};
+
+	let print_function = || {
+		println!("Group the incomes:");
+		println!("    1. By concepts");
+		println!("    2. By from");
+		println!("    3. By place");
+		println!("    4. By from and place");
+	};
+	let group = menus::utils::read_option(print_function, 1, 4);
+	let sort = read_statistics_sort();
+
+	let (rows, group_name) = match group {
+		1 => {
+			println!("How many levels of concepts? (leave blank for all)");
+			let depth = io::read_int_or_empty::<usize>().unwrap_or(usize::MAX);
+			let rows = menus::statistics::collect_incomes_concepts(
+				all_data,
+				|_| true,
+				sort,
+				|i| menus::statistics::concept(depth, i),
+			);
+			(rows, "concepts")
+		},
+		2 => (
+			menus::statistics::collect_of_from_and_place_incomes(
+				all_data,
+				sort,
+				menus::statistics::from,
+			),
+			"from",
+		),
+		3 => (
+			menus::statistics::collect_of_from_and_place_incomes(
+				all_data,
+				sort,
+				menus::statistics::place,
+			),
+			"place",
+		),
+		_ => (
+			menus::statistics::collect_of_from_and_place_incomes(
+				all_data,
+				sort,
+				menus::statistics::from_place,
+			),
+			"from_place",
+		),
+	};
+	write_statistics_file(all_data, &filename, format, rows, group_name, "");
+}
+
+// Bars of the amounts summarized, the largest first.
+fn summary_bars(summary: &ActivitySummary) -> Vec<(String, f64)> {
+	let mut bars: Vec<(String, f64)> = summary
+		.iter_summary()
+		.map(|(concepts, value)| {
+			let label = if concepts.is_empty() {
+				"(no concept)".to_string()
+			} else {
+				concepts.join(" ; ")
+			};
+			(label, value.to_f64())
+		})
+		.collect();
+	bars.sort_by(|a, b| b.1.total_cmp(&a.1));
+	bars
+}
+
+// Bars of the first rows of a table of statistics.
+fn statistics_bars(rows: &[(Vec<String>, Cell)]) -> Vec<(String, f64)> {
+	rows.iter()
+		.take(MAX_CHART_BARS)
+		.map(|(group, cell)| (group.join(" ; "), cell.total_value.to_f64()))
+		.collect()
+}
+
+fn write_html_report(all_data: &AllActivities, data_dir: &str) {
+	println!("Year:");
+	let year: u32 = io::read_int();
+	if !all_data.has_year(&year) {
+		println!("Year '{year}' does not exist.");
+		return;
+	}
+	println!("Month (leave blank for the whole year):");
+	let month = time::io::read_correct_month_or_empty();
+
+	let (title, name) = match &month {
+		Some(m) => (
+			format!("Finances of {m} {year}"),
+			format!("{year}-{:02}.html", m.clone() as u32 + 1),
+		),
+		None => (format!("Finances of {year}"), format!("{year}.html")),
+	};
+
+	let filter = ActivityFilter::period(year, month);
+	let rates = all_data.get_exchange_rates();
+	let currency = rates.get_reporting_currency();
+	let mut report = HtmlReport::new(&title);
+
+	// overview
+	let (expenses, num_unconverted_expenses) = summarize_expenses(all_data, &filter, usize::MAX);
+	let (incomes, num_unconverted_incomes) = summarize_incomes(all_data, &filter, usize::MAX);
+	report.add_paragraph(&format!(
+		"Expenses: {} {currency}. Incomes: {} {currency}. Balance: {} {currency}.",
+		expenses.get_total().display_in(currency),
+		incomes.get_total().display_in(currency),
+		(incomes.get_total() - expenses.get_total()).display_in(currency)
+	));
+	let num_unconverted = num_unconverted_expenses + num_unconverted_incomes;
+	if num_unconverted > 0 {
+		report.add_paragraph(&format!(
+			"{num_unconverted} activities were left out of the sums: missing exchange rates into {currency}."
+		));
+	}
+
+	// summaries by concept
+	report.add_heading("Expenses by concept");
+	report.add_bar_chart(
+		&summary_bars(&summarize_expenses(all_data, &filter, 1).0),
+		currency,
+	);
+	report.add_table(&summary_table(&expenses, currency));
+
+	report.add_heading("Incomes by concept");
+	report.add_bar_chart(
+		&summary_bars(&summarize_incomes(all_data, &filter, 1).0),
+		currency,
+	);
+	report.add_table(&summary_table(&incomes, currency));
+
+	// histories
+	let in_period = |e: &Expense| matches_expense(&filter, e);
+
+	let (shops, _) = menus::statistics::collect_expenses_shops(
+		all_data,
+		in_period,
+		menus::statistics::sort_by_value,
+	);
+	report.add_heading("Expenses by shop");
+	report.add_bar_chart(&statistics_bars(&shops), currency);
+	report.add_table(&statistics_table(shops, currency, "shop", "city"));
+
+	let (cities, _) = menus::statistics::collect_expenses_concepts(
+		all_data,
+		in_period,
+		menus::statistics::sort_by_value,
+		|e| vec![e.city.clone()],
+	);
+	report.add_heading("Expenses by city");
+	report.add_bar_chart(&statistics_bars(&cities), currency);
+	report.add_table(&statistics_table(cities, currency, "city", ""));
+
+	// activities
+	report.add_heading("Expenses");
+	report.add_table(&expenses_table(all_data, &filter));
+	report.add_heading("Incomes");
+	report.add_table(&incomes_table(all_data, &filter));
+
+	let directory = format!("{data_dir}{REPORTS_DIRECTORY}");
+	if let Err(e) = std::fs::create_dir_all(&directory) {
+		println!("Could not create '{directory}': {e}");
+		return;
+	}
+	let filename = format!("{directory}/{name}");
+	if let Err(e) = std::fs::write(&filename, report.to_html()) {
+		println!("Could not write '{filename}': {e}");
+		return;
+	}
+	println!("Report written into '{filename}'.");
+}
+
+fn print_export_menu() {
+	println!("Export activities to other programs:");
+	println!();
+	println!("    1. Export to a QIF file");
+	println!("    2. Export to a Beancount file");
+	println!("    3. Export to a Ledger file");
+	println!("    4. Export expenses to a CSV or JSON file");
+	println!("    5. Export incomes to a CSV or JSON file");
+	println!("    6. Export a summary of expenses to a CSV or JSON file");
+	println!("    7. Export a summary of incomes to a CSV or JSON file");
+	println!("    8. Export expense statistics to a CSV or JSON file");
+	println!("    9. Export income statistics to a CSV or JSON file");
+	println!("   10. Write an HTML report of a month or a year");
+	println!("    0. Leave");
+}
+
+pub fn menu_export(all_data: &AllActivities, data_dir: &str) {
+	let print_function = print_export_menu;
+	let min_option = 0;
+	let max_option = 10;
+
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => export_qif_file(all_data),
+			2 => export_beancount_file(all_data),
+			3 => export_ledger_file(all_data),
+			4 => export_expenses_table(all_data),
+			5 => export_incomes_table(all_data),
+			6 => export_expenses_summary(all_data),
+			7 => export_incomes_summary(all_data),
+			8 => export_expenses_statistics(all_data),
+			9 => export_incomes_statistics(all_data),
+			10 => write_html_report(all_data, data_dir),
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+}
@@ -0,0 +1,473 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::collections::BTreeSet;
+
+use crate::io;
+
+use crate::economy;
+use crate::import;
+use crate::menus;
+use crate::time;
+
+type AllActivities = economy::all_activities::AllActivities;
+type BadLine = economy::io::BadLine;
+type CsvProfile = import::csv::CsvProfile;
+type ImportDefaults = import::ImportDefaults;
+type ImportedActivities = import::ImportedActivities;
+type SignConvention = import::csv::SignConvention;
+type Month = time::date::Month;
+
+#[duplicate::duplicate_item(
+	method                     activities title      payee;
+	[display_preview_expenses] [expenses] ["Expenses"] [shop];
+	[display_preview_incomes]  [incomes]  ["Incomes"]  [from];
+)]
+fn method(imported: &ImportedActivities, year: u32, month: &Month) {
+	let in_month: Vec<_> = imported
+		.activities
+		.iter()
+		.enumerate()
+		.filter(|(_, a)| a.day_of_year.year == year && a.day_of_year.month == *month)
+		.collect();
+	if in_month.is_empty() {
+		return;
+	}
+
+	println!("    {}:", title);
+	for (i, a) in in_month {
+		println!(
+			"        {:>4} | {:<17} | {:>12} {} | {} | {}",
+			i,
+			a.day_of_year.to_string(),
+			a.price.display_in(&a.currency),
+			a.currency,
+			a.payee,
+			a.description
+		);
+	}
+}
+
+pub fn display_bad_lines(bad_lines: &[BadLine]) {
+	if bad_lines.is_empty() {
+		return;
+	}
+	for bad_line in bad_lines {
+		println!("    {bad_line}");
+	}
+	println!(
+		"{} rows could not be read and are left out.",
+		bad_lines.len()
+	);
+	println!();
+}
+
+// Shows the activities read and merges them into the data if the user agrees.
+pub fn preview_and_merge(all_data: &mut AllActivities, imported: ImportedActivities) {
+	if imported.is_empty() {
+		println!("There is nothing to import.");
+		return;
+	}
+
+	println!("Activities that would be added, by month:");
+	println!();
+
+	// the months the activities would be added to
+	let months: BTreeSet<(u32, Month)> = imported
+		.expenses
+		.iter()
+		.map(|e| &e.day_of_year)
+		.chain(imported.incomes.iter().map(|i| &i.day_of_year))
+		.map(|d| (d.year, d.month.clone()))
+		.collect();
+	for (year, month) in months.iter() {
+		println!("Into {month} {year}:");
+		display_preview_expenses(&imported, *year, month);
+		display_preview_incomes(&imported, *year, month);
+		println!();
+	}
+
+	let num_duplicates = imported
+		.expenses
+		.iter()
+		.filter(|e| economy::duplicates::find_duplicate_expense(all_data, e).is_some())
+		.count()
+		+ imported
+			.incomes
+			.iter()
+			.filter(|i| economy::duplicates::find_duplicate_income(all_data, i).is_some())
+			.count();
+	if num_duplicates > 0 {
+		println!(
+			"{num_duplicates} of them look like activities already added. You will be asked about each of them."
+		);
+	}
+
+	let num_expenses = imported.expenses.len();
+	let num_incomes = imported.incomes.len();
+	println!("Import {num_expenses} expenses and {num_incomes} incomes? (yes/no)");
+	if !io::read_yes_no() {
+		println!("Nothing was imported.");
+		return;
+	}
+
+	let num_added_expenses = imported
+		.expenses
+		.into_iter()
+		.map(|e| menus::duplicates::add_expense(all_data, e))
+		.filter(|&added| added)
+		.count();
+	let num_added_incomes = imported
+		.incomes
+		.into_iter()
+		.map(|i| menus::duplicates::add_income(all_data, i))
+		.filter(|&added| added)
+		.count();
+	println!("Imported {num_added_expenses} expenses and {num_added_incomes} incomes.");
+}
+
+fn read_profiles(data_dir: &str) -> Vec<CsvProfile> {
+	import::csv::read_profiles(data_dir).expect("Could not read the CSV mapping profiles")
+}
+
+fn import_csv_file(all_data: &mut AllActivities, data_dir: &str) {
+	let profiles = read_profiles(data_dir);
+	if profiles.is_empty() {
+		println!("There are no CSV mapping profiles. Add one first.");
+		return;
+	}
+
+	println!("Mapping profile ('?' to list them):");
+	let names = profiles.iter().map(|p| p.name.clone()).collect();
+	let Some(name) = io::read_from_options_or_empty(&names) else {
+		return;
+	};
+	let profile = profiles
+		.iter()
+		.find(|p| p.name == name)
+		.expect("The profile should exist");
+
+	println!("CSV file:");
+	let filename = io::read_string();
+
+	let defaults = profile.get_defaults(all_data.get_exchange_rates().get_home_currency());
+	let mut bad_lines = Vec::new();
+	let imported = match import::csv::read_csv_file(&filename, profile, &defaults, &mut bad_lines) {
+		Ok(imported) => imported,
+		Err(e) => {
+			println!("Could not read '{filename}': {e}");
+			return;
+		},
+	};
+
+	display_bad_lines(&bad_lines);
+	preview_and_merge(all_data, imported);
+}
+
+// Asks for the fields of the imported activities that statements do not state.
+fn read_import_defaults(all_data: &AllActivities) -> ImportDefaults {
+	let account = menus::accounts::read_account(all_data.get_accounts());
+
+	println!("Concepts of the imported expenses (leave blank for none):");
+	let expense_concepts = io::read_from_tree_options(all_data.get_expense_concepts().get_tree());
+	println!("Concepts of the imported incomes (leave blank for none):");
+	let income_concepts = io::read_from_tree_options(all_data.get_income_concepts().get_tree());
+
+	ImportDefaults {
+		currency: all_data.get_exchange_rates().get_home_currency().clone(),
+		account,
+		expense_concepts,
+		income_concepts,
+	}
+}
+
+fn import_ofx_file(all_data: &mut AllActivities) {
+	println!("OFX or QFX file:");
+	let filename = io::read_string();
+
+	let defaults = read_import_defaults(all_data);
+	let mut bad_lines = Vec::new();
+	let imported = match import::ofx::read_ofx_file(&filename, &defaults, &mut bad_lines) {
+		Ok(imported) => imported,
+		Err(e) => {
+			println!("Could not read '{filename}': {e}");
+			return;
+		},
+	};
+
+	display_bad_lines(&bad_lines);
+	preview_and_merge(all_data, imported);
+}
+
+fn import_qif_file(all_data: &mut AllActivities) {
+	println!("QIF file:");
+	let filename = io::read_string();
+
+	let date_format = menus::export::read_qif_date_format();
+	println!(
+		"Decimal separator of the amounts (leave blank for '{}'):",
+		import::qif::DEFAULT_DECIMAL_SEPARATOR
+	);
+	let decimal_separator = read_char_or_empty().unwrap_or(import::qif::DEFAULT_DECIMAL_SEPARATOR);
+	let defaults = read_import_defaults(all_data);
+	let mut bad_lines = Vec::new();
+	let imported = match import::qif::read_qif_file(
+		&filename,
+		&date_format,
+		decimal_separator,
+		&defaults,
+		&mut bad_lines,
+	) {
+		Ok(imported) => imported,
+		Err(e) => {
+			println!("Could not read '{filename}': {e}");
+			return;
+		},
+	};
+
+	display_bad_lines(&bad_lines);
+	preview_and_merge(all_data, imported);
+}
+
+fn import_camt_file(all_data: &mut AllActivities) {
+	println!("camt.053 file:");
+	let filename = io::read_string();
+
+	let defaults = read_import_defaults(all_data);
+	let mut bad_lines = Vec::new();
+	let imported = match import::camt::read_camt_file(&filename, &defaults, &mut bad_lines) {
+		Ok(imported) => imported,
+		Err(e) => {
+			println!("Could not read '{filename}': {e}");
+			return;
+		},
+	};
+
+	display_bad_lines(&bad_lines);
+	preview_and_merge(all_data, imported);
+}
+
+fn display_column(title: &str, column: Option<usize>) {
+	match column {
+		Some(c) => println!("        {title}: column {c}"),
+		None => println!("        {title}: none"),
+	}
+}
+
+fn print_csv_profiles(data_dir: &str) {
+	let profiles = read_profiles(data_dir);
+	if profiles.is_empty() {
+		println!("There are no CSV mapping profiles.");
+		return;
+	}
+
+	for p in profiles.iter() {
+		println!("    {}", p.name);
+		println!(
+			"        Delimiter: '{}', lines skipped: {}",
+			p.delimiter, p.skip_lines
+		);
+		println!(
+			"        Date: column {}, format '{}'",
+			p.date_column, p.date_format
+		);
+		println!(
+			"        Amount: column {}, decimal separator '{}', {}",
+			p.amount_column, p.decimal_separator, p.sign
+		);
+		display_column("Payee", p.payee_column);
+		display_column("Description", p.description_column);
+		println!(
+			"        Currency: {}",
+			p.currency.as_deref().unwrap_or("home currency")
+		);
+		println!(
+			"        Account: {}",
+			p.account.as_deref().unwrap_or("none")
+		);
+		println!(
+			"        Concepts of expenses: {}",
+			p.expense_concepts.join(";")
+		);
+		println!(
+			"        Concepts of incomes: {}",
+			p.income_concepts.join(";")
+		);
+	}
+	println!();
+}
+
+// Reads a character, or the tab character when the input is 'tab'.
+fn read_char_or_empty() -> Option<char> {
+	loop {
+		let s = io::read_string_or_empty()?;
+		if s == "tab" {
+			return Some('\t');
+		}
+		let mut chars = s.chars();
+		if let (Some(c), None) = (chars.next(), chars.next()) {
+			return Some(c);
+		}
+	}
+}
+
+fn read_sign_convention() -> SignConvention {
+	println!("Sign of the amounts:");
+	println!("    1. Negative amounts are expenses");
+	println!("    2. Positive amounts are expenses");
+	println!("    3. Expenses and incomes are in separate columns");
+	loop {
+		match io::read_int::<u32>() {
+			1 => break SignConvention::NegativeExpenses,
+			2 => break SignConvention::PositiveExpenses,
+			3 => {
+				println!("Column of the incomes:");
+				let income_column = io::read_int();
+				break SignConvention::SeparateColumns { income_column };
+			},
+			_ => {},
+		}
+	}
+}
+
+fn add_csv_profile(all_data: &AllActivities, data_dir: &str) {
+	let mut profiles = read_profiles(data_dir);
+
+	println!("Name of the profile:");
+	let name = io::read_string();
+	if profiles.iter().any(|p| p.name == name) {
+		println!("Profile '{name}' already exists.");
+		return;
+	}
+
+	println!("Delimiter of the columns ('tab' for tabs, leave blank for ','):");
+	let delimiter = read_char_or_empty().unwrap_or(',');
+
+	println!("Number of lines to skip at the top of the file (leave blank for none):");
+	let skip_lines = io::read_int_or_empty().unwrap_or(0);
+
+	println!("Column of the dates (starting at 1):");
+	let date_column = io::read_int();
+	println!("Format of the dates (e.g. '%d.%m.%Y' for 05.03.2024):");
+	let date_format = io::read_string();
+
+	println!("Column of the amounts (of the expenses, if incomes are in another column):");
+	let amount_column = io::read_int();
+	let sign = read_sign_convention();
+	println!("Decimal separator (leave blank for '.'):");
+	let decimal_separator = read_char_or_empty().unwrap_or('.');
+
+	println!("Column of the payees (leave blank for none):");
+	let payee_column = io::read_int_or_empty();
+	println!("Column of the descriptions (leave blank for none):");
+	let description_column = io::read_int_or_empty();
+
+	println!("Currency of the account (leave blank for the home currency):");
+	let currency = io::read_string_or_empty().map(|c| c.to_uppercase());
+	let defaults = read_import_defaults(all_data);
+
+	profiles.push(CsvProfile {
+		name,
+		delimiter,
+		skip_lines,
+		date_column,
+		date_format,
+		amount_column,
+		sign,
+		decimal_separator,
+		payee_column,
+		description_column,
+		currency,
+		account: if defaults.account.is_empty() {
+			None
+		} else {
+			Some(defaults.account)
+		},
+		expense_concepts: defaults.expense_concepts,
+		income_concepts: defaults.income_concepts,
+	});
+	import::csv::write_profiles(data_dir, &profiles)
+		.expect("Could not write the CSV mapping profiles");
+}
+
+fn remove_csv_profile(data_dir: &str) {
+	let mut profiles = read_profiles(data_dir);
+	if profiles.is_empty() {
+		println!("There are no CSV mapping profiles.");
+		return;
+	}
+
+	println!("Profile to remove ('?' to list them):");
+	let names = profiles.iter().map(|p| p.name.clone()).collect();
+	let Some(name) = io::read_from_options_or_empty(&names) else {
+		return;
+	};
+
+	profiles.retain(|p| p.name != name);
+	import::csv::write_profiles(data_dir, &profiles)
+		.expect("Could not write the CSV mapping profiles");
+}
+
+fn print_import_menu() {
+	println!("Import activities from bank statements:");
+	println!();
+	println!("    1. Import a CSV file");
+	println!("    2. Import an OFX or QFX file");
+	println!("    3. Import a QIF file");
+	println!("    4. Import a camt.053 file");
+	println!("    5. Show CSV mapping profiles");
+	println!("    6. Add a CSV mapping profile");
+	println!("    7. Remove a CSV mapping profile");
+	println!("    0. Leave");
+}
+
+pub fn menu_import(all_data: &mut AllActivities, data_dir: &str) {
+	let print_function = print_import_menu;
+	let min_option = 0;
+	let max_option = 7;
+
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => import_csv_file(all_data, data_dir),
+			2 => import_ofx_file(all_data),
+			3 => import_qif_file(all_data),
+			4 => import_camt_file(all_data),
+			5 => print_csv_profiles(data_dir),
+			6 => add_csv_profile(all_data, data_dir),
+			7 => remove_csv_profile(data_dir),
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+}
@@ -30,8 +30,22 @@
  *
  ********************************************************************/
 
+pub mod accounts;
 pub mod activities;
 pub mod activity_summary;
+pub mod backups;
+pub mod budgets;
+pub mod cash_flow;
+pub mod comparison;
 pub mod concept_types;
+pub mod currencies;
+pub mod duplicates;
+pub mod export;
+pub mod import;
+pub mod recurring;
+pub mod reminders;
+pub mod savings_goals;
 pub mod statistics;
+pub mod transfers;
 pub mod utils;
+pub mod validation;
@@ -0,0 +1,344 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+
+use crate::economy;
+use crate::menus;
+use crate::time;
+
+type AllActivities = economy::all_activities::AllActivities;
+type Date = time::date::Date;
+type Frequency = economy::recurring::Frequency;
+type Money = economy::money::Money;
+type RecurringKind = economy::recurring::RecurringKind;
+type RecurringTemplate = economy::recurring::RecurringTemplate;
+
+fn read_frequency() -> Frequency {
+	println!("Frequency (weekly/monthly/yearly/every N days):");
+	loop {
+		match io::read_string().parse::<Frequency>() {
+			Ok(frequency) => return frequency,
+			Err(_) => println!("Please, answer 'weekly', 'monthly', 'yearly' or 'every N days'."),
+		}
+	}
+}
+
+// Reads a date, or returns the given default if left blank. Returns None if
+// the date is not valid.
+fn read_date_or(default: Option<Date>) -> Option<Option<Date>> {
+	let Some(text) = io::read_string_or_empty() else {
+		return Some(default);
+	};
+	match Date::parse_valid(&text) {
+		Ok(date) => Some(Some(date)),
+		Err(e) => {
+			println!("'{text}' is not a valid date: {}", e.reason);
+			None
+		},
+	}
+}
+
+fn read_template(all_data: &AllActivities) -> Option<RecurringTemplate> {
+	let templates = all_data.get_recurring_templates();
+	println!("Name of the recurring activity ('?' to list them):");
+	let name = io::read_from_options_or_empty(&templates.get_names())?;
+	templates.get_template(&name).cloned()
+}
+
+fn generate_up_to_today(all_data: &mut AllActivities) {
+	let num_added = economy::recurring::generate_occurrences(all_data, &Date::today());
+	println!("Added {num_added} occurrences up to today.");
+}
+
+fn replace_template(
+	all_data: &mut AllActivities,
+	old: &RecurringTemplate,
+	new: Option<RecurringTemplate>,
+) {
+	let (num_rewritten, num_removed) = economy::recurring::replace_template(all_data, old, new);
+	println!("Updated {num_rewritten} and removed {num_removed} of the activities it added.");
+}
+
+fn print_recurring_all(all_data: &AllActivities) {
+	for template in all_data.get_recurring_templates().iter() {
+		let today = Date::today();
+		println!(
+			"    {} ({}): {} {} {}, from {}",
+			template.name,
+			template.kind,
+			template.get_amount(&today).display_in(&template.currency),
+			template.currency,
+			template.frequency,
+			template.start
+		);
+		if let Some(end) = &template.end {
+			println!("        Until: {end}");
+		}
+		println!("        Concepts: {}", template.concepts.join(" ; "));
+		println!(
+			"        Counterpart: {} ({})",
+			template.counterpart, template.place
+		);
+		if template.amounts.len() > 1 {
+			for (date, amount) in template.amounts.iter() {
+				println!(
+					"        From {date}: {} {}",
+					amount.display_in(&template.currency),
+					template.currency
+				);
+			}
+		}
+		if let Some(until) = &template.generated_until {
+			println!("        Added up to: {until}");
+		}
+	}
+	println!();
+}
+
+#[duplicate::duplicate_item(
+	method                   template_kind             get_concepts            counterpart_prompt place_prompt;
+	[add_recurring_expense]  [RecurringKind::Expense]  [get_expense_concepts]  ["Shop:"]          ["City:"];
+	[add_recurring_income]   [RecurringKind::Income]   [get_income_concepts]   ["From:"]          ["Place:"];
+)]
+pub fn method(all_data: &mut AllActivities) {
+	println!("Name of the recurring activity:");
+	let name = io::read_string();
+	if all_data
+		.get_recurring_templates()
+		.get_template(&name)
+		.is_some()
+	{
+		println!("Recurring activity '{name}' already exists.");
+		return;
+	}
+
+	println!("Concepts:");
+	let concepts = io::read_from_tree_options(all_data.get_concepts().get_tree());
+	if concepts.is_empty() {
+		return;
+	}
+
+	println!("Price:");
+	let price: Money = io::read_float();
+
+	let currency = menus::currencies::read_currency(all_data.get_exchange_rates());
+
+	let account = menus::accounts::read_account(all_data.get_accounts());
+
+	println!(counterpart_prompt);
+	let counterpart = io::read_string();
+
+	println!(place_prompt);
+	let place = io::read_string();
+
+	println!("Description:");
+	let description = io::read_string_or_empty().unwrap_or("".to_string());
+
+	let frequency = read_frequency();
+
+	println!("First day (leave blank for today):");
+	let Some(Some(start)) = read_date_or(Some(Date::today())) else {
+		return;
+	};
+
+	println!("Last day (leave blank to repeat forever):");
+	let Some(end) = read_date_or(None) else {
+		return;
+	};
+	if end.as_ref().is_some_and(|end| *end < start) {
+		println!("The last day cannot be earlier than the first.");
+		return;
+	}
+
+	all_data
+		.get_recurring_templates_mut()
+		.add(RecurringTemplate {
+			name,
+			kind: template_kind,
+			frequency,
+			amounts: vec![(start.clone(), price.round_to_currency(&currency))],
+			start,
+			end,
+			currency,
+			account,
+			concepts,
+			counterpart,
+			place,
+			description,
+			generated_until: None,
+		});
+	generate_up_to_today(all_data);
+}
+
+fn change_amount(all_data: &mut AllActivities) {
+	let Some(old) = read_template(all_data) else {
+		return;
+	};
+
+	println!("Day from which the amount changes (leave blank for today):");
+	let Some(Some(date)) = read_date_or(Some(Date::today())) else {
+		return;
+	};
+	if date < old.start {
+		println!(
+			"The day cannot be earlier than the first day, {}.",
+			old.start
+		);
+		return;
+	}
+
+	println!("New amount:");
+	let amount: Money = io::read_float();
+
+	let mut new = old.clone();
+	new.set_amount(date, amount.round_to_currency(&old.currency));
+	replace_template(all_data, &old, Some(new));
+}
+
+fn edit_template(all_data: &mut AllActivities) {
+	let Some(old) = read_template(all_data) else {
+		return;
+	};
+	let mut new = old.clone();
+
+	println!(
+		"Concepts (leave blank to keep '{}'):",
+		old.concepts.join(" ; ")
+	);
+	let concept_types = match old.kind {
+		RecurringKind::Expense => all_data.get_expense_concepts(),
+		RecurringKind::Income => all_data.get_income_concepts(),
+	};
+	let concepts = io::read_from_tree_options(concept_types.get_tree());
+	if !concepts.is_empty() {
+		new.concepts = concepts;
+	}
+
+	println!("Counterpart (leave blank to keep '{}'):", old.counterpart);
+	if let Some(counterpart) = io::read_string_or_empty() {
+		new.counterpart = counterpart;
+	}
+
+	println!("Place (leave blank to keep '{}'):", old.place);
+	if let Some(place) = io::read_string_or_empty() {
+		new.place = place;
+	}
+
+	println!("Description (leave blank to keep '{}'):", old.description);
+	if let Some(description) = io::read_string_or_empty() {
+		new.description = description;
+	}
+
+	let accounts = all_data.get_accounts();
+	if accounts.size() > 0 {
+		println!(
+			"Account (leave blank to keep '{}', '?' to list them):",
+			old.account
+		);
+		if let Some(account) = io::read_from_options_or_empty(&accounts.get_names()) {
+			new.account = account;
+		}
+	}
+
+	replace_template(all_data, &old, Some(new));
+}
+
+fn cancel_template(all_data: &mut AllActivities) {
+	let Some(old) = read_template(all_data) else {
+		return;
+	};
+
+	println!("Last day (leave blank for today):");
+	let Some(Some(end)) = read_date_or(Some(Date::today())) else {
+		return;
+	};
+	if end < old.start {
+		println!(
+			"The last day cannot be earlier than the first day, {}.",
+			old.start
+		);
+		return;
+	}
+
+	let mut new = old.clone();
+	new.end = Some(end);
+	replace_template(all_data, &old, Some(new));
+}
+
+fn remove_template(all_data: &mut AllActivities) {
+	let Some(old) = read_template(all_data) else {
+		return;
+	};
+
+	println!("Remove also the activities it added? (yes/no)");
+	if io::read_yes_no() {
+		replace_template(all_data, &old, None);
+	} else {
+		economy::recurring::forget_template(all_data, &old);
+	}
+}
+
+fn print_recurring_menu() {
+	println!("Query and edit the recurring activities:");
+	println!();
+	println!("    1. Show all recurring activities");
+	println!("    2. Add a recurring expense");
+	println!("    3. Add a recurring income");
+	println!("    4. Change the amount of a recurring activity");
+	println!("    5. Edit a recurring activity");
+	println!("    6. Cancel a recurring activity");
+	println!("    7. Remove a recurring activity");
+	println!("    0. Leave");
+}
+
+pub fn menu_recurring(all_data: &mut AllActivities) {
+	let print_function = print_recurring_menu;
+	let min_option = 0;
+	let max_option = 7;
+
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => print_recurring_all(all_data),
+			2 => add_recurring_expense(all_data),
+			3 => add_recurring_income(all_data),
+			4 => change_amount(all_data),
+			5 => edit_template(all_data),
+			6 => cancel_template(all_data),
+			7 => remove_template(all_data),
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+}
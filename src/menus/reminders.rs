@@ -0,0 +1,80 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::economy;
+use crate::time;
+
+type AllActivities = economy::all_activities::AllActivities;
+type Date = time::date::Date;
+
+/**
+ * @brief Shows the recurring activities due in the next days and the monthly
+ * activities not recorded yet this month.
+ *
+ * Nothing is shown when there is nothing to remind of.
+ */
+pub fn display_reminders(all_data: &AllActivities, num_days: u32) {
+	let today = Date::today();
+
+	let upcoming = economy::reminders::get_upcoming(all_data, &today, num_days);
+	if !upcoming.is_empty() {
+		println!("Due in the next {num_days} days:");
+		for (date, template) in upcoming.iter() {
+			println!(
+				"    {:<17} | {:>12} {} | {} ({})",
+				date.to_string(),
+				template.get_amount(date).display_in(&template.currency),
+				template.currency,
+				template.name,
+				template.kind
+			);
+		}
+		println!();
+	}
+
+	let expected = economy::reminders::get_expected(all_data, &today);
+	if !expected.is_empty() {
+		println!("Recorded every month but not yet this month:");
+		for e in expected.iter() {
+			println!(
+				"    usually on day {:>2} | {:>12} {} | {} | {} ({})",
+				e.day,
+				e.price.display_in(&e.currency),
+				e.currency,
+				e.concepts.join(" ; "),
+				e.counterpart,
+				e.kind
+			);
+		}
+		println!();
+	}
+}
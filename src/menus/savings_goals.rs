@@ -0,0 +1,290 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+
+use crate::economy;
+use crate::menus;
+use crate::time;
+
+type AllActivities = economy::all_activities::AllActivities;
+type Date = time::date::Date;
+type GoalProgress = economy::savings_goals::GoalProgress;
+type Money = economy::money::Money;
+type SavingsGoal = economy::savings_goals::SavingsGoal;
+
+static MONTH_WIDTH: usize = 14;
+static MONEY_WIDTH: usize = 12;
+
+fn read_date() -> Option<Date> {
+	let text = io::read_string();
+	match Date::parse_valid(&text) {
+		Ok(date) => Some(date),
+		Err(e) => {
+			println!("'{text}' is not a valid date: {}", e.reason);
+			None
+		},
+	}
+}
+
+fn read_goal_name(all_data: &AllActivities) -> Option<String> {
+	println!("Name of the goal ('?' to list them):");
+	io::read_from_options_or_empty(&all_data.get_savings_goals().get_names())
+}
+
+fn print_savings_goals_all(all_data: &AllActivities) {
+	for goal in all_data.get_savings_goals().iter() {
+		println!(
+			"    {}: {} {} from {} to {}",
+			goal.name,
+			goal.target.display_in(&goal.currency),
+			goal.currency,
+			goal.start,
+			goal.deadline
+		);
+		if !goal.expense_concepts.is_empty() {
+			println!("        Expenses of: {}", goal.expense_concepts.join(" ; "));
+		}
+		if !goal.income_concepts.is_empty() {
+			println!("        Incomes of: {}", goal.income_concepts.join(" ; "));
+		}
+		if !goal.account.is_empty() {
+			println!("        Account: {}", goal.account);
+		}
+	}
+	println!();
+}
+
+fn add_savings_goal(all_data: &mut AllActivities) {
+	println!("Name of the goal:");
+	let name = io::read_string();
+
+	println!("Amount to save:");
+	let target: Money = io::read_float();
+
+	let currency = menus::currencies::read_currency(all_data.get_exchange_rates());
+
+	println!("Start date (leave blank for today):");
+	let start = match io::read_string_or_empty() {
+		None => Date::today(),
+		Some(text) => match Date::parse_valid(&text) {
+			Ok(date) => date,
+			Err(e) => {
+				println!("'{text}' is not a valid date: {}", e.reason);
+				return;
+			},
+		},
+	};
+
+	println!("Deadline:");
+	let Some(deadline) = read_date() else {
+		return;
+	};
+	if deadline < start {
+		println!("The deadline cannot be earlier than the start.");
+		return;
+	}
+
+	println!("Expense concepts that count (leave blank for all):");
+	let expense_concepts = io::read_from_tree_options(all_data.get_expense_concepts().get_tree());
+	println!("Income concepts that count (leave blank for all):");
+	let income_concepts = io::read_from_tree_options(all_data.get_income_concepts().get_tree());
+	let account = menus::accounts::read_account(all_data.get_accounts());
+
+	all_data.get_savings_goals_mut().add(SavingsGoal {
+		name,
+		target: target.round_to_currency(&currency),
+		currency,
+		start,
+		deadline,
+		expense_concepts,
+		income_concepts,
+		account,
+	});
+}
+
+fn remove_savings_goal(all_data: &mut AllActivities) {
+	let Some(name) = read_goal_name(all_data) else {
+		return;
+	};
+	if !all_data.get_savings_goals_mut().remove(&name) {
+		println!("Goal '{name}' does not exist.");
+	}
+}
+
+fn display_projection(goal: &SavingsGoal, progress: &GoalProgress, today: &Date) {
+	let currency = &goal.currency;
+	let percentage = if goal.target.is_zero() {
+		100.0
+	} else {
+		100.0 * progress.saved.to_f64() / goal.target.to_f64()
+	};
+	println!(
+		"Saved {} of {} {currency} ({percentage:.0}%).",
+		progress.saved.display_in(currency),
+		goal.target.display_in(currency)
+	);
+
+	if goal.deadline < *today {
+		if progress.saved >= goal.target {
+			println!("The deadline has passed and the goal was reached.");
+		} else {
+			println!(
+				"The deadline has passed and the goal was missed by {} {currency}.",
+				(goal.target - progress.saved).display_in(currency)
+			);
+		}
+		return;
+	}
+
+	if progress.months.is_empty() {
+		println!("The goal starts on {}.", goal.start);
+		return;
+	}
+	if progress.get_num_rated_months() == 0 {
+		println!("There is no projection until the first full month of the goal is over.");
+		return;
+	}
+
+	let projection = progress.get_projection();
+	println!(
+		"Saving {} {currency} a month, {} {currency} will have been saved by {}.",
+		progress.get_monthly_rate().display_in(currency),
+		projection.display_in(currency),
+		goal.deadline
+	);
+	if projection >= goal.target {
+		println!("At this rate the goal will be reached.");
+	} else {
+		println!(
+			"At this rate the goal will be missed by {} {currency}.",
+			(goal.target - projection).display_in(currency)
+		);
+		if progress.remaining_months > 0 {
+			let needed = (goal.target - progress.saved)
+				.multiply(1.0 / progress.remaining_months as f64)
+				.display_in(currency);
+			println!("Saving {needed} {currency} a month from now on is needed to reach it.");
+		}
+	}
+}
+
+fn display_goal_progress(all_data: &AllActivities, goal: &SavingsGoal) {
+	let today = Date::today();
+	let progress = economy::savings_goals::get_goal_progress(all_data, goal, &today);
+
+	let month_divider = "—".repeat(MONTH_WIDTH);
+	let money_divider = "—".repeat(MONEY_WIDTH);
+	let currency = &goal.currency;
+
+	println!(
+		"Goal: {} ({} {currency} by {})",
+		goal.name,
+		goal.target.display_in(currency),
+		goal.deadline
+	);
+	println!("    +—{month_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+");
+	println!(
+		"    | {:^MONTH_WIDTH$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} | {:^MONEY_WIDTH$} |",
+		"Month", "Incomes", "Expenses", "Transfers", "Saved", "Accumulated"
+	);
+	println!("    +—{month_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+");
+
+	let mut accumulated = Money::new();
+	for month_savings in progress.months.iter() {
+		let saved = month_savings.get_saved();
+		accumulated += saved;
+
+		let month_text = format!("{}/{}", month_savings.year, month_savings.month);
+		println!(
+			"    | {month_text:<MONTH_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} | {:>MONEY_WIDTH$} |",
+			month_savings.incomes.display_in(currency),
+			month_savings.expenses.display_in(currency),
+			month_savings.transfers.display_in(currency),
+			saved.display_in(currency),
+			accumulated.display_in(currency)
+		);
+	}
+	println!("    +—{month_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+—{money_divider}—+");
+	println!();
+
+	display_projection(goal, &progress, &today);
+	println!();
+	menus::utils::display_unconverted(progress.num_unconverted, currency);
+}
+
+fn print_progress_goal(all_data: &AllActivities) {
+	let Some(name) = read_goal_name(all_data) else {
+		return;
+	};
+	match all_data.get_savings_goals().get_goal(&name) {
+		Some(goal) => display_goal_progress(all_data, goal),
+		None => println!("Goal '{name}' does not exist."),
+	}
+}
+
+fn print_progress_all(all_data: &AllActivities) {
+	for goal in all_data.get_savings_goals().iter() {
+		display_goal_progress(all_data, goal);
+	}
+}
+
+fn print_savings_goals_menu() {
+	println!("Query and edit the savings goals:");
+	println!();
+	println!("    1. Show all savings goals");
+	println!("    2. Add or replace a savings goal");
+	println!("    3. Remove a savings goal");
+	println!("    4. Show the progress of a savings goal");
+	println!("    5. Show the progress of all savings goals");
+	println!("    0. Leave");
+}
+
+pub fn menu_savings_goals(all_data: &mut AllActivities) {
+	let print_function = print_savings_goals_menu;
+	let min_option = 0;
+	let max_option = 5;
+
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => print_savings_goals_all(all_data),
+			2 => add_savings_goal(all_data),
+			3 => remove_savings_goal(all_data),
+			4 => print_progress_goal(all_data),
+			5 => print_progress_all(all_data),
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+}
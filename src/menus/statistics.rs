@@ -36,6 +36,7 @@ use crate::economy::income;
 use crate::economy::traits::HasConcepts;
 
 use crate::io;
+use crate::menus;
 use crate::menus::utils;
 
 type Expense = expense::Expense;
@@ -43,44 +44,57 @@ type Income = income::Income;
 type AllActivities = all_activities::AllActivities;
 type Cell = utils::Cell;
 
-fn sort_by_concept(a: &(Vec<String>, Cell), b: &(Vec<String>, Cell)) -> std::cmp::Ordering {
+pub fn sort_by_concept(a: &(Vec<String>, Cell), b: &(Vec<String>, Cell)) -> std::cmp::Ordering {
 	a.0.cmp(&b.0)
 }
 
-fn sort_by_times(a: &(Vec<String>, Cell), b: &(Vec<String>, Cell)) -> std::cmp::Ordering {
+pub fn sort_by_times(a: &(Vec<String>, Cell), b: &(Vec<String>, Cell)) -> std::cmp::Ordering {
 	if b.1.num_times == a.1.num_times {
 		return a.0.cmp(&b.0);
 	}
 	b.1.num_times.cmp(&a.1.num_times)
 }
 
-fn sort_by_value(a: &(Vec<String>, Cell), b: &(Vec<String>, Cell)) -> std::cmp::Ordering {
+pub fn sort_by_value(a: &(Vec<String>, Cell), b: &(Vec<String>, Cell)) -> std::cmp::Ordering {
 	if b.1.total_value == a.1.total_value {
 		return a.0.cmp(&b.0);
 	}
-	b.1.total_value.total_cmp(&a.1.total_value)
+	b.1.total_value.cmp(&a.1.total_value)
 }
 
-fn concept<T: HasConcepts>(n: usize, t: &T) -> Vec<String> {
+pub fn concept<T: HasConcepts>(n: usize, t: &T) -> Vec<String> {
 	t.get_concepts().iter().take(n).cloned().collect()
 }
 
 #[duplicate::duplicate_item(
-	method                      t         title            iter_thing;
-	[history_expenses_concepts] [Expense] ["Expense type"] [iter_expenses];
-	[history_incomes_concepts]  [Income]  ["Income type"]  [iter_incomes];
+	method                      t         iter_thing;
+	[collect_expenses_concepts] [Expense] [iter_expenses];
+	[collect_incomes_concepts]  [Income]  [iter_incomes];
 )]
-fn method<SortFunc, GroupByFunc>(all_data: &AllActivities, sort: SortFunc, group_by: GroupByFunc)
+pub fn method<FilterFunc, SortFunc, GroupByFunc>(
+	all_data: &AllActivities,
+	filter: FilterFunc,
+	sort: SortFunc,
+	group_by: GroupByFunc,
+) -> (Vec<(Vec<String>, Cell)>, u32)
 where
+	FilterFunc: Fn(&t) -> bool,
 	SortFunc: Fn(&(Vec<String>, Cell), &(Vec<String>, Cell)) -> std::cmp::Ordering,
 	GroupByFunc: Fn(&t) -> Vec<String>,
 {
+	let rates = all_data.get_exchange_rates();
+	let mut num_unconverted: u32 = 0;
+
 	let mut summary: std::collections::BTreeMap<Vec<String>, Cell> =
 		std::collections::BTreeMap::new();
 
 	for year in all_data.iter_activities() {
 		for month in year.iter_thing() {
-			for exp in month.iter() {
+			for exp in month.iter().filter(|e| filter(e)) {
+				let Some(value) = rates.convert(exp.price, &exp.currency, &exp.day_of_year) else {
+					num_unconverted += 1;
+					continue;
+				};
 				let group = group_by(&exp);
 				match summary.get_mut(&group) {
 					Some(Cell {
@@ -89,7 +103,7 @@ where
 						total_value,
 					}) => {
 						*num_times += 1;
-						*total_value += exp.price;
+						*total_value += value;
 					},
 					None => {
 						summary.insert(
@@ -97,7 +111,7 @@ where
 							Cell {
 								classifier: "".to_string(),
 								num_times: 1,
-								total_value: exp.price,
+								total_value: value,
 							},
 						);
 					},
@@ -108,20 +122,56 @@ where
 
 	let mut vec_summary: Vec<(Vec<String>, Cell)> = summary.into_iter().collect();
 	vec_summary.sort_by(sort);
+	(vec_summary, num_unconverted)
+}
 
-	utils::display_history_summary(&vec_summary, title.to_string(), "".to_string());
+#[duplicate::duplicate_item(
+	method                      t         title            collect;
+	[history_expenses_concepts] [Expense] ["Expense type"] [collect_expenses_concepts];
+	[history_incomes_concepts]  [Income]  ["Income type"]  [collect_incomes_concepts];
+)]
+pub fn method<SortFunc, GroupByFunc>(
+	all_data: &AllActivities,
+	sort: SortFunc,
+	group_by: GroupByFunc,
+) where
+	SortFunc: Fn(&(Vec<String>, Cell), &(Vec<String>, Cell)) -> std::cmp::Ordering,
+	GroupByFunc: Fn(&t) -> Vec<String>,
+{
+	let rates = all_data.get_exchange_rates();
+	let (vec_summary, num_unconverted) = collect(all_data, |_| true, sort, group_by);
+
+	utils::display_history_summary(
+		&vec_summary,
+		title.to_string(),
+		"".to_string(),
+		rates.get_reporting_currency(),
+	);
+	utils::display_unconverted(num_unconverted, rates.get_reporting_currency());
 }
 
-fn history_expenses_shops<F>(all_data: &AllActivities, func: F)
+pub fn collect_expenses_shops<FilterFunc, F>(
+	all_data: &AllActivities,
+	filter: FilterFunc,
+	func: F,
+) -> (Vec<(Vec<String>, Cell)>, u32)
 where
+	FilterFunc: Fn(&Expense) -> bool,
 	F: Fn(&(Vec<String>, Cell), &(Vec<String>, Cell)) -> std::cmp::Ordering,
 {
+	let rates = all_data.get_exchange_rates();
+	let mut num_unconverted: u32 = 0;
+
 	let mut summary: std::collections::BTreeMap<Vec<String>, Cell> =
 		std::collections::BTreeMap::new();
 
 	for year in all_data.iter_activities() {
 		for month in year.iter_expenses() {
-			for exp in month.iter() {
+			for exp in month.iter().filter(|e| filter(e)) {
+				let Some(value) = rates.convert(exp.price, &exp.currency, &exp.day_of_year) else {
+					num_unconverted += 1;
+					continue;
+				};
 				match summary.get_mut(&vec![exp.shop.clone()]) {
 					Some(Cell {
 						classifier: _,
@@ -129,7 +179,7 @@ where
 						total_value,
 					}) => {
 						*num_times += 1;
-						*total_value += exp.price;
+						*total_value += value;
 					},
 					None => {
 						summary.insert(
@@ -137,7 +187,7 @@ where
 							Cell {
 								classifier: exp.city.clone(),
 								num_times: 1,
-								total_value: exp.price,
+								total_value: value,
 							},
 						);
 					},
@@ -148,8 +198,23 @@ where
 
 	let mut vec_summary: Vec<(Vec<String>, Cell)> = summary.into_iter().collect();
 	vec_summary.sort_by(func);
+	(vec_summary, num_unconverted)
+}
 
-	utils::display_history_summary(&vec_summary, "Place".to_string(), "City".to_string());
+pub fn history_expenses_shops<F>(all_data: &AllActivities, func: F)
+where
+	F: Fn(&(Vec<String>, Cell), &(Vec<String>, Cell)) -> std::cmp::Ordering,
+{
+	let rates = all_data.get_exchange_rates();
+	let (vec_summary, num_unconverted) = collect_expenses_shops(all_data, |_| true, func);
+
+	utils::display_history_summary(
+		&vec_summary,
+		"Place".to_string(),
+		"City".to_string(),
+		rates.get_reporting_currency(),
+	);
+	utils::display_unconverted(num_unconverted, rates.get_reporting_currency());
 }
 
 fn print_statistics_menu_expenses() {
@@ -163,6 +228,8 @@ fn print_statistics_menu_expenses() {
 	println!("    4.    Sorted alphabetically");
 	println!("    5.    Sorted by times");
 	println!("    6.    Sorted by value");
+	println!("    Comparison of two periods");
+	println!("    7.    By type");
 	println!("    0. Leave");
 }
 
@@ -196,6 +263,9 @@ pub fn menu_expenses(all_data: &AllActivities) {
 			5 => history_expenses_shops(&all_data, sort_by_times),
 			6 => history_expenses_shops(&all_data, sort_by_value),
 
+			// comparison
+			7 => menus::comparison::compare_expenses(all_data),
+
 			//
 			_ => println!("Nothing to do..."),
 		}
@@ -206,21 +276,28 @@ pub fn menu_expenses(all_data: &AllActivities) {
 
 // -----------------------------------------------------------------------------
 
-fn history_of_from_and_place_incomes<SortFunc, GroupByFunc>(
+pub fn collect_of_from_and_place_incomes<SortFunc, GroupByFunc>(
 	all_data: &AllActivities,
-	title: String,
 	func: SortFunc,
 	group_by: GroupByFunc,
-) where
+) -> (Vec<(Vec<String>, Cell)>, u32)
+where
 	SortFunc: Fn(&(Vec<String>, Cell), &(Vec<String>, Cell)) -> std::cmp::Ordering,
 	GroupByFunc: Fn(&Income) -> Vec<String>,
 {
+	let rates = all_data.get_exchange_rates();
+	let mut num_unconverted: u32 = 0;
+
 	let mut summary: std::collections::BTreeMap<Vec<String>, Cell> =
 		std::collections::BTreeMap::new();
 
 	for year in all_data.iter_activities() {
 		for month in year.iter_incomes() {
 			for inc in month.iter() {
+				let Some(value) = rates.convert(inc.price, &inc.currency, &inc.day_of_year) else {
+					num_unconverted += 1;
+					continue;
+				};
 				let key = group_by(inc);
 				match summary.get_mut(&key) {
 					Some(Cell {
@@ -229,7 +306,7 @@ fn history_of_from_and_place_incomes<SortFunc, GroupByFunc>(
 						total_value,
 					}) => {
 						*num_times += 1;
-						*total_value += inc.price;
+						*total_value += value;
 					},
 					None => {
 						summary.insert(
@@ -237,7 +314,7 @@ fn history_of_from_and_place_incomes<SortFunc, GroupByFunc>(
 							Cell {
 								classifier: "".to_string(),
 								num_times: 1,
-								total_value: inc.price,
+								total_value: value,
 							},
 						);
 					},
@@ -248,17 +325,38 @@ fn history_of_from_and_place_incomes<SortFunc, GroupByFunc>(
 
 	let mut vec_summary: Vec<(Vec<String>, Cell)> = summary.into_iter().collect();
 	vec_summary.sort_by(func);
+	(vec_summary, num_unconverted)
+}
 
-	utils::display_history_summary(&vec_summary, title, "".to_string());
+pub fn history_of_from_and_place_incomes<SortFunc, GroupByFunc>(
+	all_data: &AllActivities,
+	title: String,
+	func: SortFunc,
+	group_by: GroupByFunc,
+) where
+	SortFunc: Fn(&(Vec<String>, Cell), &(Vec<String>, Cell)) -> std::cmp::Ordering,
+	GroupByFunc: Fn(&Income) -> Vec<String>,
+{
+	let rates = all_data.get_exchange_rates();
+	let (vec_summary, num_unconverted) =
+		collect_of_from_and_place_incomes(all_data, func, group_by);
+
+	utils::display_history_summary(
+		&vec_summary,
+		title,
+		"".to_string(),
+		rates.get_reporting_currency(),
+	);
+	utils::display_unconverted(num_unconverted, rates.get_reporting_currency());
 }
 
-fn from(i: &Income) -> Vec<String> {
+pub fn from(i: &Income) -> Vec<String> {
 	vec![i.from.clone()]
 }
-fn place(i: &Income) -> Vec<String> {
+pub fn place(i: &Income) -> Vec<String> {
 	vec![i.place.clone()]
 }
-fn from_place(i: &Income) -> Vec<String> {
+pub fn from_place(i: &Income) -> Vec<String> {
 	vec![i.from.clone() + " - " + &i.place.clone()]
 }
 
@@ -281,6 +379,8 @@ fn print_statistics_menu_incomes() {
 	println!("   10.    Sorted alphabetically");
 	println!("   11.    Sorted by times");
 	println!("   12.    Sorted by value");
+	println!("    Comparison of two periods");
+	println!("   13.    By type");
 	println!("    0. Leave");
 }
 
@@ -369,6 +469,9 @@ pub fn menu_incomes(all_data: &AllActivities) {
 				from_place,
 			),
 
+			// comparison
+			13 => menus::comparison::compare_incomes(all_data),
+
 			//
 			_ => println!("Nothing to do..."),
 		}
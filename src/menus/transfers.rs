@@ -0,0 +1,281 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+
+use crate::economy;
+use crate::menus;
+use crate::time;
+
+type Transfer = economy::transfer::Transfer;
+type MonthlyActivities<T> = economy::monthly_activities::MonthlyActivities<T>;
+type AllActivities = economy::all_activities::AllActivities;
+type Money = economy::money::Money;
+
+static DATE_WIDTH: usize = 17;
+static PRICE_WIDTH: usize = 12;
+static ACCOUNT_WIDTH: usize = 4;
+
+fn display_transfers(month_data: &MonthlyActivities<Transfer>) {
+	let from_width = std::cmp::max(
+		ACCOUNT_WIDTH,
+		month_data
+			.iter()
+			.map(|t| t.from_account.chars().count())
+			.max()
+			.unwrap_or(0),
+	);
+	let to_width = std::cmp::max(
+		ACCOUNT_WIDTH,
+		month_data
+			.iter()
+			.map(|t| t.to_account.chars().count())
+			.max()
+			.unwrap_or(0),
+	);
+	let divider = format!(
+		"    +————+—{}—+—{}—+—{}—+—{}—+",
+		"—".repeat(DATE_WIDTH),
+		"—".repeat(PRICE_WIDTH),
+		"—".repeat(from_width),
+		"—".repeat(to_width)
+	);
+
+	println!("{divider}");
+	println!(
+		"    | ID | {:^DATE_WIDTH$} | {:^PRICE_WIDTH$} | {:^from_width$} | {:^to_width$} | Description",
+		"Date", "Amount", "From", "To"
+	);
+	println!("{divider}");
+	for (
+		i,
+		Transfer {
+			day_of_year: d,
+			price: pr,
+			currency: cur,
+			from_account: fr,
+			to_account: to,
+			description: descr,
+		},
+	) in month_data.iter().enumerate()
+	{
+		let date_text = d.to_string();
+		let price_text = format!("{} {cur}", pr.display_in(cur));
+		println!(
+			"    | {i:>2} | {date_text:^DATE_WIDTH$} | {price_text:>PRICE_WIDTH$} | {fr:^from_width$} | {to:^to_width$} | {descr}"
+		);
+	}
+	println!("{divider}");
+	println!();
+}
+
+fn print_all_transfers(all_data: &AllActivities) {
+	for year_data in all_data.iter_activities() {
+		if year_data.get_transfers().get_activities().is_empty() {
+			continue;
+		}
+
+		println!("Data from year: {}", year_data.get_year());
+		println!("--------------------");
+		for month_data in year_data.iter_transfers() {
+			display_transfers(month_data);
+		}
+	}
+}
+
+fn print_year_user_transfers(all_data: &AllActivities) {
+	println!("What year do you want to see?");
+	let year: u32 = io::read_int();
+
+	if let Some(year_data) = all_data.get_year(&year) {
+		for month_data in year_data.iter_transfers() {
+			display_transfers(month_data);
+		}
+	} else {
+		println!("Year '{year}' does not exist!");
+	}
+}
+
+fn print_month_user_transfers(all_data: &AllActivities) {
+	println!("What year and month do you want to see? Year -> Month");
+	let year: u32 = io::read_int();
+	if !all_data.has_year(&year) {
+		println!("Year '{year}' does not exist.");
+		return;
+	}
+
+	let Some(month) = time::io::read_correct_month() else {
+		return;
+	};
+
+	if let Some(month_data) = all_data.get_month_transfers(&year, &month) {
+		display_transfers(month_data);
+	} else {
+		println!("Month '{month}' does not exist in year '{year}'.");
+	}
+}
+
+fn add_new_with_date_transfer(
+	all_data: &mut AllActivities,
+	year: u32,
+	month: time::date::Month,
+	day: u8,
+) {
+	let Some(date) = time::date::Date::new(year, month.clone(), day) else {
+		println!("Day '{day}' does not exist in {month} {year}.");
+		return;
+	};
+
+	let accounts = all_data.get_accounts().get_names();
+	if accounts.len() < 2 {
+		println!("At least two accounts are needed to transfer money.");
+		return;
+	}
+
+	println!("From account ('?' to list them):");
+	let Some(from_account) = io::read_from_options_or_empty(&accounts) else {
+		return;
+	};
+	println!("To account ('?' to list them):");
+	let Some(to_account) = io::read_from_options_or_empty(&accounts) else {
+		return;
+	};
+	if from_account == to_account {
+		println!("The accounts of a transfer must be different.");
+		return;
+	}
+
+	println!("Amount:");
+	let price: Money = io::read_float();
+
+	let currency = menus::currencies::read_currency(all_data.get_exchange_rates());
+
+	println!("Description:");
+	let description = io::read_string_or_empty().unwrap_or("".to_string());
+
+	let year_data = all_data.add_year(year);
+	let month_data = year_data.get_transfers_mut().add(&month);
+
+	month_data.push(Transfer {
+		day_of_year: date,
+		price: price.round_to_currency(&currency),
+		currency,
+		from_account,
+		to_account,
+		description,
+	});
+}
+
+fn add_new_transfer(all_data: &mut AllActivities) {
+	println!("Year:");
+	let year: u32 = io::read_int();
+
+	println!("Month:");
+	let Some(month) = time::io::read_correct_month() else {
+		return;
+	};
+
+	println!("Day:");
+	let day: u8 = io::read_int();
+
+	add_new_with_date_transfer(all_data, year, month, day);
+}
+
+fn add_new_today_transfer(all_data: &mut AllActivities) {
+	let today = time::date::Date::today();
+	println!("Today is {}, {today}.", today.weekday());
+
+	add_new_with_date_transfer(all_data, today.year, today.month, today.day);
+}
+
+fn remove_transfer(all_data: &mut AllActivities) {
+	println!("Select year:");
+	let year: u32 = io::read_int();
+	if !all_data.has_year(&year) {
+		println!("Year '{year}' does not exist.");
+		return;
+	}
+
+	println!("Select month:");
+	let Some(month) = time::io::read_correct_month() else {
+		return;
+	};
+
+	let Some(month_data) = all_data.get_month_transfers(&year, &month) else {
+		println!("Month '{month}' does not exist");
+		return;
+	};
+	display_transfers(month_data);
+
+	println!("Id of transfer to be deleted.");
+	if let Some(id_transfer) = io::read_int_or_empty::<usize>() {
+		let year_data = all_data.add_year(year);
+		let month_data = year_data.get_transfers_mut().add(&month);
+
+		if id_transfer < month_data.size() {
+			month_data.remove(id_transfer);
+		}
+	}
+}
+
+fn print_transfers_menu() {
+	println!("Query and edit the transfers between accounts:");
+	println!();
+	println!("    1. Show all transfers");
+	println!("    2. Show transfers of a year");
+	println!("    3. Show transfers of a month");
+	println!("    4. Add another transfer");
+	println!("    5.     Add another transfer today");
+	println!("    6. Remove a transfer");
+	println!("    0. Leave");
+}
+
+pub fn menu_transfers(all_data: &mut AllActivities) {
+	let print_function = print_transfers_menu;
+	let min_option = 0;
+	let max_option = 6;
+
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => print_all_transfers(all_data),
+			2 => print_year_user_transfers(all_data),
+			3 => print_month_user_transfers(all_data),
+			4 => add_new_transfer(all_data),
+			5 => add_new_today_transfer(all_data),
+			6 => remove_transfer(all_data),
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+}
@@ -34,8 +34,10 @@ use crate::io;
 
 use crate::time::date;
 
+use crate::economy::exchange_rates::ExchangeRates;
 use crate::economy::expense;
 use crate::economy::income;
+use crate::economy::money::Money;
 use crate::economy::monthly_activities;
 use crate::economy::traits::AsReferences;
 use crate::economy::traits::HasConcepts;
@@ -53,6 +55,7 @@ static SHOP_WIDTH: usize = 4;
 static FROM_WIDTH: usize = 4;
 static CITY_WIDTH: usize = 4;
 static PRICE_WIDTH: usize = 8;
+static CURRENCY_WIDTH: usize = 3;
 static DATE_WIDTH: usize = 17;
 static PERCENTAGE_WIDTH: usize = 10;
 static CONCEPT_SEPARATOR: &str = " ; ";
@@ -150,7 +153,16 @@ where
 	widths
 }
 
-pub fn display_summary_activity(summary: &ActivitySummary, pre_tab: &str) {
+pub fn display_unconverted(num_unconverted: u32, currency: &String) {
+	if num_unconverted > 0 {
+		println!(
+			"    {num_unconverted} entries were left out: missing exchange rates into {currency}."
+		);
+		println!("");
+	}
+}
+
+pub fn display_summary_activity(summary: &ActivitySummary, pre_tab: &str, currency: &String) {
 	let concept_widths = summary.get_concepts_max_widths();
 	let concept_width: usize = std::cmp::max(
 		CONCEPT_WIDTH,
@@ -166,8 +178,10 @@ pub fn display_summary_activity(summary: &ActivitySummary, pre_tab: &str) {
 		.collect::<String>();
 	let concept_header = center_string(&"Concept".to_string(), concept_width);
 
-	let price_main_divider = std::iter::repeat("—").take(PRICE_WIDTH).collect::<String>();
-	let price_header = center_string(&"Price".to_string(), PRICE_WIDTH);
+	let price_title = format!("Price ({currency})");
+	let price_width = std::cmp::max(PRICE_WIDTH, price_title.chars().count());
+	let price_main_divider = std::iter::repeat("—").take(price_width).collect::<String>();
+	let price_header = center_string(&price_title, price_width);
 
 	let percentage_main_divider = std::iter::repeat("—")
 		.take(PERCENTAGE_WIDTH)
@@ -180,19 +194,19 @@ pub fn display_summary_activity(summary: &ActivitySummary, pre_tab: &str) {
 	println!("{tab}+—{concept_divider}—+—{price_main_divider}—+—{percentage_main_divider}—+");
 	for (v, value) in summary.iter_summary() {
 		println!(
-			"{tab}| {:<concept_width$} | {:>PRICE_WIDTH$.2} | {:>9.2}% |",
+			"{tab}| {:<concept_width$} | {:>price_width$} | {:>9.2}% |",
 			left_justified_columns_text(v, &concept_widths, CONCEPT_SEPARATOR, CONCEPT_WIDTH),
-			value,
-			(value / summary.get_total()) * 100.0
+			value.display_in(currency),
+			(value.to_f64() / summary.get_total().to_f64()) * 100.0
 		);
 	}
 
 	println!("{tab}+—{concept_divider}—+—{price_main_divider}—+—{percentage_main_divider}—+");
 	let total_spent_msg: String = "Total".to_string();
 	println!(
-		"{tab}| {:<concept_width$} | {:>PRICE_WIDTH$.2} |            |",
+		"{tab}| {:<concept_width$} | {:>price_width$} |            |",
 		total_spent_msg,
-		summary.get_total()
+		summary.get_total().display_in(currency)
 	);
 	println!("{tab}+—{concept_divider}—+—{price_main_divider}—+—{percentage_main_divider}—+");
 	println!("");
@@ -203,6 +217,7 @@ pub fn display_and_accounting_expenses<F>(
 	month_data: &MonthlyActivities<Expense>,
 	func: &F,
 	concept_depth: i32,
+	rates: &ExchangeRates,
 ) -> ActivitySummary
 where
 	F: Fn(&Expense) -> bool,
@@ -259,9 +274,21 @@ where
 		.collect::<String>();
 	let city_header = center_string(&"City".to_string(), city_column_width);
 
-	let price_main_divider = std::iter::repeat("—").take(PRICE_WIDTH).collect::<String>();
-	let price_mid_divider: String = std::iter::repeat("·").take(PRICE_WIDTH).collect::<String>();
-	let price_header = center_string(&"Price".to_string(), PRICE_WIDTH);
+	let price_width = PRICE_WIDTH + 1 + CURRENCY_WIDTH;
+	let price_main_divider = std::iter::repeat("—").take(price_width).collect::<String>();
+	let price_mid_divider: String = std::iter::repeat("·").take(price_width).collect::<String>();
+	let price_header = center_string(&"Price".to_string(), price_width);
+
+	let reporting_currency = rates.get_reporting_currency();
+	let converted_title = format!("In {reporting_currency}");
+	let converted_width = std::cmp::max(PRICE_WIDTH, converted_title.chars().count());
+	let converted_main_divider = std::iter::repeat("—")
+		.take(converted_width)
+		.collect::<String>();
+	let converted_mid_divider: String = std::iter::repeat("·")
+		.take(converted_width)
+		.collect::<String>();
+	let converted_header = center_string(&converted_title, converted_width);
 
 	let date_main_divider = std::iter::repeat("—").take(DATE_WIDTH).collect::<String>();
 	let date_mid_divider: String = std::iter::repeat("·").take(DATE_WIDTH).collect::<String>();
@@ -271,6 +298,7 @@ where
 
 	let mut first: bool = true;
 	let mut size_data: u32 = 0;
+	let mut num_unconverted: u32 = 0;
 	let mut previous_date: date::Date = date::Date {
 		year: 1900,
 		month: date::Month::January,
@@ -281,10 +309,14 @@ where
 		Expense {
 			day_of_year: d,
 			price: pr,
+			currency: cur,
+			account: _,
 			concepts: cs,
 			shop: pl,
 			city: ci,
 			description: descr,
+			transaction_id: _,
+			recurring: _,
 		},
 	) in month_data.iter().filter(|e| func(e)).enumerate()
 	{
@@ -296,7 +328,21 @@ where
 			cs.iter().take(concept_depth as usize).cloned().collect()
 		};
 
-		summary.add(concepts_to_summarize, *pr);
+		let converted = rates.convert(*pr, cur, d);
+		let converted_text = match converted {
+			Some(value) => {
+				summary.add(concepts_to_summarize, value);
+				format!("{:>converted_width$}", value.display_in(reporting_currency))
+			},
+			None => {
+				num_unconverted += 1;
+				format!("{:>converted_width$}", "n/a")
+			},
+		};
+		let price_text = format!(
+			"{:>PRICE_WIDTH$} {cur:<CURRENCY_WIDTH$}",
+			pr.display_in(cur)
+		);
 
 		let concept_text =
 			left_justified_columns_text(cs, &concept_widths, CONCEPT_SEPARATOR, CONCEPT_WIDTH);
@@ -304,29 +350,31 @@ where
 		let city_text = center_string(ci, city_column_width);
 		if &previous_date != d {
 			if first {
-				println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{concept_main_divider}—+—{shop_main_divider}—+—{city_main_divider}—+");
-				println!("    | ID | {date_header} | {price_header} | {concept_header} | {shop_header} | {city_header} | Description");
-				println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{concept_main_divider}—+—{shop_main_divider}—+—{city_main_divider}—+");
+				println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{converted_main_divider}—+—{concept_main_divider}—+—{shop_main_divider}—+—{city_main_divider}—+");
+				println!("    | ID | {date_header} | {price_header} | {converted_header} | {concept_header} | {shop_header} | {city_header} | Description");
+				println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{converted_main_divider}—+—{concept_main_divider}—+—{shop_main_divider}—+—{city_main_divider}—+");
 				first = false;
 			} else {
-				println!("    +————+—{date_mid_divider}—+—{price_mid_divider}—+—{concept_mid_divider}—+—{shop_mid_divider}—+—{city_mid_divider}—+");
+				println!("    +————+—{date_mid_divider}—+—{price_mid_divider}—+—{converted_mid_divider}—+—{concept_mid_divider}—+—{shop_mid_divider}—+—{city_mid_divider}—+");
 			}
 
 			let date_text = center_string(&d.to_string(), DATE_WIDTH);
-			println!("    | {i:>2} | {date_text} | {pr:>PRICE_WIDTH$.2} | {concept_text} | {place_text} | {city_text} | {descr}");
+			println!("    | {i:>2} | {date_text} | {price_text} | {converted_text} | {concept_text} | {place_text} | {city_text} | {descr}");
 			previous_date = d.clone();
 		} else {
 			let date_text = center_string(&" ".to_string(), DATE_WIDTH);
-			println!("    | {i:>2} | {date_text} | {pr:>PRICE_WIDTH$.2} | {concept_text} | {place_text} | {city_text} | {descr}");
+			println!("    | {i:>2} | {date_text} | {price_text} | {converted_text} | {concept_text} | {place_text} | {city_text} | {descr}");
 		}
 	}
 	if size_data > 0 {
-		println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{concept_main_divider}—+—{shop_main_divider}—+—{city_main_divider}—+");
+		println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{converted_main_divider}—+—{concept_main_divider}—+—{shop_main_divider}—+—{city_main_divider}—+");
 		println!("");
 	}
 
+	display_unconverted(num_unconverted, reporting_currency);
+
 	if size_data > 1 {
-		display_summary_activity(&summary, &"    ");
+		display_summary_activity(&summary, &"    ", reporting_currency);
 	}
 
 	summary
@@ -336,6 +384,7 @@ pub fn display_and_accounting_incomes<F>(
 	month_data: &MonthlyActivities<Income>,
 	func: &F,
 	concept_depth: i32,
+	rates: &ExchangeRates,
 ) -> ActivitySummary
 where
 	F: Fn(&Income) -> bool,
@@ -392,9 +441,21 @@ where
 		.collect::<String>();
 	let from_header = center_string(&"From".to_string(), from_column_width);
 
-	let price_main_divider = std::iter::repeat("—").take(PRICE_WIDTH).collect::<String>();
-	let price_mid_divider: String = std::iter::repeat("·").take(PRICE_WIDTH).collect::<String>();
-	let price_header = center_string(&"Price".to_string(), PRICE_WIDTH);
+	let price_width = PRICE_WIDTH + 1 + CURRENCY_WIDTH;
+	let price_main_divider = std::iter::repeat("—").take(price_width).collect::<String>();
+	let price_mid_divider: String = std::iter::repeat("·").take(price_width).collect::<String>();
+	let price_header = center_string(&"Price".to_string(), price_width);
+
+	let reporting_currency = rates.get_reporting_currency();
+	let converted_title = format!("In {reporting_currency}");
+	let converted_width = std::cmp::max(PRICE_WIDTH, converted_title.chars().count());
+	let converted_main_divider = std::iter::repeat("—")
+		.take(converted_width)
+		.collect::<String>();
+	let converted_mid_divider: String = std::iter::repeat("·")
+		.take(converted_width)
+		.collect::<String>();
+	let converted_header = center_string(&converted_title, converted_width);
 
 	let date_main_divider = std::iter::repeat("—").take(DATE_WIDTH).collect::<String>();
 	let date_mid_divider: String = std::iter::repeat("·").take(DATE_WIDTH).collect::<String>();
@@ -404,6 +465,7 @@ where
 
 	let mut first: bool = true;
 	let mut size_data: u32 = 0;
+	let mut num_unconverted: u32 = 0;
 	let mut previous_date: date::Date = date::Date {
 		year: 1900,
 		month: date::Month::January,
@@ -414,10 +476,14 @@ where
 		Income {
 			day_of_year: d,
 			price: pr,
+			currency: cur,
+			account: _,
 			concepts: cs,
 			from: fr,
 			place: pl,
 			description: descr,
+			transaction_id: _,
+			recurring: _,
 		},
 	) in month_data.iter().filter(|e| func(e)).enumerate()
 	{
@@ -429,7 +495,21 @@ where
 			cs.iter().take(concept_depth as usize).cloned().collect()
 		};
 
-		summary.add(concepts_to_summarize, *pr);
+		let converted = rates.convert(*pr, cur, d);
+		let converted_text = match converted {
+			Some(value) => {
+				summary.add(concepts_to_summarize, value);
+				format!("{:>converted_width$}", value.display_in(reporting_currency))
+			},
+			None => {
+				num_unconverted += 1;
+				format!("{:>converted_width$}", "n/a")
+			},
+		};
+		let price_text = format!(
+			"{:>PRICE_WIDTH$} {cur:<CURRENCY_WIDTH$}",
+			pr.display_in(cur)
+		);
 
 		let concept_text =
 			left_justified_columns_text(cs, &concept_widths, CONCEPT_SEPARATOR, CONCEPT_WIDTH);
@@ -437,29 +517,31 @@ where
 		let from_text = center_string(fr, from_column_width);
 		if &previous_date != d {
 			if first {
-				println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{concept_main_divider}—+—{place_main_divider}—+—{from_main_divider}—+");
-				println!("    | ID | {date_header} | {price_header} | {concept_header} | {place_header} | {from_header} | Description");
-				println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{concept_main_divider}—+—{place_main_divider}—+—{from_main_divider}—+");
+				println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{converted_main_divider}—+—{concept_main_divider}—+—{place_main_divider}—+—{from_main_divider}—+");
+				println!("    | ID | {date_header} | {price_header} | {converted_header} | {concept_header} | {place_header} | {from_header} | Description");
+				println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{converted_main_divider}—+—{concept_main_divider}—+—{place_main_divider}—+—{from_main_divider}—+");
 				first = false;
 			} else {
-				println!("    +————+—{date_mid_divider}—+—{price_mid_divider}—+—{concept_mid_divider}—+—{place_mid_divider}—+—{from_mid_divider}—+");
+				println!("    +————+—{date_mid_divider}—+—{price_mid_divider}—+—{converted_mid_divider}—+—{concept_mid_divider}—+—{place_mid_divider}—+—{from_mid_divider}—+");
 			}
 
 			let date_text = center_string(&d.to_string(), DATE_WIDTH);
-			println!("    | {i:>2} | {date_text} | {pr:>PRICE_WIDTH$.2} | {concept_text} | {place_text} | {from_text} | {descr}");
+			println!("    | {i:>2} | {date_text} | {price_text} | {converted_text} | {concept_text} | {place_text} | {from_text} | {descr}");
 			previous_date = d.clone();
 		} else {
 			let date_text = center_string(&" ".to_string(), DATE_WIDTH);
-			println!("    | {i:>2} | {date_text} | {pr:>PRICE_WIDTH$.2} | {concept_text} | {place_text} | {from_text} | {descr}");
+			println!("    | {i:>2} | {date_text} | {price_text} | {converted_text} | {concept_text} | {place_text} | {from_text} | {descr}");
 		}
 	}
 	if size_data > 0 {
-		println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{concept_main_divider}—+—{place_main_divider}—+—{from_main_divider}—+");
+		println!("    +————+—{date_main_divider}—+—{price_main_divider}—+—{converted_main_divider}—+—{concept_main_divider}—+—{place_main_divider}—+—{from_main_divider}—+");
 		println!("");
 	}
 
+	display_unconverted(num_unconverted, reporting_currency);
+
 	if size_data > 1 {
-		display_summary_activity(&summary, &"    ");
+		display_summary_activity(&summary, &"    ", reporting_currency);
 	}
 
 	summary
@@ -467,7 +549,7 @@ where
 
 pub struct Cell {
 	pub num_times: u32,
-	pub total_value: f32,
+	pub total_value: Money,
 	pub classifier: String,
 }
 
@@ -475,6 +557,7 @@ pub fn display_history_summary(
 	vec_summary: &Vec<(Vec<String>, Cell)>,
 	first_title: String,
 	second_title: String,
+	currency: &String,
 ) {
 	let number_subcolumns = vec_summary
 		.iter()
@@ -521,15 +604,17 @@ pub fn display_history_summary(
 		.collect::<String>();
 	let second_header = center_string(&second_title, second_column_width);
 
+	let total_header = center_string(&format!("Total spent ({currency})"), 17);
+
 	let tab = "    ";
 
 	if second_column_width > 0 {
 		println!("{tab}+—{first_main_divider}—+—{second_main_divider}—+—————————————+———————————————————+");
-		println!("{tab}| {first_header} | {second_header} | Times found | Total money spent |");
+		println!("{tab}| {first_header} | {second_header} | Times found | {total_header} |");
 		println!("{tab}+—{first_main_divider}—+—{second_main_divider}—+—————————————+———————————————————+");
 	} else {
 		println!("{tab}+—{first_main_divider}—+—————————————+———————————————————+");
-		println!("{tab}| {first_header} | Times found | Total money spent |");
+		println!("{tab}| {first_header} | Times found | {total_header} |");
 		println!("{tab}+—{first_main_divider}—+—————————————+———————————————————+");
 	}
 
@@ -544,11 +629,14 @@ pub fn display_history_summary(
 	{
 		let first_column_text =
 			left_justified_columns_text(things, &column_widths, CONCEPT_SEPARATOR, CONCEPT_WIDTH);
+		let total_value = total_value.display_in(currency);
 		if second_column_width > 0 {
 			let city_text = center_string(city, second_column_width);
-			println!("{tab}| {first_column_text} | {city_text} | {num_times:>11} | {total_value:>17.2} |");
+			println!(
+				"{tab}| {first_column_text} | {city_text} | {num_times:>11} | {total_value:>17} |"
+			);
 		} else {
-			println!("{tab}| {first_column_text} | {num_times:>11} | {total_value:>17.2} |");
+			println!("{tab}| {first_column_text} | {num_times:>11} | {total_value:>17} |");
 		}
 	}
 	if second_column_width > 0 {
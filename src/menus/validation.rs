@@ -0,0 +1,284 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use crate::io;
+
+use crate::economy;
+use crate::menus;
+use crate::time;
+
+type AllActivities = economy::all_activities::AllActivities;
+type Date = time::date::Date;
+type Expense = economy::expense::Expense;
+type Income = economy::income::Income;
+type Issue = economy::validation::Issue;
+type Money = economy::money::Money;
+type Month = time::date::Month;
+type Problem = economy::validation::Problem;
+type Transfer = economy::transfer::Transfer;
+
+fn display_issues(issues: &[Issue]) {
+	for (i, issue) in issues.iter().enumerate() {
+		println!("    {:>3}. {issue}", i + 1);
+	}
+	println!();
+}
+
+fn print_issues(all_data: &AllActivities) {
+	let issues = economy::validation::validate(all_data);
+	if issues.is_empty() {
+		println!("No problems were found.");
+		return;
+	}
+	println!("Found {} problems:", issues.len());
+	display_issues(&issues);
+}
+
+/* ------------------------------------------------------------------------- */
+
+#[derive(PartialEq)]
+enum Action {
+	Cancel,
+	Fix,
+	Remove,
+	Move,
+}
+
+fn read_action(issue: &Issue) -> Action {
+	let misfiled = matches!(issue.problem, Problem::MisfiledDate(_));
+	let print_function = || {
+		println!("What do you want to do?");
+		println!();
+		if misfiled {
+			println!(
+				"    1. Change the date to a day of {} {}",
+				issue.month, issue.year
+			);
+		} else {
+			println!("    1. Fix the problem");
+		}
+		println!("    2. Remove the activity");
+		if misfiled {
+			println!("    3. Move the activity to the month of its date");
+		}
+		println!("    0. Cancel");
+	};
+	let max_option = if misfiled { 3 } else { 2 };
+
+	match menus::utils::read_option(print_function, 0, max_option) {
+		1 => Action::Fix,
+		2 => Action::Remove,
+		3 => Action::Move,
+		_ => Action::Cancel,
+	}
+}
+
+// Reads a day of the given month until it exists.
+fn read_day(year: u32, month: &Month) -> Date {
+	println!("New day:");
+	loop {
+		let day: u8 = io::read_int();
+		match Date::new(year, month.clone(), day) {
+			Some(date) => return date,
+			None => println!("Day {day} does not exist in {month} {year}, please enter another."),
+		}
+	}
+}
+
+// Fixes the problems that all activities share. Returns whether the problem
+// was one of them.
+fn fix_common(issue: &Issue, date: &mut Date, price: &mut Money, currency: &str) -> bool {
+	match issue.problem {
+		Problem::InvalidDate(_) => {
+			*date = read_day(date.year, &date.month);
+		},
+		Problem::MisfiledDate(_) => {
+			*date = read_day(issue.year, &issue.month);
+		},
+		Problem::NegativePrice(_) => {
+			println!("New price:");
+			*price = io::read_float::<Money>().round_to_currency(currency);
+		},
+		_ => return false,
+	}
+	true
+}
+
+#[duplicate::duplicate_item(
+	method               activity  get_activities     concept_types;
+	[fix_expense_issue]  [Expense] [get_expenses_mut] [get_expense_concepts];
+	[fix_income_issue]   [Income]  [get_incomes_mut]  [get_income_concepts];
+)]
+fn method(all_data: &mut AllActivities, issue: &Issue, action: Action) {
+	// take the activity out of the month it is filed in
+	let year_data = all_data.get_year_mut(&issue.year).unwrap();
+	let month_data = year_data
+		.get_activities()
+		.get_month_mut(&issue.month)
+		.unwrap();
+	let mut a: activity = month_data.get(issue.index).clone();
+	month_data.remove(issue.index);
+
+	if action == Action::Remove {
+		return;
+	}
+
+	let (year, month) = if action == Action::Move {
+		(a.day_of_year.year, a.day_of_year.month.clone())
+	} else {
+		(issue.year, issue.month.clone())
+	};
+
+	if action == Action::Fix && !fix_common(issue, &mut a.day_of_year, &mut a.price, &a.currency) {
+		match issue.problem {
+			Problem::UnknownConcepts(_) => {
+				println!("New concepts:");
+				a.concepts = io::read_from_tree_options(all_data.concept_types().get_tree());
+			},
+			Problem::UnknownAccount(_) => {
+				println!("New account:");
+				a.account = menus::accounts::read_account(all_data.get_accounts());
+			},
+			_ => {},
+		}
+	}
+
+	all_data.add_year(year).get_activities().add(&month).push(a);
+}
+
+fn fix_transfer_issue(all_data: &mut AllActivities, issue: &Issue, action: Action) {
+	// take the transfer out of the month it is filed in
+	let year_data = all_data.get_year_mut(&issue.year).unwrap();
+	let month_data = year_data
+		.get_transfers_mut()
+		.get_month_mut(&issue.month)
+		.unwrap();
+	let mut t: Transfer = month_data.get(issue.index).clone();
+	month_data.remove(issue.index);
+
+	if action == Action::Remove {
+		return;
+	}
+
+	let (year, month): (u32, Month) = if action == Action::Move {
+		(t.day_of_year.year, t.day_of_year.month.clone())
+	} else {
+		(issue.year, issue.month.clone())
+	};
+
+	if action == Action::Fix && !fix_common(issue, &mut t.day_of_year, &mut t.price, &t.currency) {
+		match &issue.problem {
+			Problem::UnknownAccount(account) => {
+				println!("Replace account '{account}' with:");
+				let new_account = menus::accounts::read_account(all_data.get_accounts());
+				if t.from_account == *account {
+					t.from_account = new_account.clone();
+				}
+				if t.to_account == *account {
+					t.to_account = new_account;
+				}
+			},
+			Problem::SameAccounts(_) => {
+				println!("New destination account:");
+				t.to_account = menus::accounts::read_account(all_data.get_accounts());
+			},
+			_ => {},
+		}
+	}
+
+	all_data
+		.add_year(year)
+		.get_transfers_mut()
+		.add(&month)
+		.push(t);
+}
+
+fn fix_issue(all_data: &mut AllActivities) {
+	let issues = economy::validation::validate(all_data);
+	if issues.is_empty() {
+		println!("No problems were found.");
+		return;
+	}
+	display_issues(&issues);
+
+	println!("Problem to be fixed (leave blank to cancel):");
+	let Some(idx) = io::read_int_or_empty::<usize>() else {
+		return;
+	};
+	if idx == 0 || idx > issues.len() {
+		println!("Problem '{idx}' does not exist.");
+		return;
+	}
+	let issue = &issues[idx - 1];
+
+	let action = read_action(issue);
+	if action == Action::Cancel {
+		return;
+	}
+	if action == Action::Remove {
+		println!("Remove the activity? (yes/no)");
+		if !io::read_yes_no() {
+			return;
+		}
+	}
+
+	match issue.kind {
+		economy::validation::ActivityKind::Expense => fix_expense_issue(all_data, issue, action),
+		economy::validation::ActivityKind::Income => fix_income_issue(all_data, issue, action),
+		economy::validation::ActivityKind::Transfer => fix_transfer_issue(all_data, issue, action),
+	}
+}
+
+fn print_validation_menu() {
+	println!("Check the consistency of the data:");
+	println!();
+	println!("    1. Show all problems");
+	println!("    2. Fix a problem");
+	println!("    0. Leave");
+}
+
+pub fn menu_validation(all_data: &mut AllActivities) {
+	let print_function = print_validation_menu;
+	let min_option = 0;
+	let max_option = 2;
+
+	let mut option = menus::utils::read_option(print_function, min_option, max_option);
+	while option != 0 {
+		match option {
+			1 => print_issues(all_data),
+			2 => fix_issue(all_data),
+			_ => println!("Nothing to do..."),
+		}
+
+		option = menus::utils::read_option(print_function, min_option, max_option);
+	}
+}
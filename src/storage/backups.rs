@@ -0,0 +1,148 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::io::{Result, Write};
+use std::path::{Path, PathBuf};
+
+// Number of previous versions kept of every file.
+pub const NUM_BACKUPS: usize = 5;
+
+static BACKUP_DIR: &str = "backups";
+
+/**
+ * @brief A previous version of a data file.
+ *
+ * Version 1 is the most recent one.
+ */
+#[derive(Debug)]
+pub struct Backup {
+	pub file: String,
+	pub version: usize,
+	pub modified: std::time::SystemTime,
+}
+
+fn backup_path(data_dir: &str, file: &str, version: usize) -> PathBuf {
+	PathBuf::from(format!("{data_dir}{BACKUP_DIR}/{file}.{version}"))
+}
+
+// Keeps a copy of the current version of 'file', a path relative to
+// 'data_dir', discarding the oldest copy when there are too many.
+pub fn backup_file(data_dir: &str, file: &str) -> Result<()> {
+	let live = PathBuf::from(data_dir.to_owned() + file);
+	if !live.exists() {
+		return Ok(());
+	}
+
+	let newest = backup_path(data_dir, file, 1);
+	if let Some(dir) = newest.parent() {
+		std::fs::create_dir_all(dir)?;
+	}
+
+	let oldest = backup_path(data_dir, file, NUM_BACKUPS);
+	if oldest.exists() {
+		std::fs::remove_file(oldest)?;
+	}
+	for version in (1..NUM_BACKUPS).rev() {
+		let path = backup_path(data_dir, file, version);
+		if path.exists() {
+			std::fs::rename(path, backup_path(data_dir, file, version + 1))?;
+		}
+	}
+	std::fs::copy(live, newest)?;
+	Ok(())
+}
+
+// Replaces the contents of 'file', a path relative to 'data_dir'. The new
+// contents are written into a temporary file first so that the current
+// version is never left half-written.
+pub fn write_file(data_dir: &str, file: &str, contents: &[u8]) -> Result<()> {
+	backup_file(data_dir, file)?;
+
+	let live = PathBuf::from(data_dir.to_owned() + file);
+	let temporary = PathBuf::from(data_dir.to_owned() + file + ".tmp");
+	{
+		let mut f = std::fs::File::create(&temporary)?;
+		f.write_all(contents)?;
+		f.sync_all()?;
+	}
+	std::fs::rename(temporary, live)
+}
+
+fn list_backups_rec(data_dir: &str, dir: &Path, backups: &mut Vec<Backup>) -> Result<()> {
+	for entry in std::fs::read_dir(dir)? {
+		let entry = entry?;
+		let path = entry.path();
+		if path.is_dir() {
+			list_backups_rec(data_dir, &path, backups)?;
+			continue;
+		}
+
+		let backup_dir = PathBuf::from(data_dir.to_owned() + BACKUP_DIR);
+		let relative = path
+			.strip_prefix(&backup_dir)
+			.unwrap()
+			.to_string_lossy()
+			.to_string();
+		let Some((file, version)) = relative.rsplit_once('.') else {
+			continue;
+		};
+		let Ok(version) = version.parse::<usize>() else {
+			continue;
+		};
+
+		backups.push(Backup {
+			file: file.to_string(),
+			version,
+			modified: entry.metadata()?.modified()?,
+		});
+	}
+	Ok(())
+}
+
+// All the backups in 'data_dir', sorted by file and then from newest to
+// oldest.
+pub fn list_backups(data_dir: &str) -> Result<Vec<Backup>> {
+	let mut backups = Vec::new();
+	let backup_dir = PathBuf::from(data_dir.to_owned() + BACKUP_DIR);
+	if backup_dir.exists() {
+		list_backups_rec(data_dir, &backup_dir, &mut backups)?;
+	}
+	backups.sort_by(|a, b| a.file.cmp(&b.file).then(a.version.cmp(&b.version)));
+	Ok(backups)
+}
+
+// Puts back the contents of a backup. The version being replaced is backed up
+// as well, so that restoring can be undone.
+pub fn restore_backup(data_dir: &str, backup: &Backup) -> Result<()> {
+	let contents = std::fs::read(backup_path(data_dir, &backup.file, backup.version))?;
+	write_file(data_dir, &backup.file, &contents)
+}
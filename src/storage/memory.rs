@@ -0,0 +1,229 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::io::Result;
+
+use crate::economy::accounts::Account;
+use crate::economy::all_activities::AllActivities;
+use crate::economy::budgets::Budget;
+use crate::economy::expense::Expense;
+use crate::economy::income::Income;
+use crate::economy::recurring::RecurringTemplate;
+use crate::economy::savings_goals::SavingsGoal;
+use crate::economy::transfer::Transfer;
+
+use crate::concepts;
+
+use crate::storage::Storage;
+
+use crate::time::date::Date;
+
+/**
+ * @brief Data kept in memory only.
+ *
+ * Nothing is persisted: the data is lost when the storage is dropped.
+ */
+pub struct MemoryStorage {
+	m_expenses: Vec<Expense>,
+	m_incomes: Vec<Income>,
+	m_transfers: Vec<Transfer>,
+	m_accounts: Vec<Account>,
+	m_budgets: Vec<Budget>,
+	m_savings_goals: Vec<SavingsGoal>,
+	m_recurring_templates: Vec<RecurringTemplate>,
+	m_exchange_rates: Vec<(String, String, Date, f64)>,
+	m_expense_types: String,
+	m_income_types: String,
+}
+
+impl MemoryStorage {
+	pub fn new() -> MemoryStorage {
+		MemoryStorage {
+			m_expenses: Vec::new(),
+			m_incomes: Vec::new(),
+			m_transfers: Vec::new(),
+			m_accounts: Vec::new(),
+			m_budgets: Vec::new(),
+			m_savings_goals: Vec::new(),
+			m_recurring_templates: Vec::new(),
+			m_exchange_rates: Vec::new(),
+			m_expense_types: String::new(),
+			m_income_types: String::new(),
+		}
+	}
+}
+
+impl Storage for MemoryStorage {
+	fn list_years(&self) -> Result<Vec<u32>> {
+		let mut years: Vec<u32> = self
+			.m_expenses
+			.iter()
+			.map(|e| e.day_of_year.year)
+			.chain(self.m_incomes.iter().map(|i| i.day_of_year.year))
+			.chain(self.m_transfers.iter().map(|t| t.day_of_year.year))
+			.collect();
+		years.sort();
+		years.dedup();
+		Ok(years)
+	}
+
+	fn load_activities(&self, home_currency: &str) -> Result<AllActivities> {
+		let mut all_data = AllActivities::new();
+		all_data
+			.get_exchange_rates_mut()
+			.set_home_currency(home_currency.to_string());
+
+		for e in self.m_expenses.iter() {
+			let month = e.day_of_year.month.clone();
+			all_data
+				.add_year(e.day_of_year.year)
+				.get_expenses_mut()
+				.add(&month)
+				.push(e.clone());
+		}
+		for i in self.m_incomes.iter() {
+			let month = i.day_of_year.month.clone();
+			all_data
+				.add_year(i.day_of_year.year)
+				.get_incomes_mut()
+				.add(&month)
+				.push(i.clone());
+		}
+		for t in self.m_transfers.iter() {
+			let month = t.day_of_year.month.clone();
+			all_data
+				.add_year(t.day_of_year.year)
+				.get_transfers_mut()
+				.add(&month)
+				.push(t.clone());
+		}
+
+		let rates = all_data.get_exchange_rates_mut();
+		for (from, to, date, rate) in self.m_exchange_rates.iter() {
+			rates.add_rate(from.clone(), to.clone(), date.clone(), *rate);
+		}
+
+		let accounts = all_data.get_accounts_mut();
+		for a in self.m_accounts.iter() {
+			accounts.add(a.clone());
+		}
+
+		let budgets = all_data.get_budgets_mut();
+		for b in self.m_budgets.iter() {
+			budgets.add(b.clone());
+		}
+
+		let goals = all_data.get_savings_goals_mut();
+		for g in self.m_savings_goals.iter() {
+			goals.add(g.clone());
+		}
+
+		let templates = all_data.get_recurring_templates_mut();
+		for t in self.m_recurring_templates.iter() {
+			templates.add(t.clone());
+		}
+
+		all_data.set_changes(false);
+		Ok(all_data)
+	}
+
+	fn load_concept_types(&self, all_data: &mut AllActivities) -> Result<()> {
+		if !self.m_expense_types.is_empty() {
+			let tree = concepts::io::parse_tree(&self.m_expense_types);
+			all_data.get_expense_concepts_mut().set_tree(tree);
+		}
+		if !self.m_income_types.is_empty() {
+			let tree = concepts::io::parse_tree(&self.m_income_types);
+			all_data.get_income_concepts_mut().set_tree(tree);
+		}
+
+		all_data.set_changes_concepts(false);
+		Ok(())
+	}
+
+	fn save_activities(&mut self, all_data: &AllActivities) -> Result<()> {
+		for ye in all_data.iter_activities() {
+			let year = *ye.get_year();
+
+			if ye.get_expenses().has_changes() {
+				self.m_expenses.retain(|e| e.day_of_year.year != year);
+				for me in ye.iter_expenses() {
+					self.m_expenses.extend(me.iter().cloned());
+				}
+			}
+			if ye.get_incomes().has_changes() {
+				self.m_incomes.retain(|i| i.day_of_year.year != year);
+				for mi in ye.iter_incomes() {
+					self.m_incomes.extend(mi.iter().cloned());
+				}
+			}
+			if ye.get_transfers().has_changes() {
+				self.m_transfers.retain(|t| t.day_of_year.year != year);
+				for mt in ye.iter_transfers() {
+					self.m_transfers.extend(mt.iter().cloned());
+				}
+			}
+		}
+
+		if all_data.get_accounts().has_changes() {
+			self.m_accounts = all_data.get_accounts().iter().cloned().collect();
+		}
+		if all_data.get_budgets().has_changes() {
+			self.m_budgets = all_data.get_budgets().iter().cloned().collect();
+		}
+		if all_data.get_savings_goals().has_changes() {
+			self.m_savings_goals = all_data.get_savings_goals().iter().cloned().collect();
+		}
+		if all_data.get_recurring_templates().has_changes() {
+			self.m_recurring_templates =
+				all_data.get_recurring_templates().iter().cloned().collect();
+		}
+		let rates = all_data.get_exchange_rates();
+		if rates.has_changes() {
+			self.m_exchange_rates = rates
+				.iter_rates()
+				.map(|(from, to, date, rate)| (from.clone(), to.clone(), date.clone(), rate))
+				.collect();
+		}
+		Ok(())
+	}
+
+	fn save_concept_types(&mut self, all_data: &AllActivities) -> Result<()> {
+		if all_data.get_expense_concepts().has_changes() {
+			self.m_expense_types = all_data.get_expense_concepts().get_tree().to_string();
+		}
+		if all_data.get_income_concepts().has_changes() {
+			self.m_income_types = all_data.get_income_concepts().get_tree().to_string();
+		}
+		Ok(())
+	}
+}
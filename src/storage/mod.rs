@@ -0,0 +1,86 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::io::Result;
+
+use crate::economy::all_activities::AllActivities;
+
+pub mod backups;
+pub mod memory;
+pub mod sqlite;
+pub mod text;
+
+/**
+ * @brief Backend where the data of a project is kept.
+ *
+ * Activities comprise expenses, incomes and transfers together with the
+ * accounts and the exchange rates. Saving only writes the data that has
+ * changes.
+ */
+pub trait Storage {
+	// Years for which some activity is stored, sorted increasingly.
+	fn list_years(&self) -> Result<Vec<u32>>;
+
+	fn load_activities(&self, home_currency: &str) -> Result<AllActivities>;
+	fn load_concept_types(&self, all_data: &mut AllActivities) -> Result<()>;
+
+	fn save_activities(&mut self, all_data: &AllActivities) -> Result<()>;
+	fn save_concept_types(&mut self, all_data: &AllActivities) -> Result<()>;
+
+	fn load_all(&self, home_currency: &str) -> Result<AllActivities> {
+		let mut all_data = self.load_activities(home_currency)?;
+		self.load_concept_types(&mut all_data)?;
+		Ok(all_data)
+	}
+
+	fn save_all(&mut self, all_data: &AllActivities) -> Result<()> {
+		self.save_activities(all_data)?;
+		self.save_concept_types(all_data)
+	}
+}
+
+// Copies all the data kept in 'from' into 'to'.
+pub fn migrate(
+	from: &dyn Storage,
+	to: &mut dyn Storage,
+	home_currency: &str,
+) -> Result<AllActivities> {
+	let years = from.list_years()?;
+	eprintln!("Migrating the data of {} years...", years.len());
+
+	let mut all_data = from.load_all(home_currency)?;
+	all_data.set_changes(true);
+	to.save_all(&all_data)?;
+
+	all_data.set_changes(false);
+	Ok(all_data)
+}
@@ -0,0 +1,888 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use rusqlite::types::Type;
+use rusqlite::{params, Connection, Result, Row, Transaction};
+
+use crate::economy::accounts::Account;
+use crate::economy::budgets::{Budget, BudgetPeriod};
+use crate::economy::all_activities::AllActivities;
+use crate::economy::expense::Expense;
+use crate::economy::income::Income;
+use crate::economy::money::Money;
+use crate::economy::recurring::{self, Frequency, RecurringKind, RecurringTemplate};
+use crate::economy::savings_goals::SavingsGoal;
+use crate::economy::transfer::Transfer;
+
+use crate::concepts;
+use crate::concepts::types::ConceptTypes;
+
+use crate::storage::backups;
+use crate::storage::Storage;
+
+use crate::time::date::{Date, Month};
+
+static SCHEMA_VERSION: &str = "4";
+
+static SCHEMA: &str = "
+	CREATE TABLE IF NOT EXISTS metadata (
+		key TEXT PRIMARY KEY,
+		value TEXT NOT NULL
+	);
+	CREATE TABLE IF NOT EXISTS expenses (
+		id INTEGER PRIMARY KEY,
+		year INTEGER NOT NULL,
+		month INTEGER NOT NULL,
+		day INTEGER NOT NULL,
+		price INTEGER NOT NULL,
+		currency TEXT NOT NULL,
+		concepts TEXT NOT NULL,
+		shop TEXT NOT NULL,
+		city TEXT NOT NULL,
+		description TEXT NOT NULL,
+		account TEXT NOT NULL,
+		transaction_id TEXT NOT NULL DEFAULT '',
+		recurring TEXT NOT NULL DEFAULT ''
+	);
+	CREATE TABLE IF NOT EXISTS incomes (
+		id INTEGER PRIMARY KEY,
+		year INTEGER NOT NULL,
+		month INTEGER NOT NULL,
+		day INTEGER NOT NULL,
+		price INTEGER NOT NULL,
+		currency TEXT NOT NULL,
+		concepts TEXT NOT NULL,
+		from_ TEXT NOT NULL,
+		place TEXT NOT NULL,
+		description TEXT NOT NULL,
+		account TEXT NOT NULL,
+		transaction_id TEXT NOT NULL DEFAULT '',
+		recurring TEXT NOT NULL DEFAULT ''
+	);
+	CREATE TABLE IF NOT EXISTS transfers (
+		id INTEGER PRIMARY KEY,
+		year INTEGER NOT NULL,
+		month INTEGER NOT NULL,
+		day INTEGER NOT NULL,
+		price INTEGER NOT NULL,
+		currency TEXT NOT NULL,
+		from_account TEXT NOT NULL,
+		to_account TEXT NOT NULL,
+		description TEXT NOT NULL
+	);
+	CREATE TABLE IF NOT EXISTS concept_types (
+		kind TEXT PRIMARY KEY,
+		tree TEXT NOT NULL
+	);
+	CREATE TABLE IF NOT EXISTS accounts (
+		name TEXT PRIMARY KEY,
+		opening_balance INTEGER NOT NULL,
+		currency TEXT NOT NULL
+	);
+	CREATE TABLE IF NOT EXISTS budgets (
+		concepts TEXT NOT NULL,
+		period TEXT NOT NULL,
+		amount INTEGER NOT NULL,
+		currency TEXT NOT NULL,
+		PRIMARY KEY (concepts, period)
+	);
+	CREATE TABLE IF NOT EXISTS savings_goals (
+		name TEXT PRIMARY KEY,
+		target INTEGER NOT NULL,
+		currency TEXT NOT NULL,
+		start_year INTEGER NOT NULL,
+		start_month INTEGER NOT NULL,
+		start_day INTEGER NOT NULL,
+		deadline_year INTEGER NOT NULL,
+		deadline_month INTEGER NOT NULL,
+		deadline_day INTEGER NOT NULL,
+		expense_concepts TEXT NOT NULL,
+		income_concepts TEXT NOT NULL,
+		account TEXT NOT NULL
+	);
+	CREATE TABLE IF NOT EXISTS recurring_activities (
+		name TEXT PRIMARY KEY,
+		kind TEXT NOT NULL,
+		frequency TEXT NOT NULL,
+		start_year INTEGER NOT NULL,
+		start_month INTEGER NOT NULL,
+		start_day INTEGER NOT NULL,
+		end_year INTEGER,
+		end_month INTEGER,
+		end_day INTEGER,
+		amounts TEXT NOT NULL,
+		currency TEXT NOT NULL,
+		account TEXT NOT NULL,
+		concepts TEXT NOT NULL,
+		counterpart TEXT NOT NULL,
+		place TEXT NOT NULL,
+		description TEXT NOT NULL,
+		generated_year INTEGER,
+		generated_month INTEGER,
+		generated_day INTEGER
+	);
+	CREATE TABLE IF NOT EXISTS exchange_rates (
+		year INTEGER NOT NULL,
+		month INTEGER NOT NULL,
+		day INTEGER NOT NULL,
+		from_currency TEXT NOT NULL,
+		to_currency TEXT NOT NULL,
+		rate REAL NOT NULL
+	);
+";
+
+// Brings databases created by older versions up to the current schema.
+fn upgrade_schema(conn: &Connection) -> Result<()> {
+	let version: String = conn.query_row(
+		"SELECT value FROM metadata WHERE key = 'schema_version'",
+		[],
+		|row| row.get(0),
+	)?;
+	if version == "1" {
+		conn.execute_batch(
+			"ALTER TABLE expenses ADD COLUMN transaction_id TEXT NOT NULL DEFAULT '';
+			ALTER TABLE incomes ADD COLUMN transaction_id TEXT NOT NULL DEFAULT '';",
+		)?;
+	}
+	if version == "1" || version == "2" {
+		conn.execute_batch(
+			"ALTER TABLE expenses ADD COLUMN recurring TEXT NOT NULL DEFAULT '';
+			ALTER TABLE incomes ADD COLUMN recurring TEXT NOT NULL DEFAULT '';",
+		)?;
+	}
+	if version == "1" || version == "2" || version == "3" {
+		// amounts were stored in hundredths, they are now in thousandths
+		conn.execute_batch(
+			"UPDATE expenses SET price = price * 10;
+			UPDATE incomes SET price = price * 10;
+			UPDATE transfers SET price = price * 10;
+			UPDATE accounts SET opening_balance = opening_balance * 10;
+			UPDATE budgets SET amount = amount * 10;
+			UPDATE savings_goals SET target = target * 10;",
+		)?;
+	}
+	conn.execute(
+		"UPDATE metadata SET value = ?1 WHERE key = 'schema_version'",
+		params![SCHEMA_VERSION],
+	)?;
+	Ok(())
+}
+
+fn open(db_file: &String) -> Result<Connection> {
+	let conn = Connection::open(db_file)?;
+	conn.execute_batch(SCHEMA)?;
+	conn.execute(
+		"INSERT OR IGNORE INTO metadata (key, value) VALUES ('schema_version', ?1)",
+		params![SCHEMA_VERSION],
+	)?;
+	upgrade_schema(&conn)?;
+	Ok(conn)
+}
+
+// Error for a value of the given column that does not stand for what the
+// column keeps.
+fn invalid_value(column: usize, value_type: Type, reason: String) -> rusqlite::Error {
+	rusqlite::Error::FromSqlConversionFailure(column, value_type, reason.into())
+}
+
+// Reads a date kept as its year, month and day in three consecutive columns
+// from the given one. As in the text files, days that do not exist in their
+// month are read, so that the validation of the data can report them.
+fn get_date(row: &Row, first_column: usize) -> Result<Date> {
+	let month: u32 = row.get(first_column + 1)?;
+	Ok(Date {
+		year: row.get(first_column)?,
+		month: Month::from_u32(month).ok_or_else(|| {
+			invalid_value(
+				first_column + 1,
+				Type::Integer,
+				format!("'{month}' is not a valid month"),
+			)
+		})?,
+		day: row.get(first_column + 2)?,
+	})
+}
+
+// Reads a date of the savings goals or the recurring activities, which must
+// exist.
+fn get_valid_date(row: &Row, first_column: usize) -> Result<Date> {
+	let date = get_date(row, first_column)?;
+	if !date.is_valid() {
+		return Err(invalid_value(
+			first_column + 2,
+			Type::Integer,
+			format!(
+				"day {} does not exist in {} {}",
+				date.day, date.month, date.year
+			),
+		));
+	}
+	Ok(date)
+}
+
+fn get_optional_date(row: &Row, first_column: usize) -> Result<Option<Date>> {
+	let year: Option<u32> = row.get(first_column)?;
+	if year.is_none() {
+		return Ok(None);
+	}
+	get_valid_date(row, first_column).map(Some)
+}
+
+fn split_concepts(concept_list: String) -> Vec<String> {
+	concept_list
+		.split_terminator(';')
+		.map(str::trim)
+		.filter(|s| !s.is_empty())
+		.map(|s| s.to_string())
+		.collect()
+}
+
+/* ------------------------------------------------------------------------- */
+
+fn read_expenses(conn: &Connection, all_data: &mut AllActivities) -> Result<()> {
+	let mut stmt = conn.prepare(
+		"SELECT year, month, day, price, currency, concepts, shop, city, description, account,
+		transaction_id, recurring FROM expenses ORDER BY year, month, day, id",
+	)?;
+	let rows = stmt.query_map([], |row| {
+		Ok(Expense {
+			day_of_year: get_date(row, 0)?,
+			price: Money::from_units(row.get(3)?),
+			currency: row.get(4)?,
+			concepts: split_concepts(row.get(5)?),
+			shop: row.get(6)?,
+			city: row.get(7)?,
+			description: row.get(8)?,
+			account: row.get(9)?,
+			transaction_id: row.get(10)?,
+			recurring: row.get(11)?,
+		})
+	})?;
+
+	for row in rows {
+		let e = row?;
+		let month = e.day_of_year.month.clone();
+		all_data
+			.add_year(e.day_of_year.year)
+			.get_expenses_mut()
+			.add(&month)
+			.push(e);
+	}
+	Ok(())
+}
+
+fn read_incomes(conn: &Connection, all_data: &mut AllActivities) -> Result<()> {
+	let mut stmt = conn.prepare(
+		"SELECT year, month, day, price, currency, concepts, from_, place, description, account,
+		transaction_id, recurring FROM incomes ORDER BY year, month, day, id",
+	)?;
+	let rows = stmt.query_map([], |row| {
+		Ok(Income {
+			day_of_year: get_date(row, 0)?,
+			price: Money::from_units(row.get(3)?),
+			currency: row.get(4)?,
+			concepts: split_concepts(row.get(5)?),
+			from: row.get(6)?,
+			place: row.get(7)?,
+			description: row.get(8)?,
+			account: row.get(9)?,
+			transaction_id: row.get(10)?,
+			recurring: row.get(11)?,
+		})
+	})?;
+
+	for row in rows {
+		let i = row?;
+		let month = i.day_of_year.month.clone();
+		all_data
+			.add_year(i.day_of_year.year)
+			.get_incomes_mut()
+			.add(&month)
+			.push(i);
+	}
+	Ok(())
+}
+
+fn read_transfers(conn: &Connection, all_data: &mut AllActivities) -> Result<()> {
+	let mut stmt = conn.prepare(
+		"SELECT year, month, day, price, currency, from_account, to_account, description
+		FROM transfers ORDER BY year, month, day, id",
+	)?;
+	let rows = stmt.query_map([], |row| {
+		Ok(Transfer {
+			day_of_year: get_date(row, 0)?,
+			price: Money::from_units(row.get(3)?),
+			currency: row.get(4)?,
+			from_account: row.get(5)?,
+			to_account: row.get(6)?,
+			description: row.get(7)?,
+		})
+	})?;
+
+	for row in rows {
+		let t = row?;
+		let month = t.day_of_year.month.clone();
+		all_data
+			.add_year(t.day_of_year.year)
+			.get_transfers_mut()
+			.add(&month)
+			.push(t);
+	}
+	Ok(())
+}
+
+fn read_concept_types(
+	conn: &Connection,
+	kind: &str,
+	concept_types: &mut ConceptTypes,
+) -> Result<()> {
+	let mut stmt = conn.prepare("SELECT tree FROM concept_types WHERE kind = ?1")?;
+	let mut rows = stmt.query(params![kind])?;
+	if let Some(row) = rows.next()? {
+		let text: String = row.get(0)?;
+		concept_types.set_tree(concepts::io::parse_tree(&text));
+	}
+	Ok(())
+}
+
+fn read_accounts(conn: &Connection, all_data: &mut AllActivities) -> Result<()> {
+	let mut stmt = conn.prepare("SELECT name, opening_balance, currency FROM accounts")?;
+	let rows = stmt.query_map([], |row| {
+		Ok(Account {
+			name: row.get(0)?,
+			opening_balance: Money::from_units(row.get(1)?),
+			currency: row.get(2)?,
+		})
+	})?;
+
+	let accounts = all_data.get_accounts_mut();
+	for row in rows {
+		accounts.add(row?);
+	}
+	Ok(())
+}
+
+fn read_budgets(conn: &Connection, all_data: &mut AllActivities) -> Result<()> {
+	let mut stmt = conn.prepare("SELECT concepts, period, amount, currency FROM budgets")?;
+	let rows = stmt.query_map([], |row| {
+		let period: String = row.get(1)?;
+		Ok(Budget {
+			concepts: split_concepts(row.get(0)?),
+			period: period.parse::<BudgetPeriod>().map_err(|_| {
+				invalid_value(
+					1,
+					Type::Text,
+					format!("'{period}' is not 'monthly' nor 'yearly'"),
+				)
+			})?,
+			amount: Money::from_units(row.get(2)?),
+			currency: row.get(3)?,
+		})
+	})?;
+
+	let budgets = all_data.get_budgets_mut();
+	for row in rows {
+		budgets.add(row?);
+	}
+	Ok(())
+}
+
+fn read_savings_goals(conn: &Connection, all_data: &mut AllActivities) -> Result<()> {
+	let mut stmt = conn.prepare(
+		"SELECT name, target, currency, start_year, start_month, start_day, deadline_year, deadline_month, deadline_day, expense_concepts, income_concepts, account FROM savings_goals",
+	)?;
+	let rows = stmt.query_map([], |row| {
+		Ok(SavingsGoal {
+			name: row.get(0)?,
+			target: Money::from_units(row.get(1)?),
+			currency: row.get(2)?,
+			start: get_valid_date(row, 3)?,
+			deadline: get_valid_date(row, 6)?,
+			expense_concepts: split_concepts(row.get(9)?),
+			income_concepts: split_concepts(row.get(10)?),
+			account: row.get(11)?,
+		})
+	})?;
+
+	let goals = all_data.get_savings_goals_mut();
+	for row in rows {
+		goals.add(row?);
+	}
+	Ok(())
+}
+
+fn read_recurring_templates(conn: &Connection, all_data: &mut AllActivities) -> Result<()> {
+	let mut stmt = conn.prepare(
+		"SELECT name, kind, frequency, start_year, start_month, start_day, end_year, end_month, end_day, amounts, currency, account, concepts, counterpart, place, description, generated_year, generated_month, generated_day FROM recurring_activities",
+	)?;
+	let rows = stmt.query_map([], |row| {
+		let kind: String = row.get(1)?;
+		let frequency: String = row.get(2)?;
+		let amounts: String = row.get(9)?;
+		let currency: String = row.get(10)?;
+		Ok(RecurringTemplate {
+			name: row.get(0)?,
+			kind: kind.parse::<RecurringKind>().map_err(|_| {
+				invalid_value(
+					1,
+					Type::Text,
+					format!("'{kind}' is not 'expense' nor 'income'"),
+				)
+			})?,
+			frequency: frequency.parse::<Frequency>().map_err(|_| {
+				invalid_value(
+					2,
+					Type::Text,
+					format!("'{frequency}' is not a valid frequency"),
+				)
+			})?,
+			start: get_valid_date(row, 3)?,
+			end: get_optional_date(row, 6)?,
+			amounts: recurring::parse_amounts(&amounts, &currency)
+				.map_err(|reason| invalid_value(9, Type::Text, reason))?,
+			currency,
+			account: row.get(11)?,
+			concepts: split_concepts(row.get(12)?),
+			counterpart: row.get(13)?,
+			place: row.get(14)?,
+			description: row.get(15)?,
+			generated_until: get_optional_date(row, 16)?,
+		})
+	})?;
+
+	let templates = all_data.get_recurring_templates_mut();
+	for row in rows {
+		templates.add(row?);
+	}
+	Ok(())
+}
+
+fn read_exchange_rates(conn: &Connection, all_data: &mut AllActivities) -> Result<()> {
+	let mut stmt = conn
+		.prepare("SELECT year, month, day, from_currency, to_currency, rate FROM exchange_rates")?;
+	let mut rows = stmt.query([])?;
+
+	let rates = all_data.get_exchange_rates_mut();
+	while let Some(row) = rows.next()? {
+		let date = get_date(row, 0)?;
+		rates.add_rate(row.get(3)?, row.get(4)?, date, row.get(5)?);
+	}
+	Ok(())
+}
+
+/* ------------------------------------------------------------------------- */
+
+// Years whose rows are written again: those with changes and those of the
+// dates of their activities, which may have been filed under another year.
+// All the activities dated in them are written, whatever year they are filed
+// under, so that no row is deleted without being inserted again.
+#[duplicate::duplicate_item(
+	method                     get_collection   iter_months;
+	[expense_years_to_write]   [get_expenses]   [iter_expenses];
+	[income_years_to_write]    [get_incomes]    [iter_incomes];
+	[transfer_years_to_write]  [get_transfers]  [iter_transfers];
+)]
+fn method(all_data: &AllActivities) -> Vec<u32> {
+	let mut years = Vec::new();
+	for ye in all_data.iter_activities() {
+		if !ye.get_collection().has_changes() {
+			continue;
+		}
+		years.push(*ye.get_year());
+		for month_data in ye.iter_months() {
+			years.extend(month_data.iter().map(|a| a.day_of_year.year));
+		}
+	}
+	years.sort();
+	years.dedup();
+	years
+}
+
+fn write_activities(tx: &Transaction, all_data: &AllActivities) -> Result<()> {
+	let years = expense_years_to_write(all_data);
+	if !years.is_empty() {
+		for year in years.iter() {
+			eprintln!("Writing expenses of year '{year}'...");
+			tx.execute("DELETE FROM expenses WHERE year = ?1", params![year])?;
+		}
+		let mut stmt = tx.prepare(
+			"INSERT INTO expenses
+			(year, month, day, price, currency, concepts, shop, city, description, account,
+			transaction_id, recurring)
+			VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+		)?;
+		for ye in all_data.iter_activities() {
+			for me in ye.iter_expenses() {
+				for e in me.iter().filter(|e| years.contains(&e.day_of_year.year)) {
+					stmt.execute(params![
+						e.day_of_year.year,
+						e.day_of_year.month.clone() as u32,
+						e.day_of_year.day,
+						e.price.get_units(),
+						e.currency,
+						e.concepts.join(";"),
+						e.shop,
+						e.city,
+						e.description,
+						e.account,
+						e.transaction_id,
+						e.recurring,
+					])?;
+				}
+			}
+		}
+	}
+
+	let years = income_years_to_write(all_data);
+	if !years.is_empty() {
+		for year in years.iter() {
+			eprintln!("Writing incomes of year '{year}'...");
+			tx.execute("DELETE FROM incomes WHERE year = ?1", params![year])?;
+		}
+		let mut stmt = tx.prepare(
+			"INSERT INTO incomes
+			(year, month, day, price, currency, concepts, from_, place, description, account,
+			transaction_id, recurring)
+			VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+		)?;
+		for ye in all_data.iter_activities() {
+			for mi in ye.iter_incomes() {
+				for i in mi.iter().filter(|i| years.contains(&i.day_of_year.year)) {
+					stmt.execute(params![
+						i.day_of_year.year,
+						i.day_of_year.month.clone() as u32,
+						i.day_of_year.day,
+						i.price.get_units(),
+						i.currency,
+						i.concepts.join(";"),
+						i.from,
+						i.place,
+						i.description,
+						i.account,
+						i.transaction_id,
+						i.recurring,
+					])?;
+				}
+			}
+		}
+	}
+
+	let years = transfer_years_to_write(all_data);
+	if !years.is_empty() {
+		for year in years.iter() {
+			eprintln!("Writing transfers of year '{year}'...");
+			tx.execute("DELETE FROM transfers WHERE year = ?1", params![year])?;
+		}
+		let mut stmt = tx.prepare(
+			"INSERT INTO transfers
+			(year, month, day, price, currency, from_account, to_account, description)
+			VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+		)?;
+		for ye in all_data.iter_activities() {
+			for mt in ye.iter_transfers() {
+				for t in mt.iter().filter(|t| years.contains(&t.day_of_year.year)) {
+					stmt.execute(params![
+						t.day_of_year.year,
+						t.day_of_year.month.clone() as u32,
+						t.day_of_year.day,
+						t.price.get_units(),
+						t.currency,
+						t.from_account,
+						t.to_account,
+						t.description,
+					])?;
+				}
+			}
+		}
+	}
+	Ok(())
+}
+
+fn write_concept_types(tx: &Transaction, kind: &str, concept_types: &ConceptTypes) -> Result<()> {
+	eprintln!("Writing {kind} types...");
+	tx.execute(
+		"INSERT OR REPLACE INTO concept_types (kind, tree) VALUES (?1, ?2)",
+		params![kind, concept_types.get_tree().to_string()],
+	)?;
+	Ok(())
+}
+
+fn write_accounts(tx: &Transaction, all_data: &AllActivities) -> Result<()> {
+	eprintln!("Writing accounts...");
+	tx.execute("DELETE FROM accounts", [])?;
+	let mut stmt =
+		tx.prepare("INSERT INTO accounts (name, opening_balance, currency) VALUES (?1, ?2, ?3)")?;
+	for a in all_data.get_accounts().iter() {
+		stmt.execute(params![a.name, a.opening_balance.get_units(), a.currency])?;
+	}
+	Ok(())
+}
+
+fn write_budgets(tx: &Transaction, all_data: &AllActivities) -> Result<()> {
+	eprintln!("Writing budgets...");
+	tx.execute("DELETE FROM budgets", [])?;
+	let mut stmt = tx.prepare(
+		"INSERT INTO budgets (concepts, period, amount, currency) VALUES (?1, ?2, ?3, ?4)",
+	)?;
+	for b in all_data.get_budgets().iter() {
+		stmt.execute(params![
+			b.concepts.join(";"),
+			b.period.to_string(),
+			b.amount.get_units(),
+			b.currency
+		])?;
+	}
+	Ok(())
+}
+
+fn write_savings_goals(tx: &Transaction, all_data: &AllActivities) -> Result<()> {
+	eprintln!("Writing savings goals...");
+	tx.execute("DELETE FROM savings_goals", [])?;
+	let mut stmt = tx.prepare(
+		"INSERT INTO savings_goals (name, target, currency, start_year, start_month, start_day, deadline_year, deadline_month, deadline_day, expense_concepts, income_concepts, account) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+	)?;
+	for g in all_data.get_savings_goals().iter() {
+		stmt.execute(params![
+			g.name,
+			g.target.get_units(),
+			g.currency,
+			g.start.year,
+			g.start.month.clone() as u32,
+			g.start.day,
+			g.deadline.year,
+			g.deadline.month.clone() as u32,
+			g.deadline.day,
+			g.expense_concepts.join(";"),
+			g.income_concepts.join(";"),
+			g.account
+		])?;
+	}
+	Ok(())
+}
+
+fn write_recurring_templates(tx: &Transaction, all_data: &AllActivities) -> Result<()> {
+	eprintln!("Writing recurring activities...");
+	tx.execute("DELETE FROM recurring_activities", [])?;
+	let mut stmt = tx.prepare(
+		"INSERT INTO recurring_activities (name, kind, frequency, start_year, start_month, start_day, end_year, end_month, end_day, amounts, currency, account, concepts, counterpart, place, description, generated_year, generated_month, generated_day) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, ?18, ?19)",
+	)?;
+	for t in all_data.get_recurring_templates().iter() {
+		let end = t.end.as_ref();
+		let generated = t.generated_until.as_ref();
+		stmt.execute(params![
+			t.name,
+			t.kind.to_string(),
+			t.frequency.to_string(),
+			t.start.year,
+			t.start.month.clone() as u32,
+			t.start.day,
+			end.map(|d| d.year),
+			end.map(|d| d.month.clone() as u32),
+			end.map(|d| d.day),
+			t.amounts_to_string(),
+			t.currency,
+			t.account,
+			t.concepts.join(";"),
+			t.counterpart,
+			t.place,
+			t.description,
+			generated.map(|d| d.year),
+			generated.map(|d| d.month.clone() as u32),
+			generated.map(|d| d.day)
+		])?;
+	}
+	Ok(())
+}
+
+fn write_exchange_rates(tx: &Transaction, all_data: &AllActivities) -> Result<()> {
+	eprintln!("Writing exchange rates...");
+	tx.execute("DELETE FROM exchange_rates", [])?;
+	let mut stmt = tx.prepare(
+		"INSERT INTO exchange_rates (year, month, day, from_currency, to_currency, rate)
+		VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+	)?;
+	for (from, to, date, rate) in all_data.get_exchange_rates().iter_rates() {
+		stmt.execute(params![
+			date.year,
+			date.month.clone() as u32,
+			date.day,
+			from,
+			to,
+			rate
+		])?;
+	}
+	Ok(())
+}
+
+fn to_io_error(e: rusqlite::Error) -> std::io::Error {
+	std::io::Error::other(e)
+}
+
+/**
+ * @brief Data kept in a single SQLite database file.
+ */
+pub struct SqliteStorage {
+	m_data_dir: String,
+	m_db_name: String,
+	m_db_file: String,
+}
+
+impl SqliteStorage {
+	pub fn new(data_dir: String, db_name: String) -> SqliteStorage {
+		SqliteStorage {
+			m_db_file: data_dir.clone() + &db_name,
+			m_data_dir: data_dir,
+			m_db_name: db_name,
+		}
+	}
+
+	fn read_activities(&self, home_currency: &str) -> Result<AllActivities> {
+		let conn = open(&self.m_db_file)?;
+
+		let mut all_data = AllActivities::new();
+		all_data
+			.get_exchange_rates_mut()
+			.set_home_currency(home_currency.to_string());
+
+		eprintln!("Reading data from database '{}'...", self.m_db_file);
+		eprintln!("    Reading activities data...");
+		read_expenses(&conn, &mut all_data)?;
+		read_incomes(&conn, &mut all_data)?;
+		read_transfers(&conn, &mut all_data)?;
+
+		eprintln!("    Reading exchange rates...");
+		read_exchange_rates(&conn, &mut all_data)?;
+		eprintln!("    Reading accounts...");
+		read_accounts(&conn, &mut all_data)?;
+		eprintln!("    Reading budgets...");
+		read_budgets(&conn, &mut all_data)?;
+		eprintln!("    Reading savings goals...");
+		read_savings_goals(&conn, &mut all_data)?;
+		eprintln!("    Reading recurring activities...");
+		read_recurring_templates(&conn, &mut all_data)?;
+
+		all_data.set_changes(false);
+		Ok(all_data)
+	}
+
+	fn read_concept_types(&self, all_data: &mut AllActivities) -> Result<()> {
+		let conn = open(&self.m_db_file)?;
+
+		eprintln!("    Reading expense types...");
+		read_concept_types(&conn, "expense", all_data.get_expense_concepts_mut())?;
+		eprintln!("    Reading income types...");
+		read_concept_types(&conn, "income", all_data.get_income_concepts_mut())?;
+
+		all_data.set_changes_concepts(false);
+		Ok(())
+	}
+
+	fn write_activities(&self, all_data: &AllActivities) -> Result<()> {
+		let mut conn = open(&self.m_db_file)?;
+		let tx = conn.transaction()?;
+
+		write_activities(&tx, all_data)?;
+		if all_data.get_accounts().has_changes() {
+			write_accounts(&tx, all_data)?;
+		}
+		if all_data.get_budgets().has_changes() {
+			write_budgets(&tx, all_data)?;
+		}
+		if all_data.get_savings_goals().has_changes() {
+			write_savings_goals(&tx, all_data)?;
+		}
+		if all_data.get_recurring_templates().has_changes() {
+			write_recurring_templates(&tx, all_data)?;
+		}
+		if all_data.get_exchange_rates().has_changes() {
+			write_exchange_rates(&tx, all_data)?;
+		}
+
+		tx.commit()
+	}
+
+	fn write_concept_types(&self, all_data: &AllActivities) -> Result<()> {
+		let mut conn = open(&self.m_db_file)?;
+		let tx = conn.transaction()?;
+
+		if all_data.get_expense_concepts().has_changes() {
+			write_concept_types(&tx, "expense", all_data.get_expense_concepts())?;
+		}
+		if all_data.get_income_concepts().has_changes() {
+			write_concept_types(&tx, "income", all_data.get_income_concepts())?;
+		}
+
+		tx.commit()
+	}
+}
+
+impl Storage for SqliteStorage {
+	fn list_years(&self) -> std::io::Result<Vec<u32>> {
+		let conn = open(&self.m_db_file).map_err(to_io_error)?;
+		let mut stmt = conn
+			.prepare(
+				"SELECT year FROM expenses UNION SELECT year FROM incomes
+				UNION SELECT year FROM transfers ORDER BY year",
+			)
+			.map_err(to_io_error)?;
+		let years = stmt
+			.query_map([], |row| row.get(0))
+			.map_err(to_io_error)?
+			.collect::<Result<Vec<u32>>>()
+			.map_err(to_io_error)?;
+		Ok(years)
+	}
+
+	fn load_activities(&self, home_currency: &str) -> std::io::Result<AllActivities> {
+		self.read_activities(home_currency).map_err(to_io_error)
+	}
+
+	fn load_concept_types(&self, all_data: &mut AllActivities) -> std::io::Result<()> {
+		self.read_concept_types(all_data).map_err(to_io_error)
+	}
+
+	fn save_activities(&mut self, all_data: &AllActivities) -> std::io::Result<()> {
+		self.write_activities(all_data).map_err(to_io_error)
+	}
+
+	fn save_concept_types(&mut self, all_data: &AllActivities) -> std::io::Result<()> {
+		self.write_concept_types(all_data).map_err(to_io_error)
+	}
+
+	fn save_all(&mut self, all_data: &AllActivities) -> std::io::Result<()> {
+		if all_data.has_changes() {
+			backups::backup_file(&self.m_data_dir, &self.m_db_name)?;
+		}
+		self.save_activities(all_data)?;
+		self.save_concept_types(all_data)
+	}
+}
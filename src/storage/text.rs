@@ -0,0 +1,209 @@
+/*********************************************************************
+ *
+ * Finances Manager -- A command line utility to manage domestic financial
+ * activities.
+ *
+ * Copyright (C) 2024
+ *
+ * This file is part of Finances manager. The full code is available at:
+ *      https://github.com/lluisalemanypuig/finances_manager.git
+ *
+ * Finances Manager is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU Affero General Public License as published
+ * by the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * Finances Manager is distributed in the hope that it will be useful, but
+ * WITHOUT ANY WARRANTY; without even the implied warranty of MERCHANTABILITY
+ * or FITNESS FOR A PARTICULAR PURPOSE. See the GNU Affero General Public
+ * License for more details.
+ *
+ * You should have received a copy of the GNU Affero General Public License
+ * along with Finances Manager.  If not, see <http://www.gnu.org/licenses/>.
+ *
+ * Contact:
+ *
+ *     Lluís Alemany Puig
+ *         email: lluis.alemany.puig@gmail.com
+ *         https://github.com/lluisalemanypuig
+ *         lluisalemanypuig.github.io
+ *
+ ********************************************************************/
+
+use std::io::{Error, ErrorKind, Result, Write};
+
+use crate::economy::all_activities::AllActivities;
+use crate::economy::io::BadLine;
+
+use crate::concepts;
+use crate::economy;
+
+use crate::storage::Storage;
+
+// File where the lines that could not be read are copied in lenient mode.
+static QUARANTINE_FILE: &str = "quarantine.txt";
+
+/**
+ * @brief Data kept in tab-separated text files.
+ *
+ * Every year of expenses, incomes and transfers has its own file within the
+ * directories 'expenses', 'incomes' and 'transfers' of the data directory.
+ */
+pub struct TextStorage {
+	m_data_dir: String,
+	m_lenient: bool,
+}
+
+impl TextStorage {
+	pub fn new(data_dir: String) -> TextStorage {
+		TextStorage {
+			m_data_dir: data_dir,
+			m_lenient: false,
+		}
+	}
+
+	// In lenient mode, lines that can't be read are skipped and copied into
+	// the quarantine file instead of aborting the loading of the data.
+	pub fn set_lenient(&mut self, l: bool) {
+		self.m_lenient = l;
+	}
+
+	pub fn has_data(&self) -> bool {
+		std::path::Path::new(&(self.m_data_dir.clone() + "expense_types.txt")).exists()
+	}
+}
+
+impl TextStorage {
+	fn handle_bad_lines(&self, bad_lines: &[BadLine]) -> Result<()> {
+		for bad_line in bad_lines {
+			eprintln!("    {bad_line}");
+		}
+		if !self.m_lenient {
+			return Err(Error::new(
+				ErrorKind::InvalidData,
+				format!(
+					"{} lines or files of the data could not be read",
+					bad_lines.len()
+				),
+			));
+		}
+
+		// whole files that could not be read are left where they are
+		let num_files = bad_lines.iter().filter(|b| b.line_number == 0).count();
+		if num_files > 0 {
+			eprintln!("    Skipped {num_files} files");
+		}
+		let bad_lines: Vec<&BadLine> = bad_lines.iter().filter(|b| b.line_number > 0).collect();
+		if bad_lines.is_empty() {
+			return Ok(());
+		}
+
+		let filename = self.m_data_dir.clone() + QUARANTINE_FILE;
+		eprintln!(
+			"    Skipped {} lines, copied into '{filename}'",
+			bad_lines.len()
+		);
+
+		// lines still in their files were already copied the last time
+		let quarantined = std::fs::read_to_string(&filename).unwrap_or_default();
+		let mut file = std::fs::OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(&filename)?;
+		for bad_line in bad_lines.iter() {
+			let entry = format!("# {bad_line}\n{}\n", bad_line.line);
+			if quarantined.contains(&entry) {
+				continue;
+			}
+			writeln!(file, "# {bad_line}")?;
+			writeln!(file, "{}", bad_line.line)?;
+		}
+
+		// the files are not written again unless their data changes, in which
+		// case the skipped lines are lost from them
+		let mut files: Vec<&str> = bad_lines.iter().map(|b| b.file.as_str()).collect();
+		files.dedup();
+		eprintln!(
+			"    These files are left as they are, but the next time their data is saved they"
+		);
+		eprintln!("    will be written without the skipped lines:");
+		for file in files {
+			eprintln!("        {file}");
+		}
+		Ok(())
+	}
+}
+
+impl Storage for TextStorage {
+	fn list_years(&self) -> Result<Vec<u32>> {
+		let mut years = Vec::new();
+		for dir in ["expenses", "incomes", "transfers"] {
+			let path = std::path::PathBuf::from(self.m_data_dir.clone() + dir);
+			if !path.exists() {
+				continue;
+			}
+
+			for entry in std::fs::read_dir(path)? {
+				let file_name = entry?.file_name();
+				let year = file_name
+					.to_str()
+					.and_then(|f| f.strip_suffix(".txt"))
+					.and_then(|f| f.parse::<u32>().ok());
+				if let Some(y) = year {
+					years.push(y);
+				}
+			}
+		}
+		years.sort();
+		years.dedup();
+		Ok(years)
+	}
+
+	fn load_activities(&self, home_currency: &str) -> Result<AllActivities> {
+		let data_dir = &self.m_data_dir;
+		eprintln!("Reading data from directory '{data_dir}'...");
+		eprintln!("    Reading activities data...");
+		let mut bad_lines = Vec::new();
+		let mut all_data =
+			economy::io::read_all_activities_data(data_dir, home_currency, &mut bad_lines)?;
+
+		eprintln!("    Reading exchange rates...");
+		economy::io::read_exchange_rates(data_dir, &mut all_data, &mut bad_lines)?;
+
+		eprintln!("    Reading accounts...");
+		economy::io::read_accounts(data_dir, &mut all_data, &mut bad_lines)?;
+
+		eprintln!("    Reading budgets...");
+		economy::io::read_budgets(data_dir, &mut all_data, &mut bad_lines)?;
+
+		eprintln!("    Reading savings goals...");
+		economy::io::read_savings_goals(data_dir, &mut all_data, &mut bad_lines)?;
+
+		eprintln!("    Reading recurring activities...");
+		economy::io::read_recurring_templates(data_dir, &mut all_data, &mut bad_lines)?;
+
+		all_data.set_changes(false);
+		if !bad_lines.is_empty() {
+			self.handle_bad_lines(&bad_lines)?;
+		}
+		Ok(all_data)
+	}
+
+	fn load_concept_types(&self, all_data: &mut AllActivities) -> Result<()> {
+		eprintln!("    Reading expense types...");
+		concepts::io::read_expense_types(&self.m_data_dir, all_data);
+		eprintln!("    Reading income types...");
+		concepts::io::read_income_types(&self.m_data_dir, all_data);
+
+		all_data.set_changes_concepts(false);
+		Ok(())
+	}
+
+	fn save_activities(&mut self, all_data: &AllActivities) -> Result<()> {
+		economy::io::write_all_data(&self.m_data_dir, all_data)
+	}
+
+	fn save_concept_types(&mut self, all_data: &AllActivities) -> Result<()> {
+		concepts::io::write_all_data(&self.m_data_dir, all_data)
+	}
+}
@@ -31,8 +31,15 @@
  ********************************************************************/
 
 use std::fmt;
+use std::ops::{Add, Sub};
 use std::str::FromStr;
 
+use chrono::{Datelike, Months, TimeDelta};
+
+pub fn is_leap_year(year: u32) -> bool {
+	(year.is_multiple_of(4) && !year.is_multiple_of(100)) || year.is_multiple_of(400)
+}
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 pub enum Month {
 	January = 0,
@@ -68,6 +75,20 @@ impl Month {
 		}
 	}
 
+	pub fn num_days(&self, year: u32) -> u8 {
+		match self {
+			Month::February => {
+				if is_leap_year(year) {
+					29
+				} else {
+					28
+				}
+			},
+			Month::April | Month::June | Month::September | Month::November => 30,
+			_ => 31,
+		}
+	}
+
 	pub fn next(&self) -> Month {
 		let m = self.clone() as u32;
 		match Month::from_u32(m + 1) {
@@ -99,6 +120,12 @@ impl fmt::Display for Month {
 #[derive(Debug, PartialEq, Eq)]
 pub struct ParseMonthError;
 
+impl fmt::Display for ParseMonthError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "not a valid month")
+	}
+}
+
 impl FromStr for Month {
 	type Err = ParseMonthError;
 
@@ -123,6 +150,47 @@ impl FromStr for Month {
 
 /* ------------------------------------------------------------------------- */
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Weekday {
+	Monday = 0,
+	Tuesday,
+	Wednesday,
+	Thursday,
+	Friday,
+	Saturday,
+	Sunday,
+}
+
+impl From<chrono::Weekday> for Weekday {
+	fn from(item: chrono::Weekday) -> Self {
+		match item {
+			chrono::Weekday::Mon => Weekday::Monday,
+			chrono::Weekday::Tue => Weekday::Tuesday,
+			chrono::Weekday::Wed => Weekday::Wednesday,
+			chrono::Weekday::Thu => Weekday::Thursday,
+			chrono::Weekday::Fri => Weekday::Friday,
+			chrono::Weekday::Sat => Weekday::Saturday,
+			chrono::Weekday::Sun => Weekday::Sunday,
+		}
+	}
+}
+
+impl fmt::Display for Weekday {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Weekday::Monday => write!(f, "Monday"),
+			Weekday::Tuesday => write!(f, "Tuesday"),
+			Weekday::Wednesday => write!(f, "Wednesday"),
+			Weekday::Thursday => write!(f, "Thursday"),
+			Weekday::Friday => write!(f, "Friday"),
+			Weekday::Saturday => write!(f, "Saturday"),
+			Weekday::Sunday => write!(f, "Sunday"),
+		}
+	}
+}
+
+/* ------------------------------------------------------------------------- */
+
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Date {
 	// It is crucial that these fields are declared in this exact order,
@@ -143,29 +211,215 @@ impl From<(u8, Month, u32)> for Date {
 	}
 }
 
+impl From<chrono::NaiveDate> for Date {
+	fn from(item: chrono::NaiveDate) -> Self {
+		Date {
+			year: item.year() as u32,
+			month: Month::from_u32(item.month0()).expect("Month of a chrono date is always valid"),
+			day: item.day() as u8,
+		}
+	}
+}
+
+impl Date {
+	// Returns None if the day does not exist in the month of the year.
+	pub fn new(year: u32, month: Month, day: u8) -> Option<Date> {
+		let date = Date { year, month, day };
+		if date.is_valid() {
+			Some(date)
+		} else {
+			None
+		}
+	}
+
+	pub fn today() -> Date {
+		chrono::Local::now().date_naive().into()
+	}
+
+	pub fn is_valid(&self) -> bool {
+		1 <= self.day && self.day <= self.month.num_days(self.year)
+	}
+
+	pub fn to_naive_date(&self) -> Option<chrono::NaiveDate> {
+		chrono::NaiveDate::from_ymd_opt(
+			self.year as i32,
+			self.month.clone() as u32 + 1,
+			self.day as u32,
+		)
+	}
+
+	fn to_naive_date_expect(&self) -> chrono::NaiveDate {
+		self.to_naive_date()
+			.unwrap_or_else(|| panic!("Date '{self}' does not exist"))
+	}
+
+	pub fn weekday(&self) -> Weekday {
+		self.to_naive_date_expect().weekday().into()
+	}
+}
+
+// Adds days or weeks, e.g. 'date + TimeDelta::weeks(2)'.
+impl Add<TimeDelta> for Date {
+	type Output = Date;
+
+	fn add(self, delta: TimeDelta) -> Date {
+		(self.to_naive_date_expect() + delta).into()
+	}
+}
+
+impl Sub<TimeDelta> for Date {
+	type Output = Date;
+
+	fn sub(self, delta: TimeDelta) -> Date {
+		(self.to_naive_date_expect() - delta).into()
+	}
+}
+
+// Adds months. The day is clamped to the last day of the resulting month.
+impl Add<Months> for Date {
+	type Output = Date;
+
+	fn add(self, months: Months) -> Date {
+		(self.to_naive_date_expect() + months).into()
+	}
+}
+
+impl Sub<Months> for Date {
+	type Output = Date;
+
+	fn sub(self, months: Months) -> Date {
+		(self.to_naive_date_expect() - months).into()
+	}
+}
+
+// Time elapsed between two dates.
+impl Sub<Date> for Date {
+	type Output = TimeDelta;
+
+	fn sub(self, other: Date) -> TimeDelta {
+		self.to_naive_date_expect() - other.to_naive_date_expect()
+	}
+}
+
+// The alternate flag ('{:#}') formats the date as in ISO-8601: 2024-03-05.
 impl fmt::Display for Date {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		write!(f, "{}/{}/{}", self.year, self.month, self.day)
+		if f.alternate() {
+			write!(
+				f,
+				"{:04}-{:02}-{:02}",
+				self.year,
+				self.month.clone() as u32 + 1,
+				self.day
+			)
+		} else {
+			write!(f, "{}/{}/{}", self.year, self.month, self.day)
+		}
 	}
 }
 
 #[derive(Debug, PartialEq, Eq)]
-pub struct ParseDateError;
+pub struct ParseDateError {
+	pub reason: String,
+	// Position, starting at 1, of the first wrong character of the date.
+	pub column: usize,
+}
 
+impl fmt::Display for ParseDateError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{} (column {})", self.reason, self.column)
+	}
+}
+
+impl std::error::Error for ParseDateError {}
+
+impl Date {
+	// Parses a date typed by the user, which, unlike the dates of the data
+	// files, must exist in the calendar.
+	pub fn parse_valid(s: &str) -> Result<Date, ParseDateError> {
+		let date = s.parse::<Date>()?;
+		if date.is_valid() {
+			return Ok(date);
+		}
+		let day_chars = s.rsplit(['/', '-']).next().unwrap_or("").chars().count();
+		Err(ParseDateError {
+			reason: format!(
+				"day {} does not exist in {} {}",
+				date.day, date.month, date.year
+			),
+			column: s.chars().count() - day_chars + 1,
+		})
+	}
+
+	// Parses dates of the form 2024-03-05.
+	fn from_iso_str(s: &str) -> Result<Date, ParseDateError> {
+		let parts: Vec<&str> = s.split('-').collect();
+		let [year, month, day] = parts.as_slice() else {
+			return Err(ParseDateError {
+				reason: format!("date '{s}' is not of the form year-month-day"),
+				column: 1,
+			});
+		};
+		let month_column = year.chars().count() + 2;
+		let day_column = month_column + month.chars().count() + 1;
+
+		let year_fromstr = year.parse::<u32>().map_err(|_| ParseDateError {
+			reason: format!("'{year}' is not a valid year"),
+			column: 1,
+		})?;
+		let month_fromstr = month
+			.parse::<u32>()
+			.ok()
+			.and_then(|m| m.checked_sub(1))
+			.and_then(Month::from_u32)
+			.ok_or(ParseDateError {
+				reason: format!("'{month}' is not a valid month"),
+				column: month_column,
+			})?;
+		let day_fromstr = day.parse::<u8>().map_err(|_| ParseDateError {
+			reason: format!("'{day}' is not a valid day"),
+			column: day_column,
+		})?;
+
+		Ok(Date {
+			year: year_fromstr,
+			month: month_fromstr,
+			day: day_fromstr,
+		})
+	}
+}
+
+// Dates are read either as 2024/March/5 or as 2024-03-05.
 impl FromStr for Date {
 	type Err = ParseDateError;
 
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
-		let parts: Vec<&str> = s.split("/").collect();
+		if !s.contains('/') && s.contains('-') {
+			return Date::from_iso_str(s);
+		}
+
+		let parts: Vec<&str> = s.split('/').collect();
 		let [year, month, day] = parts.as_slice() else {
-			panic!("Can't segment date '{s}' using '/'")
+			return Err(ParseDateError {
+				reason: format!("date '{s}' is not of the form year/month/day"),
+				column: 1,
+			});
 		};
+		let month_column = year.chars().count() + 2;
+		let day_column = month_column + month.chars().count() + 1;
 
-		let year_fromstr = year.parse::<u32>().map_err(|_| ParseDateError)?;
-		let month_fromstr = month
-			.parse::<Month>()
-			.expect(format!("String '{month}' not valid for a month").as_str());
-		let day_fromstr = day.parse::<u8>().map_err(|_| ParseDateError)?;
+		let year_fromstr = year.parse::<u32>().map_err(|_| ParseDateError {
+			reason: format!("'{year}' is not a valid year"),
+			column: 1,
+		})?;
+		let month_fromstr = month.parse::<Month>().map_err(|_| ParseDateError {
+			reason: format!("'{month}' is not a valid month"),
+			column: month_column,
+		})?;
+		let day_fromstr = day.parse::<u8>().map_err(|_| ParseDateError {
+			reason: format!("'{day}' is not a valid day"),
+			column: day_column,
+		})?;
 
 		Ok(Date {
 			year: year_fromstr,
@@ -46,3 +46,13 @@ pub fn read_correct_month() -> Option<date::Month> {
 		}
 	}
 }
+
+pub fn read_correct_month_or_empty() -> Option<date::Month> {
+	loop {
+		let str = io::read_string_or_empty()?;
+		match str.parse::<date::Month>() {
+			Ok(m) => return Some(m),
+			Err(e) => println!("'{str}' is {e}."),
+		}
+	}
+}